@@ -127,6 +127,7 @@ impl<'a, 'tcx> AutoTraitFinder<'a, 'tcx> {
                     deprecation: None,
                     inner: ImplItem(Impl {
                         unsafety: hir::Unsafety::Normal,
+                        constness: hir::Constness::NotConst,
                         generics: new_generics,
                         provided_trait_methods: Default::default(),
                         trait_: Some(trait_ref.clean(self.cx).get_trait_type().unwrap()),
@@ -567,7 +568,7 @@ impl<'a, 'tcx> AutoTraitFinder<'a, 'tcx> {
                 }
                 WherePredicate::EqPredicate { lhs, rhs } => {
                     match lhs {
-                        Type::QPath { name: ref left_name, ref self_type, ref trait_ } => {
+                        Type::QPath { name: ref left_name, ref self_type, ref trait_, .. } => {
                             let ty = &*self_type;
                             match **trait_ {
                                 Type::ResolvedPath {
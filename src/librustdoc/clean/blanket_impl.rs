@@ -100,6 +100,7 @@ impl<'a, 'tcx> BlanketImplFinder<'a, 'tcx> {
                     deprecation: None,
                     inner: ImplItem(Impl {
                         unsafety: hir::Unsafety::Normal,
+                        constness: hir::Constness::NotConst,
                         generics: (
                             self.cx.tcx.generics_of(impl_def_id),
                             self.cx.tcx.explicit_predicates_of(impl_def_id),
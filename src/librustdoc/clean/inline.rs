@@ -14,8 +14,10 @@ use rustc_mir::const_eval::is_min_const_fn;
 use rustc_span::hygiene::MacroKind;
 use rustc_span::symbol::{sym, Symbol};
 use rustc_span::Span;
+use rustc_trait_selection::traits::supertrait_def_ids;
 
-use crate::clean::{self, Attributes, GetDefId, ToSource, TypeKind};
+use crate::clean::utils::{display_macro_source, parse_macro_rules};
+use crate::clean::{self, Attributes, GetDefId, TypeKind};
 use crate::core::DocContext;
 use crate::doctree;
 
@@ -176,7 +178,9 @@ pub fn record_extern_fqn(cx: &DocContext<'_>, did: DefId, kind: clean::TypeKind)
         let s = elem.data.to_string();
         if !s.is_empty() { Some(s) } else { None }
     });
-    let fqn = if let clean::TypeKind::Macro = kind {
+    // `#[macro_export]` puts `macro_rules!` macros at the root of the crate, wherever they're
+    // defined.
+    let fqn = if kind == clean::TypeKind::Macro && is_macro_rules(cx, did) {
         vec![crate_name, relative.last().expect("relative was empty")]
     } else {
         once(crate_name).chain(relative).collect()
@@ -189,6 +193,23 @@ pub fn record_extern_fqn(cx: &DocContext<'_>, did: DefId, kind: clean::TypeKind)
     }
 }
 
+fn is_macro_rules(cx: &DocContext<'_>, did: DefId) -> bool {
+    if let Some(local) = did.as_local() {
+        let hir_id = cx.tcx.hir().opt_local_def_id_to_hir_id(local);
+        return match hir_id.and_then(|hir_id| cx.tcx.hir().find(hir_id)) {
+            Some(hir::Node::MacroDef(def)) => def.ast.macro_rules,
+            _ => true,
+        };
+    }
+    match cx.enter_resolver(|r| r.cstore().load_macro_untracked(did, cx.sess())) {
+        LoadedMacro::MacroDef(item, _) => match item.kind {
+            ast::ItemKind::MacroDef(ref def) => def.macro_rules,
+            _ => true,
+        },
+        LoadedMacro::ProcMacro(_) => false,
+    }
+}
+
 pub fn build_external_trait(cx: &DocContext<'_>, did: DefId) -> clean::Trait {
     let trait_items =
         cx.tcx.associated_items(did).in_definition_order().map(|item| item.clean(cx)).collect();
@@ -200,6 +221,7 @@ pub fn build_external_trait(cx: &DocContext<'_>, did: DefId) -> clean::Trait {
     let (generics, supertrait_bounds) = separate_supertrait_bounds(generics);
     let is_spotlight = load_attrs(cx, did).clean(cx).has_doc_flag(sym::spotlight);
     let is_auto = cx.tcx.trait_is_auto(did);
+    let supertraits: Vec<_> = supertrait_def_ids(cx.tcx, did).skip(1).collect();
     clean::Trait {
         auto: auto_trait,
         unsafety: cx.tcx.trait_def(did).unsafety,
@@ -208,6 +230,9 @@ pub fn build_external_trait(cx: &DocContext<'_>, did: DefId) -> clean::Trait {
         bounds: supertrait_bounds,
         is_spotlight,
         is_auto,
+        is_object_safe: cx.tcx.is_object_safe(did),
+        is_sealed: clean::utils::is_sealed(cx, &supertraits),
+        supertraits,
     }
 }
 
@@ -218,9 +243,12 @@ fn build_external_function(cx: &DocContext<'_>, did: DefId) -> clean::Function {
         if is_min_const_fn(cx.tcx, did) { hir::Constness::Const } else { hir::Constness::NotConst };
     let asyncness = cx.tcx.asyncness(did);
     let predicates = cx.tcx.predicates_of(did);
-    let (generics, decl) = clean::enter_impl_trait(cx, || {
+    let (mut generics, decl) = clean::enter_impl_trait(cx, || {
         ((cx.tcx.generics_of(did), predicates).clean(cx), (did, sig).clean(cx))
     });
+    if cx.tcx.is_const_fn_raw(did) {
+        mark_maybe_const_bounds(cx, predicates, &mut generics);
+    }
     let (all_types, ret_types) = clean::get_all_types(&generics, &decl, cx);
     clean::Function {
         decl,
@@ -231,6 +259,43 @@ fn build_external_function(cx: &DocContext<'_>, did: DefId) -> clean::Function {
     }
 }
 
+/// The trait bounds of const functions are const unless they're written `?const`, which is only
+/// recorded as the constness of their predicates. This restores the `?const` modifier of the
+/// bounds cleaned from `predicates`.
+fn mark_maybe_const_bounds(
+    cx: &DocContext<'_>,
+    predicates: ty::GenericPredicates<'_>,
+    generics: &mut clean::Generics,
+) {
+    let maybe_const = predicates
+        .predicates
+        .iter()
+        .filter_map(|(p, _)| match p.skip_binders() {
+            ty::PredicateAtom::Trait(pred, hir::Constness::NotConst) => {
+                Some((pred.self_ty().clean(cx), pred.def_id()))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if maybe_const.is_empty() {
+        return;
+    }
+    for pred in &mut generics.where_predicates {
+        if let clean::WherePredicate::BoundPredicate { ty, bounds } = pred {
+            for bound in bounds {
+                if let clean::GenericBound::TraitBound(poly, modifier) = bound {
+                    let trait_did = poly.trait_.def_id();
+                    if *modifier == hir::TraitBoundModifier::None
+                        && maybe_const.iter().any(|(t, did)| t == ty && trait_did == Some(*did))
+                    {
+                        *modifier = hir::TraitBoundModifier::MaybeConst;
+                    }
+                }
+            }
+        }
+    }
+}
+
 fn build_enum(cx: &DocContext<'_>, did: DefId) -> clean::Enum {
     let predicates = cx.tcx.explicit_predicates_of(did);
 
@@ -376,8 +441,8 @@ pub fn build_impl(
         Some(did) => {
             let hir_id = tcx.hir().local_def_id_to_hir_id(did);
             match tcx.hir().expect_item(hir_id).kind {
-                hir::ItemKind::Impl { self_ty, ref generics, ref items, .. } => {
-                    Some((self_ty, generics, items))
+                hir::ItemKind::Impl { self_ty, ref generics, ref items, constness, .. } => {
+                    Some((self_ty, generics, items, constness))
                 }
                 _ => panic!("`DefID` passed to `build_impl` is not an `impl"),
             }
@@ -386,7 +451,7 @@ pub fn build_impl(
     };
 
     let for_ = match impl_item {
-        Some((self_ty, _, _)) => self_ty.clean(cx),
+        Some((self_ty, _, _, _)) => self_ty.clean(cx),
         None => tcx.type_of(did).clean(cx),
     };
 
@@ -408,7 +473,7 @@ pub fn build_impl(
 
     let predicates = tcx.explicit_predicates_of(did);
     let (trait_items, generics) = match impl_item {
-        Some((_, generics, items)) => (
+        Some((_, generics, items, _)) => (
             items.iter().map(|item| tcx.hir().impl_item(item.id).clean(cx)).collect::<Vec<_>>(),
             generics.clean(cx),
         ),
@@ -427,6 +492,8 @@ pub fn build_impl(
         ),
     };
     let polarity = tcx.impl_polarity(did);
+    // The constness of impls isn't recorded in the metadata of other crates.
+    let constness = impl_item.map_or(hir::Constness::NotConst, |(_, _, _, constness)| constness);
     let trait_ = associated_trait.clean(cx).map(|bound| match bound {
         clean::GenericBound::TraitBound(polyt, _) => polyt.trait_,
         clean::GenericBound::Outlives(..) => unreachable!(),
@@ -448,6 +515,7 @@ pub fn build_impl(
     ret.push(clean::Item {
         inner: clean::ImplItem(clean::Impl {
             unsafety: hir::Unsafety::Normal,
+            constness,
             generics,
             provided_trait_methods: provided,
             trait_,
@@ -513,6 +581,7 @@ fn build_module(cx: &DocContext<'_>, did: DefId, visited: &mut FxHashSet<DefId>)
                                     }],
                                 },
                                 did: None,
+                                reexports: Vec::new(),
                             },
                             true,
                         )),
@@ -559,23 +628,23 @@ fn build_macro(cx: &DocContext<'_>, did: DefId, name: Symbol) -> clean::ItemEnum
     let imported_from = cx.tcx.original_crate_name(did.krate);
     match cx.enter_resolver(|r| r.cstore().load_macro_untracked(did, cx.sess())) {
         LoadedMacro::MacroDef(def, _) => {
-            let matchers: Vec<Span> = if let ast::ItemKind::MacroDef(ref def) = def.kind {
-                let tts: Vec<_> = def.body.inner_tokens().into_trees().collect();
-                tts.chunks(4).map(|arm| arm[0].span()).collect()
-            } else {
-                unreachable!()
-            };
-
-            let source = format!(
-                "macro_rules! {} {{\n{}}}",
-                name.clean(cx),
-                matchers
-                    .iter()
-                    .map(|span| { format!("    {} => {{ ... }};\n", span.to_src(cx)) })
-                    .collect::<String>()
-            );
-
-            clean::MacroItem(clean::Macro { source, imported_from: Some(imported_from).clean(cx) })
+            let (matchers, rules, macro_rules): (Vec<Span>, _, _) =
+                if let ast::ItemKind::MacroDef(ref def) = def.kind {
+                    let tts: Vec<_> = def.body.inner_tokens().into_trees().collect();
+                    let matchers = tts.chunks(4).map(|arm| arm[0].span()).collect();
+                    (matchers, parse_macro_rules(&tts), def.macro_rules)
+                } else {
+                    unreachable!()
+                };
+
+            let source = display_macro_source(cx, &name.clean(cx), &matchers, macro_rules);
+
+            clean::MacroItem(clean::Macro {
+                source,
+                imported_from: Some(imported_from).clean(cx),
+                rules,
+                macro_rules,
+            })
         }
         LoadedMacro::ProcMacro(ext) => clean::ProcMacroItem(clean::ProcMacro {
             kind: ext.macro_kind(),
@@ -616,7 +685,7 @@ fn filter_non_trait_generics(trait_did: DefId, mut g: clean::Generics) -> clean:
                 clean::QPath {
                     self_type: box clean::Generic(ref s),
                     trait_: box clean::ResolvedPath { did, .. },
-                    name: ref _name,
+                    ..
                 },
             ref bounds,
         } => !(bounds.is_empty() || *s == "Self" && did == trait_did),
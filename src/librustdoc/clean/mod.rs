@@ -14,18 +14,19 @@ use rustc_attr as attr;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_hir as hir;
 use rustc_hir::def::{CtorKind, DefKind, Res};
-use rustc_hir::def_id::{CrateNum, DefId, CRATE_DEF_INDEX};
+use rustc_hir::def_id::{CrateNum, DefId, CRATE_DEF_INDEX, LOCAL_CRATE};
 use rustc_index::vec::{Idx, IndexVec};
 use rustc_infer::infer::region_constraints::{Constraint, RegionConstraintData};
 use rustc_middle::bug;
 use rustc_middle::middle::resolve_lifetime as rl;
 use rustc_middle::ty::fold::TypeFolder;
 use rustc_middle::ty::subst::{InternalSubsts, Subst};
-use rustc_middle::ty::{self, AdtKind, Lift, Ty, TyCtxt};
+use rustc_middle::ty::{self, AdtKind, DefIdTree, Lift, Ty, TyCtxt};
 use rustc_mir::const_eval::{is_const_fn, is_min_const_fn, is_unstable_const_fn};
 use rustc_span::hygiene::MacroKind;
 use rustc_span::symbol::{kw, sym, Ident, Symbol};
 use rustc_span::{self, Pos};
+use rustc_trait_selection::traits::supertrait_def_ids;
 use rustc_typeck::hir_ty_to_ty;
 
 use std::collections::hash_map::Entry;
@@ -214,6 +215,9 @@ impl Clean<ExternalCrate> for CrateNum {
         ExternalCrate {
             name: cx.tcx.crate_name(*self).to_string(),
             src: krate_src,
+            // Hashing the local crate would mean hashing all of its HIR, which nothing needs.
+            hash: if *self == LOCAL_CRATE { None } else { Some(cx.tcx.crate_hash(*self)) },
+            disambiguator: cx.tcx.crate_disambiguator(*self),
             attrs: cx.tcx.get_attrs(root).clean(cx),
             primitives,
             keywords,
@@ -565,12 +569,22 @@ impl<'tcx> Clean<WherePredicate> for ty::ProjectionPredicate<'tcx> {
 impl<'tcx> Clean<Type> for ty::ProjectionTy<'tcx> {
     fn clean(&self, cx: &DocContext<'_>) -> Type {
         let lifted = self.lift_to_tcx(cx.tcx).unwrap();
-        let trait_ = match lifted.trait_ref(cx.tcx).clean(cx) {
+        let trait_ref = lifted.trait_ref(cx.tcx);
+        let trait_ = match trait_ref.clean(cx) {
             GenericBound::TraitBound(t, _) => t.trait_,
             GenericBound::Outlives(_) => panic!("cleaning a trait got a lifetime"),
         };
+        // The substs of a generic associated type follow the ones of its trait.
+        let own_substs = &lifted.substs[trait_ref.substs.len()..];
+        let args = if own_substs.is_empty() {
+            None
+        } else {
+            let substs = cx.tcx.intern_substs(own_substs);
+            Some(box external_generic_args(cx, None, false, vec![], substs))
+        };
         Type::QPath {
             name: cx.tcx.associated_item(self.item_def_id).ident.name.clean(cx),
+            args,
             self_type: box self.self_ty().clean(cx),
             trait_: box trait_,
         }
@@ -1016,11 +1030,13 @@ impl Clean<Item> for doctree::Trait<'_> {
     fn clean(&self, cx: &DocContext<'_>) -> Item {
         let attrs = self.attrs.clean(cx);
         let is_spotlight = attrs.has_doc_flag(sym::spotlight);
+        let def_id = cx.tcx.hir().local_def_id(self.id).to_def_id();
+        let supertraits: Vec<_> = supertrait_def_ids(cx.tcx, def_id).skip(1).collect();
         Item {
             name: Some(self.name.clean(cx)),
             attrs,
             source: self.span.clean(cx),
-            def_id: cx.tcx.hir().local_def_id(self.id).to_def_id(),
+            def_id,
             visibility: self.vis.clean(cx),
             stability: cx.stability(self.id),
             deprecation: cx.deprecation(self.id).clean(cx),
@@ -1032,6 +1048,9 @@ impl Clean<Item> for doctree::Trait<'_> {
                 bounds: self.bounds.clean(cx),
                 is_spotlight,
                 is_auto: self.is_auto.clean(cx),
+                is_object_safe: cx.tcx.is_object_safe(def_id),
+                is_sealed: is_sealed(cx, &supertraits),
+                supertraits,
             }),
         }
     }
@@ -1105,7 +1124,7 @@ impl Clean<Item> for hir::TraitItem<'_> {
         let local_did = cx.tcx.hir().local_def_id(self.hir_id);
         let inner = match self.kind {
             hir::TraitItemKind::Const(ref ty, default) => {
-                AssocConstItem(ty.clean(cx), default.map(|e| print_const_expr(cx, e)))
+                AssocConstItem(ty.clean(cx), default.map(|e| print_const_expr(cx, e)), None)
             }
             hir::TraitItemKind::Fn(ref sig, hir::TraitFn::Provided(body)) => {
                 let mut m = (sig, &self.generics, body, None).clean(cx);
@@ -1151,7 +1170,8 @@ impl Clean<Item> for hir::ImplItem<'_> {
         let local_did = cx.tcx.hir().local_def_id(self.hir_id);
         let inner = match self.kind {
             hir::ImplItemKind::Const(ref ty, expr) => {
-                AssocConstItem(ty.clean(cx), Some(print_const_expr(cx, expr)))
+                let value = print_evaluated_assoc_const(cx, local_did.to_def_id());
+                AssocConstItem(ty.clean(cx), Some(print_const_expr(cx, expr)), value)
             }
             hir::ImplItemKind::Fn(ref sig, body) => {
                 let mut m = (sig, &self.generics, body, Some(self.defaultness)).clean(cx);
@@ -1186,12 +1206,15 @@ impl Clean<Item> for ty::AssocItem {
         let inner = match self.kind {
             ty::AssocKind::Const => {
                 let ty = cx.tcx.type_of(self.def_id);
-                let default = if self.defaultness.has_value() {
-                    Some(inline::print_inlined_const(cx, self.def_id))
+                let (default, value) = if self.defaultness.has_value() {
+                    (
+                        Some(inline::print_inlined_const(cx, self.def_id)),
+                        print_evaluated_assoc_const(cx, self.def_id),
+                    )
                 } else {
-                    None
+                    (None, None)
                 };
-                AssocConstItem(ty.clean(cx), default)
+                AssocConstItem(ty.clean(cx), default, value)
             }
             ty::AssocKind::Fn => {
                 let generics =
@@ -1277,7 +1300,7 @@ impl Clean<Item> for ty::AssocItem {
                         .filter_map(|pred| {
                             let (name, self_type, trait_, bounds) = match *pred {
                                 WherePredicate::BoundPredicate {
-                                    ty: QPath { ref name, ref self_type, ref trait_ },
+                                    ty: QPath { ref name, ref self_type, ref trait_, .. },
                                     ref bounds,
                                 } => (name, self_type, trait_, bounds),
                                 _ => return None,
@@ -1372,14 +1395,23 @@ impl Clean<Type> for hir::Ty<'_> {
                 // does nothing for `ConstKind::Param`.
                 let ct = ty::Const::from_anon_const(cx.tcx, def_id);
                 let param_env = cx.tcx.param_env(def_id);
-                let length = print_const(cx, ct.eval(cx.tcx, param_env));
-                Array(box ty.clean(cx), length)
+                let ct = ct.eval(cx.tcx, param_env);
+                let value = ct.try_eval_usize(cx.tcx, param_env);
+                Array(box ty.clean(cx), print_const(cx, ct), value)
             }
             TyKind::Tup(ref tys) => Tuple(tys.clean(cx)),
             TyKind::OpaqueDef(item_id, _) => {
                 let item = cx.tcx.hir().expect_item(item_id.id);
                 if let hir::ItemKind::OpaqueTy(ref ty) = item.kind {
-                    ImplTrait(ty.bounds.clean(cx))
+                    let position = match ty.origin {
+                        hir::OpaqueTyOrigin::FnReturn | hir::OpaqueTyOrigin::AsyncFn => {
+                            ImplTraitPosition::Return
+                        }
+                        hir::OpaqueTyOrigin::Binding | hir::OpaqueTyOrigin::Misc => {
+                            ImplTraitPosition::Other
+                        }
+                    };
+                    ImplTrait(ty.bounds.clean(cx), position)
                 } else {
                     unreachable!()
                 }
@@ -1390,7 +1422,7 @@ impl Clean<Type> for hir::Ty<'_> {
                         return new_ty;
                     }
                     if let Some(bounds) = cx.impl_trait_bounds.borrow_mut().remove(&did.into()) {
-                        return ImplTrait(bounds);
+                        return ImplTrait(bounds, ImplTraitPosition::Argument);
                     }
                 }
 
@@ -1501,8 +1533,13 @@ impl Clean<Type> for hir::Ty<'_> {
                     ),
                     segments: trait_segments.clean(cx),
                 };
+                let segment = p.segments.last().expect("segments were empty");
                 Type::QPath {
-                    name: p.segments.last().expect("segments were empty").ident.name.clean(cx),
+                    name: segment.ident.name.clean(cx),
+                    args: segment
+                        .args
+                        .filter(|args| !args.is_empty())
+                        .map(|args| box args.clean(cx)),
                     self_type: box qself.clean(cx),
                     trait_: box resolve_type(cx, trait_path, self.hir_id),
                 }
@@ -1516,6 +1553,10 @@ impl Clean<Type> for hir::Ty<'_> {
                 let trait_path = hir::Path { span: self.span, res, segments: &[] };
                 Type::QPath {
                     name: segment.ident.name.clean(cx),
+                    args: segment
+                        .args
+                        .filter(|args| !args.is_empty())
+                        .map(|args| box args.clean(cx)),
                     self_type: box qself.clean(cx),
                     trait_: box resolve_type(cx, trait_path.clean(cx), self.hir_id),
                 }
@@ -1565,8 +1606,8 @@ impl<'tcx> Clean<Type> for Ty<'tcx> {
             ty::Array(ty, n) => {
                 let mut n = cx.tcx.lift(&n).expect("array lift failed");
                 n = n.eval(cx.tcx, ty::ParamEnv::reveal_all());
-                let n = print_const(cx, n);
-                Array(box ty.clean(cx), n)
+                let value = n.try_eval_usize(cx.tcx, ty::ParamEnv::reveal_all());
+                Array(box ty.clean(cx), print_const(cx, n), value)
             }
             ty::RawPtr(mt) => RawPointer(mt.mutbl, box mt.ty.clean(cx)),
             ty::Ref(r, ty, mutbl) => {
@@ -1666,7 +1707,7 @@ impl<'tcx> Clean<Type> for Ty<'tcx> {
 
             ty::Param(ref p) => {
                 if let Some(bounds) = cx.impl_trait_bounds.borrow_mut().remove(&p.index.into()) {
-                    ImplTrait(bounds)
+                    ImplTrait(bounds, ImplTraitPosition::Argument)
                 } else {
                     Generic(p.name.to_string())
                 }
@@ -1745,7 +1786,12 @@ impl<'tcx> Clean<Type> for Ty<'tcx> {
                 if !has_sized && !bounds.is_empty() {
                     bounds.insert(0, GenericBound::maybe_sized(cx));
                 }
-                ImplTrait(bounds)
+                // The opaque types of return types belong to their function.
+                let position = match cx.tcx.parent(def_id).map(|did| cx.tcx.def_kind(did)) {
+                    Some(DefKind::Fn | DefKind::AssocFn) => ImplTraitPosition::Return,
+                    _ => ImplTraitPosition::Other,
+                };
+                ImplTrait(bounds, position)
             }
 
             ty::Closure(..) | ty::Generator(..) => Tuple(vec![]), // FIXME(pcwalton)
@@ -1894,6 +1940,7 @@ impl Clean<Item> for doctree::Enum<'_> {
 
 impl Clean<Item> for doctree::Variant<'_> {
     fn clean(&self, cx: &DocContext<'_>) -> Item {
+        let def_id = cx.tcx.hir().local_def_id(self.id).to_def_id();
         Item {
             name: Some(self.name.clean(cx)),
             attrs: self.attrs.clean(cx),
@@ -1901,8 +1948,11 @@ impl Clean<Item> for doctree::Variant<'_> {
             visibility: Inherited,
             stability: cx.stability(self.id),
             deprecation: cx.deprecation(self.id).clean(cx),
-            def_id: cx.tcx.hir().local_def_id(self.id).to_def_id(),
-            inner: VariantItem(Variant { kind: self.def.clean(cx) }),
+            def_id,
+            inner: VariantItem(Variant {
+                kind: self.def.clean(cx),
+                discriminant: print_discriminant(cx, def_id),
+            }),
         }
     }
 }
@@ -1911,9 +1961,7 @@ impl Clean<Item> for ty::VariantDef {
     fn clean(&self, cx: &DocContext<'_>) -> Item {
         let kind = match self.ctor_kind {
             CtorKind::Const => VariantKind::CLike,
-            CtorKind::Fn => VariantKind::Tuple(
-                self.fields.iter().map(|f| cx.tcx.type_of(f.did).clean(cx)).collect(),
-            ),
+            CtorKind::Fn => VariantKind::Tuple(self.fields.iter().map(|f| f.clean(cx)).collect()),
             CtorKind::Fictive => VariantKind::Struct(VariantStruct {
                 struct_type: doctree::Plain,
                 fields_stripped: false,
@@ -1939,7 +1987,7 @@ impl Clean<Item> for ty::VariantDef {
             source: cx.tcx.def_span(self.def_id).clean(cx),
             visibility: Inherited,
             def_id: self.def_id,
-            inner: VariantItem(Variant { kind }),
+            inner: VariantItem(Variant { kind, discriminant: print_discriminant(cx, self.def_id) }),
             stability: get_stability(cx, self.def_id),
             deprecation: get_deprecation(cx, self.def_id),
         }
@@ -1951,7 +1999,7 @@ impl Clean<VariantKind> for hir::VariantData<'_> {
         match self {
             hir::VariantData::Struct(..) => VariantKind::Struct(self.clean(cx)),
             hir::VariantData::Tuple(..) => {
-                VariantKind::Tuple(self.fields().iter().map(|x| x.ty.clean(cx)).collect())
+                VariantKind::Tuple(self.fields().iter().map(|x| x.clean(cx)).collect())
             }
             hir::VariantData::Unit(..) => VariantKind::CLike,
         }
@@ -2170,6 +2218,7 @@ impl Clean<Vec<Item>> for doctree::Impl<'_> {
             deprecation: cx.deprecation(self.id).clean(cx),
             inner: ImplItem(Impl {
                 unsafety: self.unsafety,
+                constness: self.constness,
                 generics: self.generics.clean(cx),
                 provided_trait_methods: provided.clone(),
                 trait_,
@@ -2250,14 +2299,37 @@ impl Clean<Vec<Item>> for doctree::Import<'_> {
         // crate in Rust 2018+
         let please_inline = self.attrs.lists(sym::doc).has_word(sym::inline);
         let path = self.path.clean(cx);
+        let res = path.res;
+        // Each name of a `use` statement with braces gets an item (and a `DefId`) of its own.
+        let def_id = cx.tcx.hir().local_def_id(self.id).to_def_id();
+        let reexports = if self.glob || !cx.uses_json_types {
+            Vec::new()
+        } else {
+            reexport_chain(cx, self.path)
+        };
         let inner = if self.glob {
+            let items = if cx.uses_json_types { glob_items(cx, path.res) } else { Vec::new() };
             if !denied {
                 let mut visited = FxHashSet::default();
-                if let Some(items) = inline::try_inline_glob(cx, path.res, &mut visited) {
-                    return items;
+                if let Some(mut inlined) = inline::try_inline_glob(cx, path.res, &mut visited) {
+                    inlined.push(Item {
+                        name: None,
+                        attrs: self.attrs.clean(cx),
+                        source: self.span.clean(cx),
+                        def_id,
+                        visibility: self.vis.clean(cx),
+                        stability: None,
+                        deprecation: None,
+                        inner: ImportItem(Import::new_glob(
+                            resolve_use_source(cx, path, reexports),
+                            items,
+                            false,
+                        )),
+                    });
+                    return inlined;
                 }
             }
-            Import::new_glob(resolve_use_source(cx, path), true)
+            Import::new_glob(resolve_use_source(cx, path, reexports), items, true)
         } else {
             let name = self.name;
             if !please_inline {
@@ -2284,27 +2356,44 @@ impl Clean<Vec<Item>> for doctree::Import<'_> {
                         name: None,
                         attrs: self.attrs.clean(cx),
                         source: self.span.clean(cx),
-                        def_id: cx.tcx.hir().local_def_id(self.id).to_def_id(),
+                        def_id,
                         visibility: self.vis.clean(cx),
                         stability: None,
                         deprecation: None,
                         inner: ImportItem(Import::new_simple(
                             self.name.clean(cx),
-                            resolve_use_source(cx, path),
+                            resolve_use_source(cx, path, reexports),
                             false,
                         )),
                     });
                     return items;
                 }
             }
-            Import::new_simple(name.clean(cx), resolve_use_source(cx, path), true)
+            Import::new_simple(name.clean(cx), resolve_use_source(cx, path, reexports), true)
         };
 
+        // The items that weren't inlined can still be added to the index of the JSON output, while
+        // the module keeps showing the import.
+        if cx.render_options.json_inline_reexports
+            && self.vis.node.is_pub()
+            && (cx.render_options.document_hidden
+                || !self.attrs.lists(sym::doc).has_word(sym::hidden))
+        {
+            let mut visited = FxHashSet::default();
+            let items = if self.glob {
+                inline::try_inline_glob(cx, res, &mut visited)
+            } else {
+                let parent = cx.tcx.parent_module(self.id).to_def_id();
+                inline::try_inline(cx, parent, res, self.name, None, &mut visited)
+            };
+            cx.reexported_items.borrow_mut().extend(items.into_iter().flatten());
+        }
+
         vec![Item {
             name: None,
             attrs: self.attrs.clean(cx),
             source: self.span.clean(cx),
-            def_id: DefId::local(CRATE_DEF_INDEX),
+            def_id,
             visibility: self.vis.clean(cx),
             stability: None,
             deprecation: None,
@@ -2362,20 +2451,15 @@ impl Clean<Item> for doctree::Macro<'_> {
             name: Some(name.clone()),
             attrs: self.attrs.clean(cx),
             source: self.span.clean(cx),
-            visibility: Public,
+            visibility: if self.macro_rules { Public } else { self.vis.clean(cx) },
             stability: cx.stability(self.hid),
             deprecation: cx.deprecation(self.hid).clean(cx),
             def_id: self.def_id,
             inner: MacroItem(Macro {
-                source: format!(
-                    "macro_rules! {} {{\n{}}}",
-                    name,
-                    self.matchers
-                        .iter()
-                        .map(|span| { format!("    {} => {{ ... }};\n", span.to_src(cx)) })
-                        .collect::<String>()
-                ),
+                source: display_macro_source(cx, &name, &self.matchers, self.macro_rules),
                 imported_from: self.imported_from.clean(cx),
+                rules: self.rules.clone(),
+                macro_rules: self.macro_rules,
             }),
         }
     }
@@ -9,11 +9,13 @@ use std::sync::Arc;
 use std::{slice, vec};
 
 use rustc_ast::attr;
+use rustc_ast::crate_disambiguator::CrateDisambiguator;
 use rustc_ast::util::comments::beautify_doc_string;
 use rustc_ast::{self as ast, AttrStyle};
 use rustc_ast::{FloatTy, IntTy, UintTy};
 use rustc_attr::{Stability, StabilityLevel};
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::svh::Svh;
 use rustc_hir as hir;
 use rustc_hir::def::Res;
 use rustc_hir::def_id::{CrateNum, DefId, LOCAL_CRATE};
@@ -51,6 +53,7 @@ pub struct Crate {
     pub name: String,
     pub version: Option<String>,
     pub src: FileName,
+    pub disambiguator: CrateDisambiguator,
     pub module: Option<Item>,
     pub externs: Vec<(CrateNum, ExternalCrate)>,
     pub primitives: Vec<(DefId, PrimitiveType, Attributes)>,
@@ -59,12 +62,20 @@ pub struct Crate {
     pub external_traits: Rc<RefCell<FxHashMap<DefId, Trait>>>,
     pub masked_crates: FxHashSet<CrateNum>,
     pub collapsed: bool,
+    /// The items of other crates that are re-exported without being inlined, for
+    /// `--json-inline-reexports`. They aren't part of any module.
+    pub reexported_items: Vec<Item>,
 }
 
 #[derive(Clone, Debug)]
 pub struct ExternalCrate {
     pub name: String,
     pub src: FileName,
+    /// The stable hash of the crate's metadata. This isn't computed for the local crate.
+    pub hash: Option<Svh>,
+    /// Tells the crate apart from other crates of the same name. Unlike its `CrateNum`, this is
+    /// the same in every compilation linking to the crate.
+    pub disambiguator: CrateDisambiguator,
     pub attrs: Attributes,
     pub primitives: Vec<(DefId, PrimitiveType, Attributes)>,
     pub keywords: Vec<(DefId, String, Attributes)>,
@@ -124,7 +135,7 @@ impl Item {
 
     pub fn is_crate(&self) -> bool {
         match self.inner {
-            StrippedItem(box ModuleItem(Module { is_crate: true, .. }))
+            StrippedItem(box ModuleItem(Module { is_crate: true, .. }), _)
             | ModuleItem(Module { is_crate: true, .. }) => true,
             _ => false,
         }
@@ -185,7 +196,7 @@ impl Item {
         match self.inner {
             StructItem(ref _struct) => Some(_struct.fields_stripped),
             UnionItem(ref union) => Some(union.fields_stripped),
-            VariantItem(Variant { kind: VariantKind::Struct(ref vstruct) }) => {
+            VariantItem(Variant { kind: VariantKind::Struct(ref vstruct), .. }) => {
                 Some(vstruct.fields_stripped)
             }
             _ => None,
@@ -278,10 +289,12 @@ pub enum ItemEnum {
     MacroItem(Macro),
     ProcMacroItem(ProcMacro),
     PrimitiveItem(PrimitiveType),
-    AssocConstItem(Type, Option<String>),
+    /// The type of an associated constant, its default as written, and the value of the default
+    /// if it could be evaluated.
+    AssocConstItem(Type, Option<String>, Option<String>),
     AssocTypeItem(Vec<GenericBound>, Option<Type>),
     /// An item that has been stripped by a rustdoc pass
-    StrippedItem(Box<ItemEnum>),
+    StrippedItem(Box<ItemEnum>, StripReason),
     KeywordItem(String),
 }
 
@@ -293,6 +306,62 @@ impl ItemEnum {
         }
     }
 
+    /// Some items contain others such as structs (for their fields) and Enums
+    /// (for their variants). This method returns those contained items.
+    pub fn inner_items(&self) -> impl Iterator<Item = &Item> {
+        match self {
+            StructItem(s) => s.fields.iter(),
+            UnionItem(u) => u.fields.iter(),
+            VariantItem(Variant { kind: VariantKind::Struct(v), .. }) => v.fields.iter(),
+            VariantItem(Variant { kind: VariantKind::Tuple(fields), .. }) => fields.iter(),
+            EnumItem(e) => e.variants.iter(),
+            TraitItem(t) => t.items.iter(),
+            ImplItem(i) => i.items.iter(),
+            ModuleItem(m) => m.items.iter(),
+            ExternCrateItem(_, _)
+            | ImportItem(_)
+            | FunctionItem(_)
+            | TypedefItem(_, _)
+            | OpaqueTyItem(_)
+            | StaticItem(_)
+            | ConstantItem(_)
+            | TraitAliasItem(_)
+            | TyMethodItem(_)
+            | MethodItem(_)
+            | StructFieldItem(_)
+            | VariantItem(_)
+            | ForeignFunctionItem(_)
+            | ForeignStaticItem(_)
+            | ForeignTypeItem
+            | MacroItem(_)
+            | ProcMacroItem(_)
+            | PrimitiveItem(_)
+            | AssocConstItem(..)
+            | AssocTypeItem(_, _)
+            | StrippedItem(..)
+            | KeywordItem(_) => [].iter(),
+        }
+    }
+
+    /// The generic parameters and where clauses of the item, for the kinds of items that have
+    /// them.
+    pub fn generics(&self) -> Option<&Generics> {
+        Some(match self {
+            StructItem(s) => &s.generics,
+            UnionItem(u) => &u.generics,
+            EnumItem(e) => &e.generics,
+            FunctionItem(f) | ForeignFunctionItem(f) => &f.generics,
+            TypedefItem(t, _) => &t.generics,
+            OpaqueTyItem(t) => &t.generics,
+            TraitItem(t) => &t.generics,
+            TraitAliasItem(t) => &t.generics,
+            ImplItem(i) => &i.generics,
+            TyMethodItem(m) => &m.generics,
+            MethodItem(m) => &m.generics,
+            _ => return None,
+        })
+    }
+
     pub fn as_assoc_kind(&self) -> Option<AssocKind> {
         match *self {
             ItemEnum::AssocConstItem(..) => Some(AssocKind::Const),
@@ -303,6 +372,15 @@ impl ItemEnum {
     }
 }
 
+/// Why an item was turned into a `StrippedItem`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StripReason {
+    /// The item isn't public. Only struct fields and modules are kept around when stripped.
+    Private,
+    /// The item is `#[doc(hidden)]`.
+    Hidden,
+}
+
 #[derive(Clone, Debug)]
 pub struct Module {
     pub items: Vec<Item>,
@@ -956,7 +1034,7 @@ impl FnDecl {
     /// functions.
     pub fn sugared_async_return_type(&self) -> FnRetTy {
         match &self.output {
-            FnRetTy::Return(Type::ImplTrait(bounds)) => match &bounds[0] {
+            FnRetTy::Return(Type::ImplTrait(bounds, _)) => match &bounds[0] {
                 GenericBound::TraitBound(PolyTrait { trait_, .. }, ..) => {
                     let bindings = trait_.bindings().unwrap();
                     FnRetTy::Return(bindings[0].ty().clone())
@@ -1027,6 +1105,13 @@ pub struct Trait {
     pub bounds: Vec<GenericBound>,
     pub is_spotlight: bool,
     pub is_auto: bool,
+    /// Whether `dyn Trait` can be used.
+    pub is_object_safe: bool,
+    /// Every trait this one inherits from, directly or not, each once.
+    pub supertraits: Vec<DefId>,
+    /// Whether one of `supertraits` isn't public, so that the trait can't be implemented outside
+    /// of its crate.
+    pub is_sealed: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -1065,7 +1150,9 @@ pub enum Type {
     BareFunction(Box<BareFunctionDecl>),
     Tuple(Vec<Type>),
     Slice(Box<Type>),
-    Array(Box<Type>, String),
+    // `[T; N]`, along with the value of `N` if it could be evaluated. `N` is a `usize` of the
+    // target, which is never wider than 64 bits, so `try_eval_usize` gives it without loss.
+    Array(Box<Type>, String, Option<u64>),
     Never,
     RawPointer(Mutability, Box<Type>),
     BorrowedRef {
@@ -1074,9 +1161,10 @@ pub enum Type {
         type_: Box<Type>,
     },
 
-    // `<Type as Trait>::Name`
+    // `<Type as Trait>::Name`, or `<Type as Trait>::Name<Args>` for generic associated types
     QPath {
         name: String,
+        args: Option<Box<GenericArgs>>,
         self_type: Box<Type>,
         trait_: Box<Type>,
     },
@@ -1085,7 +1173,18 @@ pub enum Type {
     Infer,
 
     // `impl TraitA + TraitB + ...`
-    ImplTrait(Vec<GenericBound>),
+    ImplTrait(Vec<GenericBound>, ImplTraitPosition),
+}
+
+/// Where an `impl Trait` type was written, which decides what it stands for.
+#[derive(Clone, PartialEq, Eq, Hash, Copy, Debug)]
+pub enum ImplTraitPosition {
+    /// The type of a function argument, which makes the function generic over it.
+    Argument,
+    /// The return type of a function, which is a type picked by the function.
+    Return,
+    /// Anywhere else, like type aliases.
+    Other,
 }
 
 #[derive(Clone, PartialEq, Eq, Hash, Copy, Debug)]
@@ -1222,7 +1321,7 @@ impl Type {
 
     pub fn projection(&self) -> Option<(&Type, DefId, &str)> {
         let (self_, trait_, name) = match self {
-            QPath { ref self_type, ref trait_, ref name } => (self_type, trait_, name),
+            QPath { ref self_type, ref trait_, ref name, .. } => (self_type, trait_, name),
             _ => return None,
         };
         let trait_did = match **trait_ {
@@ -1522,12 +1621,54 @@ pub struct Enum {
 #[derive(Clone, Debug)]
 pub struct Variant {
     pub kind: VariantKind,
+    /// Only set for the variants of enums without fields, where the discriminant is meaningful
+    /// outside of the compiler (e.g. for FFI).
+    pub discriminant: Option<Discriminant>,
+}
+
+/// The layout of a type, for `--json-type-layout`.
+#[derive(Clone, Debug)]
+pub struct TypeLayout {
+    pub size: u64,
+    pub align: u64,
+    /// The offset of each field of a struct or union, in bytes. Empty for enums.
+    pub field_offsets: Vec<(DefId, u64)>,
+    /// The number of invalid values of the largest niche of the type, which enums containing it
+    /// can use to store their discriminant.
+    pub niche_values: Option<u128>,
+}
+
+/// The `extern` block a foreign item is declared in.
+#[derive(Clone, Debug)]
+pub struct ExternBlock {
+    pub def_id: DefId,
+    pub abi: Abi,
+    pub attrs: Vec<ast::Attribute>,
+}
+
+/// The macro invocation an item was generated by.
+#[derive(Clone, Debug)]
+pub struct Expansion {
+    pub macro_def_id: Option<DefId>,
+    pub name: String,
+    pub kind: MacroKind,
+    pub call_site: Span,
+    pub def_site: Span,
+}
+
+#[derive(Clone, Debug)]
+pub struct Discriminant {
+    /// The expression given for the discriminant, if it's explicit.
+    pub expr: Option<String>,
+    /// The value of the discriminant, if it could be evaluated.
+    pub value: Option<String>,
 }
 
 #[derive(Clone, Debug)]
 pub enum VariantKind {
     CLike,
-    Tuple(Vec<Type>),
+    /// The fields are `StructFieldItem`s named by their index, like those of tuple structs.
+    Tuple(Vec<Item>),
     Struct(VariantStruct),
 }
 
@@ -1571,6 +1712,11 @@ impl Path {
     pub fn last_name(&self) -> &str {
         self.segments.last().expect("segments were empty").name.as_str()
     }
+
+    pub fn whole_name(&self) -> String {
+        String::from(if self.global { "::" } else { "" })
+            + &self.segments.iter().map(|s| s.name.clone()).collect::<Vec<_>>().join("::")
+    }
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Hash)]
@@ -1647,6 +1793,8 @@ pub enum ImplPolarity {
 #[derive(Clone, Debug)]
 pub struct Impl {
     pub unsafety: hir::Unsafety,
+    /// Whether this is an `impl const Trait`, whose methods can be called in const contexts.
+    pub constness: hir::Constness,
     pub generics: Generics,
     pub provided_trait_methods: FxHashSet<String>,
     pub trait_: Option<Type>,
@@ -1669,8 +1817,8 @@ impl Import {
         Self { kind: ImportKind::Simple(name), source, should_be_displayed }
     }
 
-    pub fn new_glob(source: ImportSource, should_be_displayed: bool) -> Self {
-        Self { kind: ImportKind::Glob, source, should_be_displayed }
+    pub fn new_glob(source: ImportSource, items: Vec<DefId>, should_be_displayed: bool) -> Self {
+        Self { kind: ImportKind::Glob(items), source, should_be_displayed }
     }
 }
 
@@ -1679,19 +1827,47 @@ pub enum ImportKind {
     // use source as str;
     Simple(String),
     // use source::*;
-    Glob,
+    // with the items it brings into scope, which are only listed for formats using the JSON types
+    Glob(Vec<DefId>),
 }
 
 #[derive(Clone, Debug)]
 pub struct ImportSource {
     pub path: Path,
     pub did: Option<DefId>,
+    /// The modules that re-export the item on the way to `did`, see `utils::reexport_chain`. Only
+    /// filled in for formats using the JSON types.
+    pub reexports: Vec<DefId>,
 }
 
 #[derive(Clone, Debug)]
 pub struct Macro {
     pub source: String,
     pub imported_from: Option<String>,
+    pub rules: Vec<MacroRule>,
+    /// `false` for macros 2.0, which are defined with the `macro` keyword and have a visibility
+    /// and a path like other items.
+    pub macro_rules: bool,
+}
+
+/// One arm of a `macro_rules!` macro, see `utils::parse_macro_rules`.
+#[derive(Clone, Debug)]
+pub struct MacroRule {
+    pub matcher: Vec<MacroMatcher>,
+    /// Whether the arm expands to anything at all.
+    pub has_transcriber: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum MacroMatcher {
+    /// A token that has to be matched literally.
+    Token(String),
+    /// `$name:kind`
+    Fragment { name: String, kind: String },
+    /// `$( ... ) separator op`
+    Repetition { matchers: Vec<MacroMatcher>, separator: Option<String>, op: char },
+    /// Matchers surrounded by a pair of delimiters.
+    Delimited(ast::token::DelimToken, Vec<MacroMatcher>),
 }
 
 #[derive(Clone, Debug)]
@@ -1,14 +1,17 @@
 use crate::clean::auto_trait::AutoTraitFinder;
 use crate::clean::blanket_impl::BlanketImplFinder;
 use crate::clean::{
-    inline, Clean, Crate, Deprecation, ExternalCrate, FnDecl, FnRetTy, Generic, GenericArg,
-    GenericArgs, GenericBound, Generics, GetDefId, ImportSource, Item, ItemEnum, Lifetime,
-    MacroKind, Path, PathSegment, Primitive, PrimitiveType, ResolvedPath, Span, Type, TypeBinding,
-    TypeKind, Visibility, WherePredicate,
+    inline, Clean, Crate, Deprecation, Discriminant, ExternalCrate, FnDecl, FnRetTy, Generic,
+    GenericArg, GenericArgs, GenericBound, Generics, GetDefId, ImportSource, Item, ItemEnum,
+    Lifetime, MacroKind, MacroMatcher, MacroRule, Path, PathSegment, Primitive, PrimitiveType,
+    ResolvedPath, Span, Type, TypeBinding, TypeKind, Visibility, WherePredicate,
 };
 use crate::core::DocContext;
 
 use itertools::Itertools;
+use rustc_ast::token::{BinOpToken, TokenKind};
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
+use rustc_ast_pretty::pprust;
 use rustc_attr::Stability;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_hir as hir;
@@ -60,7 +63,8 @@ pub fn krate(mut cx: &mut DocContext<'_>) -> Crate {
         _ => unreachable!(),
     }
 
-    let ExternalCrate { name, src, primitives, keywords, .. } = LOCAL_CRATE.clean(cx);
+    let ExternalCrate { name, src, disambiguator, primitives, keywords, .. } =
+        LOCAL_CRATE.clean(cx);
     {
         let m = match module.inner {
             ItemEnum::ModuleItem(ref mut m) => m,
@@ -92,12 +96,14 @@ pub fn krate(mut cx: &mut DocContext<'_>) -> Crate {
         name,
         version: None,
         src,
+        disambiguator,
         module: Some(module),
         externs,
         primitives,
         external_traits: cx.external_traits.clone(),
         masked_crates,
         collapsed: false,
+        reexported_items: mem::take(cx.reexported_items.get_mut()),
     }
 }
 
@@ -110,7 +116,7 @@ pub fn get_deprecation(cx: &DocContext<'_>, def_id: DefId) -> Option<Deprecation
     cx.tcx.lookup_deprecation(def_id).clean(cx)
 }
 
-fn external_generic_args(
+pub fn external_generic_args(
     cx: &DocContext<'_>,
     trait_did: Option<DefId>,
     has_self: bool,
@@ -305,13 +311,14 @@ pub fn strip_type(ty: Type) -> Type {
             Type::Tuple(inner_tys.iter().map(|t| strip_type(t.clone())).collect())
         }
         Type::Slice(inner_ty) => Type::Slice(Box::new(strip_type(*inner_ty))),
-        Type::Array(inner_ty, s) => Type::Array(Box::new(strip_type(*inner_ty)), s),
+        Type::Array(inner_ty, s, n) => Type::Array(Box::new(strip_type(*inner_ty)), s, n),
         Type::RawPointer(m, inner_ty) => Type::RawPointer(m, Box::new(strip_type(*inner_ty))),
         Type::BorrowedRef { lifetime, mutability, type_ } => {
             Type::BorrowedRef { lifetime, mutability, type_: Box::new(strip_type(*type_)) }
         }
-        Type::QPath { name, self_type, trait_ } => Type::QPath {
+        Type::QPath { name, args, self_type, trait_ } => Type::QPath {
             name,
+            args,
             self_type: Box::new(strip_type(*self_type)),
             trait_: Box::new(strip_type(*trait_)),
         },
@@ -485,6 +492,42 @@ pub fn print_evaluated_const(cx: &DocContext<'_>, def_id: DefId) -> Option<Strin
     })
 }
 
+/// The value of an associated constant, like [`print_evaluated_const`]. Constants that depend on
+/// generic parameters, including the ones of traits, can't be evaluated.
+pub fn print_evaluated_assoc_const(cx: &DocContext<'_>, def_id: DefId) -> Option<String> {
+    if cx.tcx.generics_of(def_id).requires_monomorphization(cx.tcx) {
+        return None;
+    }
+    print_evaluated_const(cx, def_id)
+}
+
+/// Whether a trait with these supertraits is sealed, which is commonly done with a supertrait
+/// that's `pub` but in a private module.
+pub fn is_sealed(cx: &DocContext<'_>, supertraits: &[DefId]) -> bool {
+    let access_levels = &cx.renderinfo.borrow().access_levels;
+    supertraits.iter().any(|&did| !access_levels.is_public(did))
+}
+
+/// The discriminant of the variant `did`, if its enum has no fields in any of its variants.
+pub fn print_discriminant(cx: &DocContext<'_>, did: DefId) -> Option<Discriminant> {
+    let adt = cx.tcx.adt_def(cx.tcx.parent(did)?);
+    if !adt.is_payloadfree() {
+        return None;
+    }
+    let index = adt.variant_index_with_id(did);
+    let expr = match adt.variants[index].discr {
+        ty::VariantDiscr::Explicit(expr_did) => Some(inline::print_inlined_const(cx, expr_did)),
+        ty::VariantDiscr::Relative(_) => None,
+    };
+    // The value is counted from the last explicit discriminant, which might not evaluate (e.g.
+    // if it overflows). `discriminant_for_variant` would silently fall back to zero then.
+    let value = match adt.discriminant_def_for_variant(index) {
+        (Some(expr_did), _) if cx.tcx.const_eval_poly(expr_did).is_err() => None,
+        _ => Some(adt.discriminant_for_variant(cx.tcx, index).to_string()),
+    };
+    Some(Discriminant { expr, value })
+}
+
 fn format_integer_with_underscore_sep(num: &str) -> String {
     let num_chars: Vec<_> = num.chars().collect();
     let num_start_index = if num_chars.get(0) == Some(&'-') { 1 } else { 0 };
@@ -615,10 +658,260 @@ pub fn register_res(cx: &DocContext<'_>, res: Res) -> DefId {
     did
 }
 
-pub fn resolve_use_source(cx: &DocContext<'_>, path: Path) -> ImportSource {
+pub fn resolve_use_source(cx: &DocContext<'_>, path: Path, reexports: Vec<DefId>) -> ImportSource {
     ImportSource {
         did: if path.res.opt_def_id().is_none() { None } else { Some(register_res(cx, path.res)) },
         path,
+        reexports,
+    }
+}
+
+/// The modules re-exporting the item `path` resolves to that the `use` statement goes through,
+/// in order: `use a::B;` goes through `a` if `a` has `pub use b::B;` rather than defining `B`.
+///
+/// The chain stops at the first module of another crate, because their metadata doesn't record
+/// where the re-exports come from.
+pub fn reexport_chain(cx: &DocContext<'_>, path: &hir::Path<'_>) -> Vec<DefId> {
+    let mut chain = Vec::new();
+    if let Some((module, name)) = lookup(path) {
+        if !follow_reexports(cx, module, name, path.res, &mut chain) {
+            chain.clear();
+        }
+    }
+    chain
+}
+
+/// The item the last segment of `path` is looked up in, and the name looked up there.
+fn lookup(path: &hir::Path<'_>) -> Option<(DefId, Symbol)> {
+    let (last, rest) = path.segments.split_last()?;
+    match rest.last()?.res? {
+        Res::Def(_, did) => Some((did, last.ident.name)),
+        _ => None,
+    }
+}
+
+/// Pushes the modules on the way from `name` in `module` to `res` onto `chain`. Returns whether
+/// the definition of `res` (or a module of another crate) was reached.
+fn follow_reexports(
+    cx: &DocContext<'_>,
+    module: DefId,
+    name: Symbol,
+    res: Res,
+    chain: &mut Vec<DefId>,
+) -> bool {
+    if res.opt_def_id().and_then(|did| cx.tcx.parent(did)) == Some(module) {
+        return true;
+    }
+    if chain.contains(&module) || cx.tcx.def_kind(module) != DefKind::Mod {
+        return false;
+    }
+    chain.push(module);
+    let local = match module.as_local() {
+        Some(local) => local,
+        None => return true,
+    };
+    let hir = cx.tcx.hir();
+    let mut next = Vec::new();
+    let mut globs = Vec::new();
+    for id in hir.get_module(local).0.item_ids {
+        let item = hir.item(id.id);
+        match item.kind {
+            hir::ItemKind::Use(path, hir::UseKind::Single)
+                if item.ident.name == name && path.res == res =>
+            {
+                next.extend(lookup(path))
+            }
+            hir::ItemKind::Use(path, hir::UseKind::Glob) => {
+                globs.extend(path.res.opt_def_id().map(|did| (did, name)))
+            }
+            _ => {}
+        }
+    }
+    // Names imported on their own shadow the ones from globs.
+    next.extend(globs);
+    if next.into_iter().any(|(module, name)| follow_reexports(cx, module, name, res, chain)) {
+        return true;
+    }
+    chain.pop();
+    false
+}
+
+/// The public items a glob import of `res` brings into scope: the items the module defines or
+/// re-exports, or the variants of the enum. Names the importing module defines itself shadow them.
+pub fn glob_items(cx: &DocContext<'_>, res: Res) -> Vec<DefId> {
+    let mut items = Vec::new();
+    match res {
+        Res::Def(DefKind::Enum, did) => {
+            items.extend(cx.tcx.adt_def(did).variants.iter().map(|v| v.def_id));
+        }
+        Res::Def(DefKind::Mod, did) => match did.as_local() {
+            Some(local) => {
+                let hir = cx.tcx.hir();
+                for id in hir.get_module(local).0.item_ids {
+                    let item = hir.item(id.id);
+                    match item.kind {
+                        // `use` and `extern crate` are among the re-exports below.
+                        hir::ItemKind::Use(..)
+                        | hir::ItemKind::ExternCrate(..)
+                        | hir::ItemKind::Impl { .. }
+                        | hir::ItemKind::GlobalAsm(..) => {}
+                        hir::ItemKind::ForeignMod(ref fm) => items.extend(
+                            fm.items
+                                .iter()
+                                .filter(|i| i.vis.node.is_pub())
+                                .map(|i| hir.local_def_id(i.hir_id).to_def_id()),
+                        ),
+                        _ if item.vis.node.is_pub() => {
+                            items.push(hir.local_def_id(item.hir_id).to_def_id())
+                        }
+                        _ => {}
+                    }
+                }
+                let exports = cx.tcx.module_exports(local).unwrap_or(&[]);
+                for export in exports.iter().filter(|e| e.vis == ty::Visibility::Public) {
+                    // Something re-exported in several namespaces is listed once.
+                    if let Some(did) = export.res.opt_def_id() {
+                        if !items.contains(&did) {
+                            items.push(did);
+                        }
+                    }
+                }
+            }
+            None => {
+                for child in cx.tcx.item_children(did).iter() {
+                    if child.vis != ty::Visibility::Public {
+                        continue;
+                    }
+                    if let Some(did) = child.res.opt_def_id() {
+                        if !items.contains(&did) {
+                            items.push(did);
+                        }
+                    }
+                }
+            }
+        },
+        _ => {}
+    }
+    items
+}
+
+/// The signature of a macro as shown in the docs, with the transcribers of its rules elided.
+pub fn display_macro_source(
+    cx: &DocContext<'_>,
+    name: &str,
+    matchers: &[rustc_span::Span],
+    macro_rules: bool,
+) -> String {
+    if macro_rules {
+        format!(
+            "macro_rules! {} {{\n{}}}",
+            name,
+            matchers
+                .iter()
+                .map(|span| format!("    {} => {{ ... }};\n", span.to_src(cx)))
+                .collect::<String>()
+        )
+    } else if let [matcher] = matchers {
+        format!("macro {}{} {{ ... }}", name, matcher.to_src(cx))
+    } else {
+        format!(
+            "macro {} {{\n{}}}",
+            name,
+            matchers
+                .iter()
+                .map(|span| format!("    {} => {{ ... }},\n", span.to_src(cx)))
+                .collect::<String>()
+        )
+    }
+}
+
+/// Splits the body of a `macro_rules!` macro into its arms, given as `matcher => transcriber;`.
+pub fn parse_macro_rules(tts: &[TokenTree]) -> Vec<MacroRule> {
+    tts.chunks(4)
+        .map(|arm| {
+            let matcher = match &arm[0] {
+                TokenTree::Delimited(_, _, stream) => macro_matchers(stream),
+                tt => macro_matchers(&tt.clone().into()),
+            };
+            let has_transcriber = match arm.get(2) {
+                Some(TokenTree::Delimited(_, _, stream)) => !stream.is_empty(),
+                _ => false,
+            };
+            MacroRule { matcher, has_transcriber }
+        })
+        .collect()
+}
+
+fn macro_matchers(stream: &TokenStream) -> Vec<MacroMatcher> {
+    let tts: Vec<_> = stream.trees().collect();
+    let mut matchers = Vec::new();
+    let mut i = 0;
+    while i < tts.len() {
+        match (&tts[i], tts.get(i + 1)) {
+            (TokenTree::Token(dollar), Some(TokenTree::Delimited(_, _, inner)))
+                if dollar.kind == TokenKind::Dollar =>
+            {
+                // `$( ... ) op` or `$( ... ) separator op`.
+                let (separator, op) = match (tts.get(i + 2), tts.get(i + 3)) {
+                    (Some(TokenTree::Token(tok)), _) if kleene_op(&tok.kind).is_some() => {
+                        i += 3;
+                        (None, kleene_op(&tok.kind))
+                    }
+                    (Some(TokenTree::Token(sep)), Some(TokenTree::Token(tok)))
+                        if kleene_op(&tok.kind).is_some() =>
+                    {
+                        i += 4;
+                        (Some(pprust::token_to_string(sep)), kleene_op(&tok.kind))
+                    }
+                    _ => {
+                        i += 2;
+                        (None, None)
+                    }
+                };
+                matchers.push(MacroMatcher::Repetition {
+                    matchers: macro_matchers(inner),
+                    separator,
+                    op: op.unwrap_or('*'),
+                });
+            }
+            (TokenTree::Token(dollar), Some(TokenTree::Token(name)))
+                if dollar.kind == TokenKind::Dollar =>
+            {
+                let name = pprust::token_to_string(name);
+                match (tts.get(i + 2), tts.get(i + 3)) {
+                    (Some(TokenTree::Token(colon)), Some(TokenTree::Token(kind)))
+                        if colon.kind == TokenKind::Colon =>
+                    {
+                        let kind = pprust::token_to_string(kind);
+                        matchers.push(MacroMatcher::Fragment { name, kind });
+                        i += 4;
+                    }
+                    // `$crate`, or a metavariable without a fragment specifier.
+                    _ => {
+                        matchers.push(MacroMatcher::Token(format!("${}", name)));
+                        i += 2;
+                    }
+                }
+            }
+            (TokenTree::Token(tok), _) => {
+                matchers.push(MacroMatcher::Token(pprust::token_to_string(tok)));
+                i += 1;
+            }
+            (TokenTree::Delimited(_, delim, inner), _) => {
+                matchers.push(MacroMatcher::Delimited(*delim, macro_matchers(inner)));
+                i += 1;
+            }
+        }
+    }
+    matchers
+}
+
+fn kleene_op(kind: &TokenKind) -> Option<char> {
+    match kind {
+        TokenKind::BinOp(BinOpToken::Star) => Some('*'),
+        TokenKind::BinOp(BinOpToken::Plus) => Some('+'),
+        TokenKind::Question => Some('?'),
+        _ => None,
     }
 }
 
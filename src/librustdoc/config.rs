@@ -4,9 +4,11 @@ use std::ffi::OsStr;
 use std::fmt;
 use std::path::PathBuf;
 
+use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
-use rustc_hir::def_id::DefId;
+use rustc_hir::def_id::{DefId, DefPathHash};
 use rustc_middle::middle::privacy::AccessLevels;
+use rustc_middle::ty;
 use rustc_session::config::{self, parse_crate_types_from_list, parse_externs, CrateType};
 use rustc_session::config::{
     build_codegen_options, build_debugging_options, get_cmd_lint_options, host_triple,
@@ -19,8 +21,10 @@ use rustc_session::search_paths::SearchPath;
 use rustc_span::edition::{Edition, DEFAULT_EDITION};
 use rustc_target::spec::TargetTriple;
 
+use crate::clean;
 use crate::core::new_handler;
 use crate::externalfiles::ExternalHtml;
+use crate::formats::registry;
 use crate::html;
 use crate::html::markdown::IdMap;
 use crate::html::render::StylePath;
@@ -33,6 +37,14 @@ use crate::theme;
 pub enum OutputFormat {
     Json,
     Html,
+    Msgpack,
+    Cbor,
+    MsgpackIndexed,
+    Markdown,
+    Man,
+    Lsif,
+    Dot,
+    Sqlite,
 }
 
 impl OutputFormat {
@@ -42,16 +54,57 @@ impl OutputFormat {
             _ => false,
         }
     }
+
+    /// Whether this is one of the formats written by the JSON backend: JSON itself, or another
+    /// encoding of the same data.
+    pub fn uses_json_types(&self) -> bool {
+        match self {
+            OutputFormat::Json
+            | OutputFormat::Msgpack
+            | OutputFormat::Cbor
+            | OutputFormat::MsgpackIndexed
+            | OutputFormat::Lsif
+            | OutputFormat::Sqlite => true,
+            OutputFormat::Html | OutputFormat::Markdown | OutputFormat::Man | OutputFormat::Dot => {
+                false
+            }
+        }
+    }
 }
 
 impl TryFrom<&str> for OutputFormat {
     type Error = String;
 
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        registry::backends()
+            .find(|b| b.name == value)
+            .map(|b| b.format)
+            .ok_or_else(|| format!("unknown output format `{}`", value))
+    }
+}
+
+/// Compression applied to the file written by the JSON backend.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum JsonCompression {
+    Gzip,
+}
+
+impl JsonCompression {
+    /// The extension appended to the name of the compressed file.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            JsonCompression::Gzip => "gz",
+        }
+    }
+}
+
+impl TryFrom<&str> for JsonCompression {
+    type Error = String;
+
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         match value {
-            "json" => Ok(OutputFormat::Json),
-            "html" => Ok(OutputFormat::Html),
-            _ => Err(format!("unknown output format `{}`", value)),
+            "gzip" => Ok(JsonCompression::Gzip),
+            _ => Err(format!("unknown JSON compression `{}`, expected `gzip`", value)),
         }
     }
 }
@@ -143,8 +196,9 @@ pub struct Options {
     pub crate_version: Option<String>,
     /// Collected options specific to outputting final pages.
     pub render_options: RenderOptions,
-    /// Output format rendering (used only for "show-coverage" option for the moment)
-    pub output_format: Option<OutputFormat>,
+    /// The formats to render the documentation in, in order. This is empty if none was given, in
+    /// which case HTML is rendered (or a table, for "show-coverage").
+    pub output_formats: Vec<OutputFormat>,
 }
 
 impl fmt::Debug for Options {
@@ -250,6 +304,56 @@ pub struct RenderOptions {
     pub document_private: bool,
     /// Document items that have `doc(hidden)`.
     pub document_hidden: bool,
+    /// If true, the JSON output is indented for human readers instead of being written on a
+    /// single line.
+    pub json_pretty: bool,
+    /// If true, the JSON output is written as newline-delimited JSON: one item per line as soon as
+    /// it's converted, followed by the crate metadata.
+    pub json_lines: bool,
+    /// If present, the JSON output file is compressed with the given format.
+    pub json_compression: Option<JsonCompression>,
+    /// If true, the JSON output uses sequential integers as item IDs, and the IDs they replace are
+    /// written to a separate file.
+    pub json_compact_ids: bool,
+    /// If true, a JSON Schema describing the output of the JSON backend is written next to it.
+    pub emit_schema: bool,
+    /// If true, the JSON output includes the doctests of each item.
+    pub json_doctests: bool,
+    /// If true, the JSON output includes the docs of each item rendered to HTML.
+    pub json_render_docs: bool,
+    /// If true, the JSON output includes the Markdown tree of the docs of each item.
+    pub json_docs_ast: bool,
+    /// If true, the JSON output includes the layout of the types that have one.
+    pub json_type_layout: bool,
+    /// If true, the items of other crates that are re-exported without being inlined are added to
+    /// the index of the JSON output, while their modules still list the imports.
+    pub json_inline_reexports: bool,
+    /// If true, the index of the JSON output only has the C API of the crate.
+    pub json_ffi_only: bool,
+    /// If present, the index of the JSON output only has the items of these kinds.
+    pub json_kinds: Option<Vec<crate::json::types::ItemKind>>,
+    /// If present, the index of the JSON output only has the items whose path starts with this
+    /// one. The first segment can be `crate` rather than the name of the crate.
+    pub json_only_path: Option<Vec<String>>,
+    /// If true, the docs and intra-doc links of items are left empty in the JSON output.
+    pub json_no_docs: bool,
+    /// If true, the JSON output only describes the public API of the crate: no docs, attributes,
+    /// source locations or stripped items.
+    pub json_api_surface: bool,
+    /// If true, the index of the JSON output is split into one file per module.
+    pub json_shard: bool,
+    /// If true, the docs of items are written to a separate file rather than in the JSON output.
+    pub json_docs_file: bool,
+    /// If true, the external crates and items are added to a file shared by the crates documented
+    /// into the same directory rather than written in the JSON output.
+    pub json_externs_file: bool,
+    /// If true, the items that didn't change since the last run are taken from its JSON output.
+    pub json_incremental: bool,
+    /// An earlier JSON output of the crate to compare the items with.
+    pub json_diff: Option<PathBuf>,
+    /// If true, the changes found with `json_diff` are classified by the version bump they call
+    /// for.
+    pub json_semver: bool,
 }
 
 /// Temporary storage for data obtained during `RustdocVisitor::clean()`.
@@ -263,7 +367,33 @@ pub struct RenderInfo {
     pub deref_trait_did: Option<DefId>,
     pub deref_mut_trait_did: Option<DefId>,
     pub owned_box_did: Option<DefId>,
+    /// The format being rendered. Each renderer gets its own copy of the `RenderInfo` with this
+    /// set when several formats are rendered at once.
     pub output_format: Option<OutputFormat>,
+    /// The stable hashes of the paths of every `DefId` that can end up in the output. Only filled
+    /// in for formats using the JSON types, which derive item IDs from them.
+    pub def_path_hashes: FxHashMap<DefId, DefPathHash>,
+    /// The target triple the crate is documented for.
+    pub target_triple: String,
+    /// Whether the crate is a proc-macro crate.
+    pub is_proc_macro_crate: bool,
+    /// The documentation coverage of every counted local item. Only filled in for formats using
+    /// the JSON types.
+    pub doc_coverage: FxHashMap<DefId, passes::ItemCoverage>,
+    /// The layout of every local type that has one. Only filled in with `--json-type-layout`.
+    pub type_layouts: FxHashMap<DefId, clean::TypeLayout>,
+    /// The variance of the generic parameters of every struct, enum and union, by name. Only
+    /// filled in for formats using the JSON types.
+    pub variances: FxHashMap<DefId, FxHashMap<String, ty::Variance>>,
+    /// The `extern` block of every local foreign item. Only filled in for formats using the JSON
+    /// types.
+    pub extern_blocks: FxHashMap<DefId, clean::ExternBlock>,
+    /// The macro invocation each local item generated by a macro comes from. Only filled in for
+    /// formats using the JSON types.
+    pub expansions: FxHashMap<DefId, clean::Expansion>,
+    /// The stable hash of the HIR of every local item, combined with everything from outside of
+    /// the crate its output depends on. Only filled in with `--json-incremental`.
+    pub hir_hashes: FxHashMap<DefId, Fingerprint>,
 }
 
 impl Options {
@@ -503,22 +633,43 @@ impl Options {
             }
         };
 
-        let output_format = match matches.opt_str("output-format") {
-            Some(s) => match OutputFormat::try_from(s.as_str()) {
-                Ok(o) => {
-                    if o.is_json() && !(show_coverage || nightly_options::is_nightly_build()) {
-                        diag.struct_err("json output format isn't supported for doc generation")
-                            .emit();
-                        return Err(1);
-                    } else if !o.is_json() && show_coverage {
-                        diag.struct_err(
-                            "html output format isn't supported for the --show-coverage option",
-                        )
-                        .emit();
-                        return Err(1);
-                    }
-                    Some(o)
+        let mut output_formats = Vec::new();
+        for s in matches.opt_str("output-format").iter().flat_map(|s| s.split(',')) {
+            let o = match OutputFormat::try_from(s) {
+                Ok(o) => o,
+                Err(e) => {
+                    diag.struct_err(&e).emit();
+                    return Err(1);
                 }
+            };
+            if o.is_json() && !(show_coverage || nightly_options::is_nightly_build()) {
+                diag.struct_err("json output format isn't supported for doc generation").emit();
+                return Err(1);
+            } else if !o.is_json() && show_coverage {
+                diag.struct_err(&format!(
+                    "{} output format isn't supported for the --show-coverage option",
+                    s
+                ))
+                .emit();
+                return Err(1);
+            } else if o != OutputFormat::Html
+                && !o.is_json()
+                && !nightly_options::is_nightly_build()
+            {
+                diag.struct_err(&format!("{} output format isn't supported for doc generation", s))
+                    .emit();
+                return Err(1);
+            } else if output_formats.contains(&o) {
+                diag.struct_err(&format!("{} output format was given more than once", s)).emit();
+                return Err(1);
+            }
+            output_formats.push(o);
+        }
+        let uses_json_types = output_formats.iter().any(|o| o.uses_json_types());
+        let emit_schema = matches.opt_present("emit-schema");
+        let json_compression = match matches.opt_str("json-compress") {
+            Some(s) => match JsonCompression::try_from(s.as_str()) {
+                Ok(c) => Some(c),
                 Err(e) => {
                     diag.struct_err(&e).emit();
                     return Err(1);
@@ -552,6 +703,141 @@ impl Options {
         let enable_per_target_ignores = matches.opt_present("enable-per-target-ignores");
         let document_private = matches.opt_present("document-private-items");
         let document_hidden = matches.opt_present("document-hidden-items");
+        let json_pretty = matches.opt_present("json-pretty");
+        let json_lines = matches.opt_present("json-lines");
+        let json_compact_ids = matches.opt_present("json-compact-ids");
+        let json_doctests = matches.opt_present("json-doctests");
+        let json_render_docs = matches.opt_present("json-render-docs");
+        let json_docs_ast = matches.opt_present("json-docs-ast");
+        let json_type_layout = matches.opt_present("json-type-layout");
+        let json_inline_reexports = matches.opt_present("json-inline-reexports");
+        let json_ffi_only = matches.opt_present("json-ffi-only");
+        let json_kinds = match matches.opt_str("json-kinds") {
+            Some(kinds) => {
+                let mut parsed = Vec::new();
+                for kind in kinds.split(',').map(str::trim) {
+                    // The kinds are named as they're serialized in the output.
+                    match serde_json::from_value(serde_json::Value::String(kind.to_string())) {
+                        Ok(kind) => parsed.push(kind),
+                        Err(_) => {
+                            let msg = format!("unknown item kind `{}` in --json-kinds", kind);
+                            diag.struct_err(&msg).emit();
+                            return Err(1);
+                        }
+                    }
+                }
+                Some(parsed)
+            }
+            None => None,
+        };
+        let json_only_path = match matches.opt_str("json-only-path") {
+            Some(path) => {
+                let segments: Vec<_> = path.split("::").map(|s| s.trim().to_string()).collect();
+                if segments.iter().any(|s| s.is_empty()) {
+                    let msg = format!("invalid path `{}` in --json-only-path", path);
+                    diag.struct_err(&msg).emit();
+                    return Err(1);
+                }
+                Some(segments)
+            }
+            None => None,
+        };
+        let json_no_docs = matches.opt_present("json-no-docs");
+        let json_api_surface = matches.opt_present("json-api-surface");
+        let json_shard = matches.opt_present("json-shard");
+        let json_docs_file = matches.opt_present("json-docs-file");
+        let json_externs_file = matches.opt_present("json-externs-file");
+        let json_incremental = matches.opt_present("json-incremental");
+        let json_diff = matches.opt_str("json-diff").map(PathBuf::from);
+        let json_semver = matches.opt_present("json-semver");
+
+        // Each flag is only accepted along with the flag or output format it refines.
+        let json = ("--output-format json", uses_json_types);
+        let requirements = [
+            (("--json-pretty", json_pretty), json),
+            (("--json-lines", json_lines), json),
+            (("--json-compress", json_compression.is_some()), json),
+            (("--emit-schema", emit_schema), json),
+            (("--json-compact-ids", json_compact_ids), json),
+            (("--json-doctests", json_doctests), json),
+            (("--json-render-docs", json_render_docs), json),
+            (("--json-docs-ast", json_docs_ast), json),
+            (("--json-type-layout", json_type_layout), json),
+            (("--json-inline-reexports", json_inline_reexports), json),
+            (("--json-ffi-only", json_ffi_only), json),
+            (("--json-kinds", json_kinds.is_some()), json),
+            (("--json-only-path", json_only_path.is_some()), json),
+            (("--json-no-docs", json_no_docs), json),
+            (("--json-api-surface", json_api_surface), json),
+            (("--json-shard", json_shard), json),
+            (("--json-docs-file", json_docs_file), json),
+            (("--json-externs-file", json_externs_file), json),
+            (("--json-incremental", json_incremental), json),
+            (("--json-diff", json_diff.is_some()), json),
+            (("--json-semver", json_semver), ("--json-diff", json_diff.is_some())),
+        ];
+        for ((flag, present), (required, required_present)) in requirements.iter() {
+            if *present && !*required_present {
+                diag.struct_err(&format!("{} requires {}", flag, required)).emit();
+                return Err(1);
+            }
+        }
+
+        // Pairs of flags, or of a flag and an output format, that can't be used together.
+        let has = |o| output_formats.contains(&o);
+        let lines = ("--json-lines", json_lines);
+        let shard = ("--json-shard", json_shard);
+        let compress = ("--json-compress", json_compression.is_some());
+        let compact_ids = ("--json-compact-ids", json_compact_ids);
+        let api_surface = ("--json-api-surface", json_api_surface);
+        let incremental = ("--json-incremental", json_incremental);
+        let diff = ("--json-diff", json_diff.is_some());
+        let msgpack_indexed =
+            ("--output-format msgpack-indexed", has(OutputFormat::MsgpackIndexed));
+        let lsif = ("--output-format lsif", has(OutputFormat::Lsif));
+        let sqlite = ("--output-format sqlite", has(OutputFormat::Sqlite));
+        let conflicts = [
+            // The public API is what's left once the private and hidden items are stripped.
+            (("--document-private-items", document_private), api_surface),
+            (("--document-hidden-items", document_hidden), api_surface),
+            (lines, shard),
+            // Compact IDs are only unique within the output of one crate.
+            (compact_ids, ("--json-externs-file", json_externs_file)),
+            // Items are written as soon as they're converted, before it's known which ones are
+            // used.
+            (lines, ("--json-ffi-only", json_ffi_only)),
+            // These formats are written from the whole index once every item was converted. The
+            // items of the msgpack-indexed format are also read at the offsets of its table.
+            (lines, msgpack_indexed),
+            (lines, lsif),
+            (lines, sqlite),
+            (shard, msgpack_indexed),
+            (shard, lsif),
+            (shard, sqlite),
+            (compress, msgpack_indexed),
+            // The last output is read back as a single JSON file whose IDs are the ones of this
+            // run.
+            (lines, incremental),
+            (shard, incremental),
+            (compress, incremental),
+            (compact_ids, incremental),
+            (("--json-docs-file", json_docs_file), incremental),
+            (incremental, ("--output-format msgpack", has(OutputFormat::Msgpack))),
+            (incremental, ("--output-format cbor", has(OutputFormat::Cbor))),
+            (incremental, msgpack_indexed),
+            (incremental, lsif),
+            (incremental, sqlite),
+            // Items are matched up by ID, and need to be in the index once every one was
+            // converted.
+            (lines, diff),
+            (compact_ids, diff),
+        ];
+        for ((flag, present), (other, other_present)) in conflicts.iter() {
+            if *present && *other_present {
+                diag.struct_err(&format!("{} isn't supported with {}", flag, other)).emit();
+                return Err(1);
+            }
+        }
 
         let (lint_opts, describe_lints, lint_cap) = get_cmd_lint_options(matches, error_format);
 
@@ -607,8 +893,29 @@ impl Options {
                 generate_search_filter,
                 document_private,
                 document_hidden,
+                json_pretty,
+                json_lines,
+                json_compression,
+                json_compact_ids,
+                emit_schema,
+                json_doctests,
+                json_render_docs,
+                json_docs_ast,
+                json_type_layout,
+                json_inline_reexports,
+                json_ffi_only,
+                json_kinds,
+                json_only_path,
+                json_no_docs,
+                json_api_surface,
+                json_shard,
+                json_docs_file,
+                json_externs_file,
+                json_incremental,
+                json_diff,
+                json_semver,
             },
-            output_format,
+            output_formats,
         })
     }
 
@@ -1,12 +1,16 @@
 use rustc_attr as attr;
+use rustc_data_structures::fingerprint::Fingerprint;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
 use rustc_data_structures::sync::{self, Lrc};
 use rustc_driver::abort_on_err;
 use rustc_errors::emitter::{Emitter, EmitterWriter};
 use rustc_errors::json::JsonEmitter;
 use rustc_feature::UnstableFeatures;
 use rustc_hir::def::{Namespace::TypeNS, Res};
-use rustc_hir::def_id::{CrateNum, DefId, DefIndex, LocalDefId, CRATE_DEF_INDEX, LOCAL_CRATE};
+use rustc_hir::def_id::{
+    CrateNum, DefId, DefIndex, DefPathHash, LocalDefId, CRATE_DEF_INDEX, LOCAL_CRATE,
+};
 use rustc_hir::HirId;
 use rustc_hir::{
     intravisit::{self, NestedVisitorMap, Visitor},
@@ -14,26 +18,30 @@ use rustc_hir::{
 };
 use rustc_interface::interface;
 use rustc_middle::hir::map::Map;
+use rustc_middle::ich::StableHashingContext;
 use rustc_middle::middle::cstore::CrateStore;
 use rustc_middle::middle::privacy::AccessLevels;
-use rustc_middle::ty::{Ty, TyCtxt};
+use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_resolve as resolve;
 use rustc_session::config::{self, CrateType, ErrorOutputType};
 use rustc_session::lint;
 use rustc_session::DiagnosticOutput;
 use rustc_session::Session;
+use rustc_span::hygiene::ExpnKind;
 use rustc_span::source_map;
 use rustc_span::symbol::sym;
 use rustc_span::DUMMY_SP;
 
 use std::cell::RefCell;
+use std::hash::Hash;
 use std::mem;
 use std::rc::Rc;
 
 use crate::clean;
-use crate::clean::{AttributesExt, MAX_DEF_ID};
+use crate::clean::{AttributesExt, Clean, MAX_DEF_ID};
 use crate::config::{Options as RustdocOptions, RenderOptions};
 use crate::config::{OutputFormat, RenderInfo};
+use crate::fold::DocFolder;
 use crate::passes::{self, Condition::*, ConditionalPass};
 
 pub use rustc_session::config::{CodegenOptions, DebuggingOptions, Input, Options};
@@ -69,11 +77,16 @@ pub struct DocContext<'tcx> {
     pub auto_traits: Vec<DefId>,
     /// The options given to rustdoc that could be relevant to a pass.
     pub render_options: RenderOptions,
+    /// Whether one of the output formats uses the JSON types, which need some information about
+    /// items that the other formats don't.
+    pub uses_json_types: bool,
     /// The traits in scope for a given module.
     ///
     /// See `collect_intra_doc_links::traits_implemented_by` for more details.
     /// `map<module, set<trait>>`
     pub module_trait_cache: RefCell<FxHashMap<DefId, FxHashSet<DefId>>>,
+    /// Later on moved into `clean::Crate`
+    pub reexported_items: RefCell<Vec<clean::Item>>,
 }
 
 impl<'tcx> DocContext<'tcx> {
@@ -305,7 +318,7 @@ pub fn run_core(
         manual_passes,
         display_warnings,
         render_options,
-        output_format,
+        output_formats,
         ..
     } = options;
 
@@ -475,7 +488,7 @@ pub fn run_core(
                         default_passes,
                         manual_passes,
                         render_options,
-                        output_format,
+                        output_formats,
                     )
                 })
             });
@@ -490,7 +503,7 @@ fn run_global_ctxt(
     mut default_passes: passes::DefaultPassOption,
     mut manual_passes: Vec<String>,
     render_options: RenderOptions,
-    output_format: Option<OutputFormat>,
+    output_formats: Vec<OutputFormat>,
 ) -> (clean::Crate, RenderInfo, RenderOptions) {
     // Certain queries assume that some checks were run elsewhere
     // (see https://github.com/rust-lang/rust/pull/73566#issuecomment-656954425),
@@ -533,7 +546,11 @@ fn run_global_ctxt(
 
     let mut renderinfo = RenderInfo::default();
     renderinfo.access_levels = access_levels;
-    renderinfo.output_format = output_format;
+    // This is replaced by the format of each renderer before it runs. Until then the passes see
+    // the first one, which is the only one `--show-coverage` accepts.
+    renderinfo.output_format = output_formats.first().copied();
+    renderinfo.target_triple = tcx.sess.opts.target_triple.triple().to_string();
+    renderinfo.is_proc_macro_crate = tcx.sess.crate_types().contains(&CrateType::ProcMacro);
 
     let mut ctxt = DocContext {
         tcx,
@@ -555,7 +572,9 @@ fn run_global_ctxt(
             .filter(|trait_def_id| tcx.trait_is_auto(*trait_def_id))
             .collect(),
         render_options,
+        uses_json_types: output_formats.iter().any(|f| f.uses_json_types()),
         module_trait_cache: RefCell::new(FxHashMap::default()),
+        reexported_items: Default::default(),
     };
     debug!("crate: {:?}", tcx.hir().krate());
 
@@ -660,6 +679,75 @@ fn run_global_ctxt(
 
     ctxt.sess().abort_if_errors();
 
+    if ctxt.uses_json_types {
+        krate = ctxt.tcx.sess.time("collect_def_path_hashes", || {
+            let mut collector = CollectDefPathHashes::new(ctxt.tcx);
+            for (hash, did) in ctxt
+                .tcx
+                .hir()
+                .definitions()
+                .def_path_table()
+                .all_def_path_hashes_and_def_ids(LOCAL_CRATE)
+            {
+                collector.hashes.insert(did, hash);
+            }
+            for &did in ctxt.renderinfo.borrow().external_paths.keys() {
+                collector.record(did);
+            }
+            for trait_ in krate.external_traits.borrow().values() {
+                for &did in &trait_.supertraits {
+                    collector.record(did);
+                }
+                collector.record_generics(&trait_.generics);
+            }
+            let krate = collector.fold_crate(krate);
+            ctxt.renderinfo.borrow_mut().def_path_hashes = collector.hashes;
+            krate
+        });
+        krate = ctxt.tcx.sess.time("collect_variances", || {
+            let mut collector = CollectVariances { tcx: ctxt.tcx, variances: Default::default() };
+            let krate = collector.fold_crate(krate);
+            ctxt.renderinfo.borrow_mut().variances = collector.variances;
+            krate
+        });
+        krate = ctxt.tcx.sess.time("collect_extern_blocks", || {
+            let mut collector = CollectExternBlocks { tcx: ctxt.tcx, blocks: Default::default() };
+            let krate = collector.fold_crate(krate);
+            ctxt.renderinfo.borrow_mut().extern_blocks = collector.blocks;
+            krate
+        });
+        krate = ctxt.tcx.sess.time("collect_expansions", || {
+            let mut collector = CollectExpansions { cx: &ctxt, expansions: Default::default() };
+            let krate = collector.fold_crate(krate);
+            ctxt.renderinfo.borrow_mut().expansions = collector.expansions;
+            krate
+        });
+        // `--show-coverage` already counted everything, and only prints the results.
+        if default_passes != passes::DefaultPassOption::Coverage {
+            krate = ctxt
+                .tcx
+                .sess
+                .time("count_doc_coverage", || passes::count_doc_coverage(krate, &ctxt));
+        }
+        if ctxt.render_options.json_incremental {
+            krate = ctxt.tcx.sess.time("collect_hir_hashes", || {
+                let mut collector = CollectHirHashes::new(ctxt.tcx);
+                let krate = collector.fold_crate(krate);
+                ctxt.renderinfo.borrow_mut().hir_hashes = collector.hashes;
+                krate
+            });
+        }
+        if ctxt.render_options.json_type_layout {
+            krate = ctxt.tcx.sess.time("collect_type_layouts", || {
+                let mut collector =
+                    CollectTypeLayouts { tcx: ctxt.tcx, layouts: Default::default() };
+                let krate = collector.fold_crate(krate);
+                ctxt.renderinfo.borrow_mut().type_layouts = collector.layouts;
+                krate
+            });
+        }
+    }
+
     (krate, ctxt.renderinfo.into_inner(), ctxt.render_options)
 }
 
@@ -719,6 +807,197 @@ impl<'tcx> Visitor<'tcx> for EmitIgnoredResolutionErrors<'tcx> {
     }
 }
 
+/// Looks up the `DefPathHash` of every `DefId` the JSON backend may write out, since the
+/// `TyCtxt` is gone by the time it runs. Every local `DefId` is added up front; this collects
+/// the ones from other crates that were inlined or linked to.
+struct CollectDefPathHashes<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    hashes: FxHashMap<DefId, DefPathHash>,
+}
+
+impl<'tcx> CollectDefPathHashes<'tcx> {
+    fn new(tcx: TyCtxt<'tcx>) -> Self {
+        Self { tcx, hashes: FxHashMap::default() }
+    }
+
+    fn record(&mut self, did: DefId) {
+        let tcx = self.tcx;
+        self.hashes.entry(did).or_insert_with(|| tcx.def_path_hash(did));
+    }
+
+    fn record_generics(&mut self, generics: &clean::Generics) {
+        for param in &generics.params {
+            match param.kind {
+                clean::GenericParamDefKind::Type { did, .. }
+                | clean::GenericParamDefKind::Const { did, .. } => self.record(did),
+                clean::GenericParamDefKind::Lifetime => {}
+            }
+        }
+    }
+}
+
+impl<'tcx> DocFolder for CollectDefPathHashes<'tcx> {
+    fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
+        // Synthetic impls get fake `DefId`s, which have no def path.
+        if !item.is_fake() {
+            self.record(item.def_id);
+        }
+        for did in item.attrs.links.iter().filter_map(|link| link.did) {
+            self.record(did);
+        }
+        if let clean::Visibility::Restricted(did, _) = item.visibility {
+            self.record(did);
+        }
+        if let clean::ImportItem(ref import) = item.inner {
+            if let Some(did) = import.source.did {
+                self.record(did);
+            }
+            for &did in &import.source.reexports {
+                self.record(did);
+            }
+            if let clean::ImportKind::Glob(ref items) = import.kind {
+                for &did in items {
+                    self.record(did);
+                }
+            }
+        }
+        if let clean::TraitItem(ref trait_) = item.inner {
+            for &did in &trait_.supertraits {
+                self.record(did);
+            }
+        }
+        if let Some(generics) = item.inner.generics() {
+            self.record_generics(generics);
+        }
+        self.fold_item_recur(item)
+    }
+}
+
+/// Looks up the variance of the generic parameters of every struct, enum and union, which is only
+/// known after type checking.
+struct CollectVariances<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    variances: FxHashMap<DefId, FxHashMap<String, ty::Variance>>,
+}
+
+impl<'tcx> DocFolder for CollectVariances<'tcx> {
+    fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
+        if let clean::StructItem(_) | clean::EnumItem(_) | clean::UnionItem(_) = item.inner {
+            let variances = self.tcx.variances_of(item.def_id);
+            let params = &self.tcx.generics_of(item.def_id).params;
+            let by_name = params
+                .iter()
+                .filter_map(|param| {
+                    let variance = variances.get(param.index as usize)?;
+                    Some((param.name.to_string(), *variance))
+                })
+                .collect();
+            self.variances.insert(item.def_id, by_name);
+        }
+        self.fold_item_recur(item)
+    }
+}
+
+/// Looks up the `extern` block of every local foreign item, which the items don't keep track of.
+struct CollectExternBlocks<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    blocks: FxHashMap<DefId, clean::ExternBlock>,
+}
+
+impl<'tcx> DocFolder for CollectExternBlocks<'tcx> {
+    fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
+        if let clean::ForeignFunctionItem(_)
+        | clean::ForeignStaticItem(_)
+        | clean::ForeignTypeItem = item.inner
+        {
+            if let Some(local) = item.def_id.as_local() {
+                let hir = self.tcx.hir();
+                let block = hir.expect_item(hir.get_parent_item(hir.local_def_id_to_hir_id(local)));
+                if let rustc_hir::ItemKind::ForeignMod(ref fm) = block.kind {
+                    let block = clean::ExternBlock {
+                        def_id: hir.local_def_id(block.hir_id).to_def_id(),
+                        abi: fm.abi,
+                        attrs: block.attrs.to_vec(),
+                    };
+                    self.blocks.insert(item.def_id, block);
+                }
+            }
+        }
+        self.fold_item_recur(item)
+    }
+}
+
+/// Looks up the macro invocation every local item generated by a macro comes from, which the
+/// span of the item only points into the definition of.
+struct CollectExpansions<'a, 'tcx> {
+    cx: &'a DocContext<'tcx>,
+    expansions: FxHashMap<DefId, clean::Expansion>,
+}
+
+impl<'a, 'tcx> DocFolder for CollectExpansions<'a, 'tcx> {
+    fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
+        let span = item.source.span();
+        if item.def_id.is_local() && span.from_expansion() {
+            let data = span.ctxt().outer_expn_data();
+            if let ExpnKind::Macro(kind, name) = data.kind {
+                let expansion = clean::Expansion {
+                    macro_def_id: data.macro_def_id,
+                    name: name.to_string(),
+                    kind,
+                    call_site: data.call_site.clean(self.cx),
+                    def_site: data.def_site.clean(self.cx),
+                };
+                self.expansions.insert(item.def_id, expansion);
+            }
+        }
+        self.fold_item_recur(item)
+    }
+}
+
+/// Computes the layout of every local struct, enum and union for `--json-type-layout`. Types
+/// with type or const parameters don't have a single layout, and are skipped.
+struct CollectTypeLayouts<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    layouts: FxHashMap<DefId, clean::TypeLayout>,
+}
+
+impl<'tcx> CollectTypeLayouts<'tcx> {
+    fn layout(&self, did: DefId) -> Option<clean::TypeLayout> {
+        let tcx = self.tcx;
+        let ty = tcx.erase_regions(&tcx.type_of(did));
+        let layout = tcx.layout_of(tcx.param_env(did).and(ty)).ok()?;
+        let field_offsets = match ty.kind() {
+            ty::Adt(adt, _) if !adt.is_enum() => adt
+                .non_enum_variant()
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| (field.did, layout.fields.offset(i).bytes()))
+                .collect(),
+            _ => Vec::new(),
+        };
+        Some(clean::TypeLayout {
+            size: layout.size.bytes(),
+            align: layout.align.abi.bytes(),
+            field_offsets,
+            niche_values: layout.largest_niche.as_ref().map(|niche| niche.available(&tcx)),
+        })
+    }
+}
+
+impl<'tcx> DocFolder for CollectTypeLayouts<'tcx> {
+    fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
+        if let clean::StructItem(_) | clean::EnumItem(_) | clean::UnionItem(_) = item.inner {
+            if item.def_id.is_local() {
+                if let Some(layout) = self.layout(item.def_id) {
+                    self.layouts.insert(item.def_id, layout);
+                }
+            }
+        }
+        self.fold_item_recur(item)
+    }
+}
+
 /// `DefId` or parameter index (`ty::ParamTy.index`) of a synthetic type parameter
 /// for `impl Trait` in argument position.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -738,3 +1017,48 @@ impl From<u32> for ImplTraitParam {
         ImplTraitParam::ParamIndex(idx)
     }
 }
+
+/// Hashes the HIR of every local item for `--json-incremental`. Each hash also covers everything
+/// from outside of the crate that the output depends on: the crates it links to, and the version
+/// and command line of rustdoc.
+struct CollectHirHashes<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    hcx: StableHashingContext<'tcx>,
+    context: Fingerprint,
+    hashes: FxHashMap<DefId, Fingerprint>,
+}
+
+impl<'tcx> CollectHirHashes<'tcx> {
+    fn new(tcx: TyCtxt<'tcx>) -> Self {
+        let mut hasher = StableHasher::new();
+        for &cnum in tcx.crates() {
+            tcx.crate_hash(cnum).hash(&mut hasher);
+        }
+        option_env!("CFG_VERSION").hash(&mut hasher);
+        std::env::args_os().for_each(|arg| arg.hash(&mut hasher));
+        Self {
+            tcx,
+            hcx: tcx.create_stable_hashing_context(),
+            context: hasher.finish(),
+            hashes: FxHashMap::default(),
+        }
+    }
+}
+
+impl<'tcx> DocFolder for CollectHirHashes<'tcx> {
+    fn fold_item(&mut self, item: clean::Item) -> Option<clean::Item> {
+        // Synthetic impls get fake `DefId`s. Fields and variants are part of the HIR of their
+        // parent, and are always converted again.
+        let nodes = match item.def_id.as_local() {
+            Some(did) if !item.is_fake() => self.tcx.hir_owner_nodes(did),
+            _ => None,
+        };
+        if let Some(nodes) = nodes {
+            let mut hasher = StableHasher::new();
+            nodes.hash_stable(&mut self.hcx, &mut hasher);
+            let hash: Fingerprint = hasher.finish();
+            self.hashes.insert(item.def_id, self.context.combine(hash));
+        }
+        self.fold_item_recur(item)
+    }
+}
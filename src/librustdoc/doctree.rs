@@ -230,8 +230,12 @@ pub struct Macro<'hir> {
     pub hid: hir::HirId,
     pub def_id: hir::def_id::DefId,
     pub attrs: &'hir [ast::Attribute],
+    pub vis: &'hir hir::Visibility<'hir>,
     pub span: Span,
     pub matchers: Vec<Span>,
+    pub rules: Vec<crate::clean::MacroRule>,
+    /// `false` for macros defined with the `macro` keyword.
+    pub macro_rules: bool,
     pub imported_from: Option<Symbol>,
 }
 
@@ -0,0 +1,215 @@
+//! Rustdoc's Graphviz backend
+//!
+//! This writes a [DOT](https://graphviz.org/doc/info/lang.html) graph of the crate's types and
+//! traits to `<out>/<crate>.dot`, for architecture overviews. A type has a dashed edge to each
+//! trait it implements, and a solid edge to each type used in its fields.
+//!
+//! Only items of the documented crate are drawn: otherwise every type implementing `Debug` or
+//! holding a `String` would be connected to the same few nodes of the standard library.
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use rustc_hir::def_id::DefId;
+use rustc_span::edition::Edition;
+
+use crate::clean::{self, GetDefId};
+use crate::config::{OutputFormat, RenderInfo, RenderOptions};
+use crate::docfs::PathError;
+use crate::error::Error;
+use crate::formats::cache::Cache;
+use crate::formats::item_type::ItemType;
+use crate::formats::registry::Backend;
+use crate::formats::{run_format, FormatRenderer};
+
+#[cfg(test)]
+mod tests;
+
+crate static BACKEND: Backend = Backend {
+    name: "dot",
+    format: OutputFormat::Dot,
+    timer: "render_dot",
+    run: run_format::<DotRenderer>,
+};
+
+#[derive(Clone)]
+crate struct DotRenderer {
+    out_path: PathBuf,
+    /// Every type and the types used in its fields.
+    fields: Rc<RefCell<BTreeSet<(DefId, DefId)>>>,
+}
+
+impl FormatRenderer for DotRenderer {
+    fn init(
+        krate: clean::Crate,
+        options: RenderOptions,
+        _render_info: RenderInfo,
+        _edition: Edition,
+        _cache: &mut Cache,
+    ) -> Result<(Self, clean::Crate), Error> {
+        debug!("Initializing dot renderer");
+        let out_path = options.output;
+        try_err!(fs::create_dir_all(&out_path), &out_path);
+        Ok((DotRenderer { out_path, fields: Rc::new(RefCell::new(BTreeSet::new())) }, krate))
+    }
+
+    fn item(&mut self, item: clean::Item, _cache: &Cache) -> Result<(), Error> {
+        let mut tys = Vec::new();
+        match &item.inner {
+            clean::StructItem(s) => tys.extend(field_types(&s.fields)),
+            clean::UnionItem(u) => tys.extend(field_types(&u.fields)),
+            clean::EnumItem(e) => {
+                for variant in e.variants.iter() {
+                    match &variant.inner {
+                        clean::VariantItem(clean::Variant {
+                            kind: clean::VariantKind::Tuple(fields),
+                            ..
+                        }) => tys.extend(field_types(fields)),
+                        clean::VariantItem(clean::Variant {
+                            kind: clean::VariantKind::Struct(s),
+                            ..
+                        }) => tys.extend(field_types(&s.fields)),
+                        _ => {}
+                    }
+                }
+            }
+            _ => return Ok(()),
+        }
+        let mut dids = Vec::new();
+        for ty in tys {
+            type_dids(ty, &mut dids);
+        }
+        let mut fields = self.fields.borrow_mut();
+        for did in dids {
+            fields.insert((item.def_id, did));
+        }
+        Ok(())
+    }
+
+    fn mod_item_in(
+        &mut self,
+        _item: &clean::Item,
+        _item_name: &str,
+        _cache: &Cache,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn mod_item_out(&mut self, _item_name: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn after_krate(&mut self, krate: &clean::Crate, cache: &Cache) -> Result<(), Error> {
+        let out = graph(&krate.name, &self.fields.borrow(), cache);
+        let p = self.out_path.join(format!("{}.dot", krate.name));
+        try_err!(fs::write(&p, out), &p);
+        Ok(())
+    }
+
+    fn after_run(&mut self, _diag: &rustc_errors::Handler) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// The DOT graph of the crate `name`, given every type and the types used in its fields.
+fn graph(name: &str, fields: &BTreeSet<(DefId, DefId)>, cache: &Cache) -> String {
+    // The name of the node of each item that's drawn.
+    let node = |did: DefId| match cache.paths.get(&did) {
+        Some((path, ty)) if did.is_local() && node_attrs(*ty).is_some() => Some(path.join("::")),
+        _ => None,
+    };
+    let mut nodes = BTreeSet::new();
+    for (did, (_, ty)) in &cache.paths {
+        if let (Some(name), Some(attrs)) = (node(*did), node_attrs(*ty)) {
+            nodes.insert((name, attrs));
+        }
+    }
+    let mut edges = BTreeSet::new();
+    for (trait_did, impls) in &cache.implementors {
+        for i in impls {
+            let impl_ = i.inner_impl();
+            if impl_.synthetic || impl_.blanket_impl.is_some() {
+                continue;
+            }
+            if let (Some(from), Some(to)) = (impl_.for_.def_id().and_then(node), node(*trait_did)) {
+                edges.insert((from, to, " [style=dashed, arrowhead=empty]"));
+            }
+        }
+    }
+    for (from, to) in fields {
+        if let (Some(from), Some(to)) = (node(*from), node(*to)) {
+            edges.insert((from, to, ""));
+        }
+    }
+
+    let mut out = format!("digraph {} {{\n", quote(name));
+    for (name, attrs) in nodes {
+        out.push_str(&format!("    {} [{}];\n", quote(&name), attrs));
+    }
+    for (from, to, attrs) in edges {
+        out.push_str(&format!("    {} -> {}{};\n", quote(&from), quote(&to), attrs));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// The attributes of the nodes of the given kind of item, or `None` for kinds that aren't drawn.
+fn node_attrs(ty: ItemType) -> Option<&'static str> {
+    match ty {
+        ItemType::Struct | ItemType::Union => Some("shape=box"),
+        ItemType::Enum => Some("shape=box, style=rounded"),
+        ItemType::Trait => Some("shape=ellipse"),
+        _ => None,
+    }
+}
+
+/// The types of the visible fields among `fields`.
+fn field_types(fields: &[clean::Item]) -> impl Iterator<Item = &clean::Type> {
+    fields.iter().filter_map(|f| match &f.inner {
+        clean::StructFieldItem(ty) => Some(ty),
+        _ => None,
+    })
+}
+
+/// Collects the items `ty` refers to, including the ones in its generic arguments.
+fn type_dids(ty: &clean::Type, dids: &mut Vec<DefId>) {
+    match ty {
+        clean::ResolvedPath { path, did, is_generic: false, .. } => {
+            dids.push(*did);
+            for segment in &path.segments {
+                match &segment.args {
+                    clean::GenericArgs::AngleBracketed { args, .. } => {
+                        for arg in args {
+                            if let clean::GenericArg::Type(ty) = arg {
+                                type_dids(ty, dids);
+                            }
+                        }
+                    }
+                    clean::GenericArgs::Parenthesized { inputs, output } => {
+                        for ty in inputs.iter().chain(output) {
+                            type_dids(ty, dids);
+                        }
+                    }
+                }
+            }
+        }
+        clean::Tuple(tys) => {
+            for ty in tys {
+                type_dids(ty, dids);
+            }
+        }
+        clean::Slice(ty)
+        | clean::Array(ty, ..)
+        | clean::RawPointer(_, ty)
+        | clean::BorrowedRef { type_: ty, .. } => type_dids(ty, dids),
+        _ => {}
+    }
+}
+
+/// Quotes `s` as a DOT identifier.
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,175 @@
+use rustc_hir::def::Res;
+use rustc_hir::def_id::{CrateNum, DefIndex, LOCAL_CRATE};
+
+use crate::doctree::StructType;
+use crate::formats::Impl;
+
+use super::*;
+
+fn def_id(index: u32) -> DefId {
+    DefId { krate: LOCAL_CRATE, index: DefIndex::from_u32(index) }
+}
+
+fn item(did: DefId, inner: clean::ItemEnum) -> clean::Item {
+    clean::Item {
+        source: clean::Span::empty(),
+        name: None,
+        attrs: clean::Attributes::default(),
+        inner,
+        visibility: clean::Public,
+        def_id: did,
+        stability: None,
+        deprecation: None,
+    }
+}
+
+/// The path `name<args>` resolved to `did`.
+fn path(did: DefId, name: &str, args: Vec<clean::Type>) -> clean::Type {
+    let args = args.into_iter().map(clean::GenericArg::Type).collect();
+    clean::ResolvedPath {
+        path: clean::Path {
+            global: false,
+            res: Res::Err,
+            segments: vec![clean::PathSegment {
+                name: name.to_owned(),
+                args: clean::GenericArgs::AngleBracketed { args, bindings: Vec::new() },
+            }],
+        },
+        param_names: None,
+        did,
+        is_generic: false,
+    }
+}
+
+fn fields(tys: Vec<clean::Type>) -> Vec<clean::Item> {
+    tys.into_iter().map(|ty| item(def_id(100), clean::StructFieldItem(ty))).collect()
+}
+
+fn renderer() -> DotRenderer {
+    DotRenderer { out_path: PathBuf::new(), fields: Rc::new(RefCell::new(BTreeSet::new())) }
+}
+
+#[test]
+fn quoting() {
+    assert_eq!(quote("krate::Foo"), "\"krate::Foo\"");
+    assert_eq!(quote("a\"b\\c"), "\"a\\\"b\\\\c\"");
+}
+
+#[test]
+fn drawn_kinds() {
+    assert!(node_attrs(ItemType::Struct).is_some());
+    assert!(node_attrs(ItemType::Trait).is_some());
+    assert!(node_attrs(ItemType::Function).is_none());
+}
+
+#[test]
+fn types_in_generics_and_references() {
+    // `Vec<Option<&'a [Bar; 2]>>`
+    let array = clean::Array(box path(def_id(2), "Bar", vec![]), "2".to_owned(), Some(2));
+    let reference = clean::BorrowedRef {
+        lifetime: Some(clean::Lifetime("'a".to_owned())),
+        mutability: rustc_hir::Mutability::Not,
+        type_: box array,
+    };
+    let ty = path(def_id(10), "Vec", vec![path(def_id(11), "Option", vec![reference])]);
+    let mut dids = Vec::new();
+    type_dids(&ty, &mut dids);
+    assert_eq!(dids, vec![def_id(10), def_id(11), def_id(2)]);
+
+    // `(T, *const Baz)`, where `T` is a generic parameter.
+    let pointer = clean::RawPointer(rustc_hir::Mutability::Not, box path(def_id(3), "Baz", vec![]));
+    let ty = clean::Tuple(vec![clean::Generic("T".to_owned()), pointer]);
+    let mut dids = Vec::new();
+    type_dids(&ty, &mut dids);
+    assert_eq!(dids, vec![def_id(3)]);
+}
+
+#[test]
+fn fields_of_structs_and_variants() {
+    // Stripped fields aren't documented, so they aren't drawn either.
+    let mut struct_fields = fields(vec![path(def_id(2), "Bar", vec![])]);
+    let private = clean::StructFieldItem(path(def_id(3), "Baz", vec![]));
+    let private = clean::StrippedItem(box private, clean::StripReason::Private);
+    struct_fields.push(item(def_id(100), private));
+    assert_eq!(
+        field_types(&struct_fields).collect::<Vec<_>>(),
+        vec![&path(def_id(2), "Bar", vec![])]
+    );
+
+    let variant =
+        |kind| item(def_id(100), clean::VariantItem(clean::Variant { kind, discriminant: None }));
+    let enum_ = clean::EnumItem(clean::Enum {
+        variants: vec![
+            variant(clean::VariantKind::CLike),
+            // `Tuple(Vec<Bar>)`
+            variant(clean::VariantKind::Tuple(fields(vec![path(
+                def_id(10),
+                "Vec",
+                vec![path(def_id(2), "Bar", vec![])],
+            )]))),
+            // `Struct { baz: &Baz }`
+            variant(clean::VariantKind::Struct(clean::VariantStruct {
+                struct_type: StructType::Plain,
+                fields: fields(vec![clean::BorrowedRef {
+                    lifetime: None,
+                    mutability: rustc_hir::Mutability::Not,
+                    type_: box path(def_id(3), "Baz", vec![]),
+                }]),
+                fields_stripped: false,
+            })),
+        ]
+        .into_iter()
+        .collect(),
+        generics: clean::Generics::default(),
+        variants_stripped: false,
+    });
+    let mut renderer = renderer();
+    renderer.item(item(def_id(1), enum_), &Cache::default()).unwrap();
+    let expected = vec![(def_id(1), def_id(2)), (def_id(1), def_id(3)), (def_id(1), def_id(10))];
+    assert_eq!(renderer.fields.borrow().iter().copied().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn edges_between_local_types_and_traits() {
+    let mut cache = Cache::default();
+    for (index, name, ty) in &[
+        (1, "Foo", ItemType::Struct),
+        (2, "Bar", ItemType::Enum),
+        (3, "Trait", ItemType::Trait),
+        (4, "function", ItemType::Function),
+    ] {
+        cache.paths.insert(def_id(*index), (vec!["krate".to_owned(), name.to_string()], *ty));
+    }
+    let external = DefId { krate: CrateNum::new(1), index: DefIndex::from_u32(1) };
+    cache.paths.insert(external, (vec!["std".to_owned(), "String".to_owned()], ItemType::Struct));
+
+    let impl_ = |for_: DefId, synthetic: bool| Impl {
+        impl_item: item(
+            def_id(100),
+            clean::ImplItem(clean::Impl {
+                unsafety: rustc_hir::Unsafety::Normal,
+                constness: rustc_hir::Constness::NotConst,
+                generics: clean::Generics::default(),
+                provided_trait_methods: Default::default(),
+                trait_: Some(path(def_id(3), "Trait", vec![])),
+                for_: path(for_, "Type", vec![]),
+                items: Vec::new(),
+                polarity: None,
+                synthetic,
+                blanket_impl: None,
+            }),
+        ),
+    };
+    cache.implementors.insert(def_id(3), vec![impl_(def_id(1), false), impl_(def_id(2), true)]);
+
+    let field_edges = vec![(def_id(1), def_id(2)), (def_id(1), external), (def_id(2), def_id(4))];
+    let expected = "digraph \"krate\" {
+    \"krate::Bar\" [shape=box, style=rounded];
+    \"krate::Foo\" [shape=box];
+    \"krate::Trait\" [shape=ellipse];
+    \"krate::Foo\" -> \"krate::Bar\";
+    \"krate::Foo\" -> \"krate::Trait\" [style=dashed, arrowhead=empty];
+}
+";
+    assert_eq!(graph("krate", &field_edges.into_iter().collect(), &cache), expected);
+}
@@ -3,11 +3,11 @@ use crate::clean::*;
 pub struct StripItem(pub Item);
 
 impl StripItem {
-    pub fn strip(self) -> Option<Item> {
+    pub fn strip(self, reason: StripReason) -> Option<Item> {
         match self.0 {
             Item { inner: StrippedItem(..), .. } => Some(self.0),
             mut i => {
-                i.inner = StrippedItem(box i.inner);
+                i.inner = StrippedItem(box i.inner, reason);
                 Some(i)
             }
         }
@@ -63,6 +63,10 @@ pub trait DocFolder: Sized {
                             || j.fields.iter().any(|f| f.is_stripped());
                         VariantItem(Variant { kind: VariantKind::Struct(j), ..i2 })
                     }
+                    VariantKind::Tuple(fields) => {
+                        let fields = fields.into_iter().filter_map(|x| self.fold_item(x)).collect();
+                        VariantItem(Variant { kind: VariantKind::Tuple(fields), ..i2 })
+                    }
                     _ => VariantItem(i2),
                 }
             }
@@ -75,7 +79,7 @@ pub trait DocFolder: Sized {
         let Item { attrs, name, source, visibility, def_id, inner, stability, deprecation } = item;
 
         let inner = match inner {
-            StrippedItem(box i) => StrippedItem(box self.fold_inner_recur(i)),
+            StrippedItem(box i, reason) => StrippedItem(box self.fold_inner_recur(i), reason),
             _ => self.fold_inner_recur(inner),
         };
 
@@ -91,6 +95,9 @@ pub trait DocFolder: Sized {
 
     fn fold_crate(&mut self, mut c: Crate) -> Crate {
         c.module = c.module.take().and_then(|module| self.fold_item(module));
+        let reexported_items = std::mem::take(&mut c.reexported_items);
+        c.reexported_items =
+            reexported_items.into_iter().filter_map(|i| self.fold_item(i)).collect();
 
         {
             let external_traits = { std::mem::take(&mut *c.external_traits.borrow_mut()) };
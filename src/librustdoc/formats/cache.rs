@@ -4,8 +4,9 @@ use std::mem;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use rustc_ast::crate_disambiguator::CrateDisambiguator;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
-use rustc_hir::def_id::{CrateNum, DefId, CRATE_DEF_INDEX};
+use rustc_hir::def_id::{CrateNum, DefId, DefPathHash, CRATE_DEF_INDEX, LOCAL_CRATE};
 use rustc_middle::middle::privacy::AccessLevels;
 use rustc_span::source_map::FileName;
 
@@ -122,6 +123,13 @@ pub struct Cache {
     /// Aliases added through `#[doc(alias = "...")]`. Since a few items can have the same alias,
     /// we need the alias element to have an array of items.
     pub aliases: BTreeMap<String, Vec<usize>>,
+
+    /// The stable hashes of the def paths of the items being rendered, see
+    /// [`RenderInfo::def_path_hashes`].
+    pub def_path_hashes: FxHashMap<DefId, DefPathHash>,
+
+    /// The disambiguators of the local crate and of all the external crates.
+    pub crate_disambiguators: FxHashMap<CrateNum, CrateDisambiguator>,
 }
 
 impl Cache {
@@ -141,6 +149,7 @@ impl Cache {
             deref_trait_did,
             deref_mut_trait_did,
             owned_box_did,
+            def_path_hashes,
             ..
         } = render_info;
 
@@ -160,9 +169,15 @@ impl Cache {
             deref_mut_trait_did,
             owned_box_did,
             masked_crates: mem::take(&mut krate.masked_crates),
+            def_path_hashes,
             ..Cache::default()
         };
 
+        cache.crate_disambiguators.insert(LOCAL_CRATE, krate.disambiguator);
+        for &(n, ref e) in &krate.externs {
+            cache.crate_disambiguators.insert(n, e.disambiguator);
+        }
+
         // Cache where all our extern crates are located
         // FIXME: this part is specific to HTML so it'd be nice to remove it from the common code
         for &(n, ref e) in &krate.externs {
@@ -219,7 +234,7 @@ impl DocFolder for Cache {
         // If this is a stripped module,
         // we don't want it or its children in the search index.
         let orig_stripped_mod = match item.inner {
-            clean::StrippedItem(box clean::ModuleItem(..)) => {
+            clean::StrippedItem(box clean::ModuleItem(..), _) => {
                 mem::replace(&mut self.stripped_mod, true)
             }
             _ => self.stripped_mod,
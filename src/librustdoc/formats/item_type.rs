@@ -61,7 +61,7 @@ impl Serialize for ItemType {
 impl<'a> From<&'a clean::Item> for ItemType {
     fn from(item: &'a clean::Item) -> ItemType {
         let inner = match item.inner {
-            clean::StrippedItem(box ref item) => item,
+            clean::StrippedItem(box ref item, _) => item,
             ref inner => inner,
         };
 
@@ -1,5 +1,7 @@
 pub mod cache;
 pub mod item_type;
+crate mod registry;
+crate mod signature;
 pub mod renderer;
 
 pub use renderer::{run_format, FormatRenderer};
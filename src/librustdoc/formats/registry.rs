@@ -0,0 +1,43 @@
+//! The backends that can be selected with `--output-format`.
+//!
+//! Each backend module declares its own [`Backend`]s next to its
+//! [`FormatRenderer`](crate::formats::FormatRenderer), so adding one only takes listing it in
+//! [`backends`].
+
+use rustc_span::edition::Edition;
+
+use crate::clean;
+use crate::config::{OutputFormat, RenderInfo, RenderOptions};
+use crate::error::Error;
+
+crate struct Backend {
+    /// The name of the format, as given to `--output-format`.
+    crate name: &'static str,
+    crate format: OutputFormat,
+    /// The label the backend is timed under with `-Z time-passes`.
+    crate timer: &'static str,
+    /// Renders the crate. This is usually [`run_format`](crate::formats::run_format) with the
+    /// backend's renderer.
+    crate run: fn(
+        clean::Crate,
+        RenderOptions,
+        RenderInfo,
+        &rustc_errors::Handler,
+        Edition,
+    ) -> Result<(), Error>,
+}
+
+/// Every backend known to rustdoc.
+crate fn backends() -> impl Iterator<Item = &'static Backend> {
+    std::iter::once(&crate::html::render::BACKEND)
+        .chain(crate::json::BACKENDS.iter())
+        .chain(std::iter::once(&crate::markdown_output::BACKEND))
+        .chain(std::iter::once(&crate::man::BACKEND))
+        .chain(std::iter::once(&crate::dot::BACKEND))
+        .chain(std::iter::once(&crate::sqlite::BACKEND))
+}
+
+/// The backend rendering `format`.
+crate fn backend(format: OutputFormat) -> &'static Backend {
+    backends().find(|b| b.format == format).expect("every output format has a backend")
+}
@@ -87,7 +87,7 @@ pub fn run_format<T: FormatRenderer>(
 
             cx.mod_item_in(&item, &name, &cache)?;
             let module = match item.inner {
-                clean::StrippedItem(box clean::ModuleItem(m)) | clean::ModuleItem(m) => m,
+                clean::StrippedItem(box clean::ModuleItem(m), _) | clean::ModuleItem(m) => m,
                 _ => unreachable!(),
             };
             for it in module.items {
@@ -0,0 +1,218 @@
+//! Plain text declarations of items, for the backends that print them without the HTML
+//! backend's markup.
+
+use rustc_span::hygiene::MacroKind;
+
+use crate::clean;
+use crate::formats::cache::Cache;
+use crate::html::format::{
+    print_abi_with_space, print_generic_bounds, PrintWithSpace, WhereClause,
+};
+
+/// A group of the items listed under another one, such as the fields of a struct.
+crate struct Members<'a> {
+    crate title: &'static str,
+    /// The declaration of each member, and the item whose docs describe it.
+    crate entries: Vec<(String, &'a clean::Item)>,
+}
+
+/// The fields, variants or associated items of `item`, followed by its inherent methods and the
+/// traits it implements. Auto trait and blanket impls are left out, as they would be repeated on
+/// every type. Empty groups are skipped.
+crate fn members<'a>(item: &'a clean::Item, cache: &'a Cache) -> Vec<Members<'a>> {
+    let own = match &item.inner {
+        clean::StructItem(clean::Struct { fields, .. })
+        | clean::UnionItem(clean::Union { fields, .. }) => {
+            Members { title: "Fields", entries: fields.iter().filter_map(field).collect() }
+        }
+        clean::EnumItem(e) => {
+            Members { title: "Variants", entries: e.variants.iter().filter_map(variant).collect() }
+        }
+        clean::TraitItem(t) => Members {
+            title: "Associated items",
+            entries: t.items.iter().filter_map(|i| Some((signature(i)?, i))).collect(),
+        },
+        _ => Members { title: "", entries: Vec::new() },
+    };
+    let mut inherent = Members { title: "Implementations", entries: Vec::new() };
+    let mut traits = Members { title: "Trait implementations", entries: Vec::new() };
+    for i in cache.impls.get(&item.def_id).into_iter().flatten() {
+        let impl_ = i.inner_impl();
+        if impl_.synthetic || impl_.blanket_impl.is_some() {
+            continue;
+        }
+        if impl_.trait_.is_some() {
+            let sig = format!("{:#}", impl_.print()).trim_end().to_owned();
+            traits.entries.push((sig, &i.impl_item));
+        } else {
+            inherent.entries.extend(impl_.items.iter().filter_map(|i| Some((signature(i)?, i))));
+        }
+    }
+    vec![own, inherent, traits].into_iter().filter(|m| !m.entries.is_empty()).collect()
+}
+
+fn field(item: &clean::Item) -> Option<(String, &clean::Item)> {
+    match &item.inner {
+        clean::StructFieldItem(ty) => {
+            let vis = item.visibility.print_with_space();
+            Some((format!("{:#}{}: {:#}", vis, item.name.as_ref()?, ty.print()), item))
+        }
+        _ => None,
+    }
+}
+
+fn variant(item: &clean::Item) -> Option<(String, &clean::Item)> {
+    let name = item.name.as_ref()?;
+    let sig = match &item.inner {
+        clean::VariantItem(v) => match &v.kind {
+            clean::VariantKind::CLike => name.clone(),
+            clean::VariantKind::Tuple(fields) => {
+                let tys: Vec<_> = fields
+                    .iter()
+                    .map(|f| match &f.inner {
+                        clean::StructFieldItem(ty) => format!("{:#}", ty.print()),
+                        _ => "_".to_owned(),
+                    })
+                    .collect();
+                format!("{}({})", name, tys.join(", "))
+            }
+            clean::VariantKind::Struct(s) => {
+                let fields: Vec<_> = s
+                    .fields
+                    .iter()
+                    .filter_map(|f| match &f.inner {
+                        clean::StructFieldItem(ty) => {
+                            Some(format!("{}: {:#}", f.name.as_ref()?, ty.print()))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                format!("{} {{ {} }}", name, fields.join(", "))
+            }
+        },
+        _ => return None,
+    };
+    Some((sig, item))
+}
+
+fn fn_signature(
+    item: &clean::Item,
+    header: &rustc_hir::FnHeader,
+    generics: &clean::Generics,
+    decl: &clean::FnDecl,
+) -> String {
+    format!(
+        "{:#}{}{}{}{:#}fn {}{:#}{:#}{:#}",
+        item.visibility.print_with_space(),
+        header.constness.print_with_space(),
+        header.asyncness.print_with_space(),
+        header.unsafety.print_with_space(),
+        print_abi_with_space(header.abi),
+        item.name.as_ref().unwrap(),
+        generics.print(),
+        decl.print(),
+        where_clause(generics),
+    )
+}
+
+fn where_clause(gens: &clean::Generics) -> WhereClause<'_> {
+    WhereClause { gens, indent: 0, end_newline: false }
+}
+
+/// The declaration of `item`, without its body. This is `None` for items that can't be written
+/// as Rust, such as primitives, and items that aren't rendered, such as imports.
+crate fn signature(item: &clean::Item) -> Option<String> {
+    let name = item.name.as_ref()?;
+    let vis = item.visibility.print_with_space();
+    let sig = match &item.inner {
+        clean::StructItem(clean::Struct { generics, .. }) => {
+            format!("{:#}struct {}{:#}{:#}", vis, name, generics.print(), where_clause(generics))
+        }
+        clean::UnionItem(clean::Union { generics, .. }) => {
+            format!("{:#}union {}{:#}{:#}", vis, name, generics.print(), where_clause(generics))
+        }
+        clean::EnumItem(clean::Enum { generics, .. }) => {
+            format!("{:#}enum {}{:#}{:#}", vis, name, generics.print(), where_clause(generics))
+        }
+        clean::FunctionItem(f) | clean::ForeignFunctionItem(f) => {
+            fn_signature(item, &f.header, &f.generics, &f.decl)
+        }
+        clean::MethodItem(m) => fn_signature(item, &m.header, &m.generics, &m.decl),
+        clean::TyMethodItem(m) => fn_signature(item, &m.header, &m.generics, &m.decl),
+        clean::TraitItem(t) => {
+            let bounds = if t.bounds.is_empty() {
+                String::new()
+            } else {
+                format!(": {:#}", print_generic_bounds(&t.bounds))
+            };
+            format!(
+                "{:#}{}{}trait {}{:#}{}{:#}",
+                vis,
+                t.unsafety.print_with_space(),
+                if t.is_auto { "auto " } else { "" },
+                name,
+                t.generics.print(),
+                bounds,
+                where_clause(&t.generics),
+            )
+        }
+        clean::TraitAliasItem(t) => format!(
+            "{:#}trait {}{:#} = {:#}{:#};",
+            vis,
+            name,
+            t.generics.print(),
+            print_generic_bounds(&t.bounds),
+            where_clause(&t.generics),
+        ),
+        clean::TypedefItem(t, _) => format!(
+            "{:#}type {}{:#}{:#} = {:#};",
+            vis,
+            name,
+            t.generics.print(),
+            where_clause(&t.generics),
+            t.type_.print(),
+        ),
+        clean::OpaqueTyItem(t) => format!(
+            "{:#}type {}{:#}{:#} = impl {:#};",
+            vis,
+            name,
+            t.generics.print(),
+            where_clause(&t.generics),
+            print_generic_bounds(&t.bounds),
+        ),
+        clean::StaticItem(s) | clean::ForeignStaticItem(s) => format!(
+            "{:#}static {}{}: {:#}",
+            vis,
+            s.mutability.print_with_space(),
+            name,
+            s.type_.print()
+        ),
+        clean::ConstantItem(c) => {
+            format!("{:#}const {}: {:#} = {};", vis, name, c.type_.print(), c.expr)
+        }
+        clean::AssocConstItem(ty, default, _) => match default {
+            Some(default) => format!("const {}: {:#} = {};", name, ty.print(), default),
+            None => format!("const {}: {:#};", name, ty.print()),
+        },
+        clean::AssocTypeItem(bounds, default) => {
+            let mut sig = format!("type {}", name);
+            if !bounds.is_empty() {
+                sig.push_str(&format!(": {:#}", print_generic_bounds(bounds)));
+            }
+            if let Some(default) = default {
+                sig.push_str(&format!(" = {:#}", default.print()));
+            }
+            sig.push(';');
+            sig
+        }
+        clean::ForeignTypeItem => format!("{:#}type {};", vis, name),
+        clean::MacroItem(m) => m.source.clone(),
+        clean::ProcMacroItem(m) => match m.kind {
+            MacroKind::Bang => format!("{}!() {{ /* proc-macro */ }}", name),
+            MacroKind::Attr => format!("#[{}]", name),
+            MacroKind::Derive => format!("#[derive({})]", name),
+        },
+        _ => return None,
+    };
+    Some(sig)
+}
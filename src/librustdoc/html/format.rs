@@ -696,7 +696,7 @@ fn fmt_type(t: &clean::Type, f: &mut fmt::Formatter<'_>, use_absolute: bool) ->
             fmt::Display::fmt(&t.print(), f)?;
             primitive_link(f, PrimitiveType::Slice, "]")
         }
-        clean::Array(ref t, ref n) => {
+        clean::Array(ref t, ref n, _) => {
             primitive_link(f, PrimitiveType::Array, "[")?;
             fmt::Display::fmt(&t.print(), f)?;
             if f.alternate() {
@@ -789,14 +789,14 @@ fn fmt_type(t: &clean::Type, f: &mut fmt::Formatter<'_>, use_absolute: bool) ->
                 }
             }
         }
-        clean::ImplTrait(ref bounds) => {
+        clean::ImplTrait(ref bounds, _) => {
             if f.alternate() {
                 write!(f, "impl {:#}", print_generic_bounds(bounds))
             } else {
                 write!(f, "impl {}", print_generic_bounds(bounds))
             }
         }
-        clean::QPath { ref name, ref self_type, ref trait_ } => {
+        clean::QPath { ref name, ref args, ref self_type, ref trait_ } => {
             let should_show_cast = match *trait_ {
                 box clean::ResolvedPath { ref path, .. } => {
                     !path.segments.is_empty() && !self_type.is_self_type()
@@ -845,9 +845,13 @@ fn fmt_type(t: &clean::Type, f: &mut fmt::Formatter<'_>, use_absolute: bool) ->
 
                     // FIXME: `param_names` are not rendered, and this seems bad?
                     drop(param_names);
-                    Ok(())
                 }
-                _ => write!(f, "{}", name),
+                _ => write!(f, "{}", name)?,
+            }
+            match args {
+                Some(args) if f.alternate() => write!(f, "{:#}", args.print()),
+                Some(args) => write!(f, "{}", args.print()),
+                None => Ok(()),
             }
         }
     }
@@ -873,6 +877,9 @@ impl clean::Impl {
             }
 
             if let Some(ref ty) = self.trait_ {
+                if self.constness == hir::Constness::Const {
+                    write!(f, "const ")?;
+                }
                 if self.polarity == Some(clean::ImplPolarity::Negative) {
                     write!(f, "!")?;
                 }
@@ -1157,7 +1164,7 @@ impl clean::Import {
                     write!(f, "use {} as {};", self.source.print(), *name)
                 }
             }
-            clean::ImportKind::Glob => {
+            clean::ImportKind::Glob(_) => {
                 if self.source.path.segments.is_empty() {
                     write!(f, "use *;")
                 } else {
@@ -105,13 +105,13 @@ impl ErrorCodes {
 /// Controls whether a line will be hidden or shown in HTML output.
 ///
 /// All lines are used in documentation tests.
-enum Line<'a> {
+crate enum Line<'a> {
     Hidden(&'a str),
     Shown(Cow<'a, str>),
 }
 
 impl<'a> Line<'a> {
-    fn for_html(self) -> Option<Cow<'a, str>> {
+    crate fn for_html(self) -> Option<Cow<'a, str>> {
         match self {
             Line::Shown(l) => Some(l),
             Line::Hidden(_) => None,
@@ -131,7 +131,7 @@ impl<'a> Line<'a> {
 // is done in the single # case. This inconsistency seems okay, if non-ideal. In
 // order to fix it we'd have to iterate to find the first non-# character, and
 // then reallocate to remove it; which would make us return a String.
-fn map_line(s: &str) -> Line<'_> {
+crate fn map_line(s: &str) -> Line<'_> {
     let trimmed = s.trim();
     if trimmed.starts_with("##") {
         Line::Shown(Cow::Owned(s.replacen("##", "#", 1)))
@@ -774,7 +774,7 @@ pub enum Ignore {
 }
 
 impl LangString {
-    fn all_false() -> LangString {
+    crate fn all_false() -> LangString {
         LangString {
             original: String::new(),
             should_panic: false,
@@ -789,7 +789,7 @@ impl LangString {
         }
     }
 
-    fn parse_without_check(
+    crate fn parse_without_check(
         string: &str,
         allow_error_code_check: ErrorCodes,
         enable_per_target_ignores: bool,
@@ -65,13 +65,14 @@ use serde::ser::SerializeSeq;
 use serde::{Serialize, Serializer};
 
 use crate::clean::{self, AttributesExt, Deprecation, GetDefId, RenderedLink, SelfTy, TypeKind};
-use crate::config::{RenderInfo, RenderOptions};
+use crate::config::{OutputFormat, RenderInfo, RenderOptions};
 use crate::docfs::{DocFS, PathError};
 use crate::doctree;
 use crate::error::Error;
 use crate::formats::cache::{cache, Cache};
 use crate::formats::item_type::ItemType;
-use crate::formats::{AssocItemRender, FormatRenderer, Impl, RenderMode};
+use crate::formats::registry::Backend;
+use crate::formats::{run_format, AssocItemRender, FormatRenderer, Impl, RenderMode};
 use crate::html::escape::Escape;
 use crate::html::format::fmt_impl_for_trait_page;
 use crate::html::format::Function;
@@ -374,6 +375,14 @@ pub fn initial_ids() -> Vec<String> {
     .collect()
 }
 
+/// The default backend, rendering a browsable site with [`Context`].
+crate static BACKEND: Backend = Backend {
+    name: "html",
+    format: OutputFormat::Html,
+    timer: "render_html",
+    run: run_format::<Context>,
+};
+
 /// Generates the documentation for `crate` into the directory `dst`
 impl FormatRenderer for Context {
     fn init(
@@ -616,7 +625,7 @@ impl FormatRenderer for Context {
         // Render sidebar-items.js used throughout this module.
         if !self.render_redirect_pages {
             let module = match item.inner {
-                clean::StrippedItem(box clean::ModuleItem(ref m)) | clean::ModuleItem(ref m) => m,
+                clean::StrippedItem(box clean::ModuleItem(ref m), _) | clean::ModuleItem(ref m) => m,
                 _ => unreachable!(),
             };
             let items = self.build_sidebar_items(module);
@@ -2862,7 +2871,7 @@ fn render_assoc_item(
         clean::StrippedItem(..) => {}
         clean::TyMethodItem(ref m) => method(w, item, m.header, &m.generics, &m.decl, link, parent),
         clean::MethodItem(ref m) => method(w, item, m.header, &m.generics, &m.decl, link, parent),
-        clean::AssocConstItem(ref ty, ref default) => assoc_const(
+        clean::AssocConstItem(ref ty, ref default, _) => assoc_const(
             w,
             item,
             ty,
@@ -2978,6 +2987,19 @@ fn item_union(w: &mut Buffer, cx: &Context, it: &clean::Item, s: &clean::Union,
     render_assoc_items(w, cx, it, it.def_id, AssocItemRender::All, cache)
 }
 
+fn print_tuple_variant_fields(w: &mut Buffer, fields: &[clean::Item]) {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            write!(w, ",&nbsp;");
+        }
+        match field.inner {
+            clean::StrippedItem(box clean::StructFieldItem(..), _) => write!(w, "_"),
+            clean::StructFieldItem(ref ty) => write!(w, "{}", ty.print()),
+            _ => unreachable!(),
+        }
+    }
+}
+
 fn item_enum(w: &mut Buffer, cx: &Context, it: &clean::Item, e: &clean::Enum, cache: &Cache) {
     wrap_into_docblock(w, |w| {
         write!(w, "<pre class='rust enum'>");
@@ -3000,14 +3022,9 @@ fn item_enum(w: &mut Buffer, cx: &Context, it: &clean::Item, e: &clean::Enum, ca
                 match v.inner {
                     clean::VariantItem(ref var) => match var.kind {
                         clean::VariantKind::CLike => write!(w, "{}", name),
-                        clean::VariantKind::Tuple(ref tys) => {
+                        clean::VariantKind::Tuple(ref fields) => {
                             write!(w, "{}(", name);
-                            for (i, ty) in tys.iter().enumerate() {
-                                if i > 0 {
-                                    write!(w, ",&nbsp;")
-                                }
-                                write!(w, "{}", ty.print());
-                            }
+                            print_tuple_variant_fields(w, fields);
                             write!(w, ")");
                         }
                         clean::VariantKind::Struct(ref s) => {
@@ -3048,14 +3065,9 @@ fn item_enum(w: &mut Buffer, cx: &Context, it: &clean::Item, e: &clean::Enum, ca
                 name = variant.name.as_ref().unwrap()
             );
             if let clean::VariantItem(ref var) = variant.inner {
-                if let clean::VariantKind::Tuple(ref tys) = var.kind {
+                if let clean::VariantKind::Tuple(ref fields) = var.kind {
                     write!(w, "(");
-                    for (i, ty) in tys.iter().enumerate() {
-                        if i > 0 {
-                            write!(w, ",&nbsp;");
-                        }
-                        write!(w, "{}", ty.print());
-                    }
+                    print_tuple_variant_fields(w, fields);
                     write!(w, ")");
                 }
             }
@@ -3064,7 +3076,8 @@ fn item_enum(w: &mut Buffer, cx: &Context, it: &clean::Item, e: &clean::Enum, ca
             document_non_exhaustive(w, variant);
 
             use crate::clean::{Variant, VariantKind};
-            if let clean::VariantItem(Variant { kind: VariantKind::Struct(ref s) }) = variant.inner
+            if let clean::VariantItem(Variant { kind: VariantKind::Struct(ref s), .. }) =
+                variant.inner
             {
                 let variant_id = cx.derive_id(format!(
                     "{}.{}.fields",
@@ -3207,7 +3220,7 @@ fn render_struct(
                     write!(w, ", ");
                 }
                 match field.inner {
-                    clean::StrippedItem(box clean::StructFieldItem(..)) => write!(w, "_"),
+                    clean::StrippedItem(box clean::StructFieldItem(..), _) => write!(w, "_"),
                     clean::StructFieldItem(ref ty) => {
                         write!(w, "{}{}", field.visibility.print_with_space(), ty.print())
                     }
@@ -3651,7 +3664,7 @@ fn render_impl(
                 assoc_type(w, item, &Vec::new(), Some(&tydef.type_), link.anchor(&id), "");
                 write!(w, "</code></h4>");
             }
-            clean::AssocConstItem(ref ty, ref default) => {
+            clean::AssocConstItem(ref ty, ref default, _) => {
                 let id = cx.derive_id(format!("{}.{}", item_type, name));
                 write!(w, "<h4 id='{}' class=\"{}{}\"><code>", id, item_type, extra_class);
                 assoc_const(w, item, ty, default.as_ref(), link.anchor(&id), "");
@@ -4413,7 +4426,7 @@ fn sidebar_enum(buf: &mut Buffer, it: &clean::Item, e: &clean::Enum) {
     }
 }
 
-fn item_ty_to_strs(ty: &ItemType) -> (&'static str, &'static str) {
+crate fn item_ty_to_strs(ty: &ItemType) -> (&'static str, &'static str) {
     match *ty {
         ItemType::ExternCrate | ItemType::Import => ("reexports", "Re-exports"),
         ItemType::Module => ("modules", "Modules"),
@@ -4593,7 +4606,7 @@ fn collect_paths_for_type(first_ty: clean::Type) -> Vec<String> {
             clean::Type::Slice(ty) => {
                 work.push_back(*ty);
             }
-            clean::Type::Array(ty, _) => {
+            clean::Type::Array(ty, ..) => {
                 work.push_back(*ty);
             }
             clean::Type::RawPointer(_, ty) => {
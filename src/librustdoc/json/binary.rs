@@ -0,0 +1,399 @@
+//! A serde serializer shared by the binary encodings of the JSON backend's output.
+//!
+//! The data model is the same as the one `serde_json` uses for the JSON output, so the structure
+//! of the output is identical: structs are maps keyed by field name, unit variants are strings
+//! and other enum variants are single-entry maps from the variant name to its contents. The
+//! [`Encoding`] decides how each of those values is written out.
+
+use std::error;
+use std::fmt::{self, Display};
+use std::marker::PhantomData;
+
+use serde::ser::{self, Serialize};
+
+#[derive(Debug)]
+pub struct Error(pub String);
+
+impl error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Container {
+    Array,
+    Map,
+}
+
+/// How the values of the serde data model are encoded by a binary format.
+pub trait Encoding {
+    fn write_nil(out: &mut Vec<u8>);
+    fn write_bool(out: &mut Vec<u8>, v: bool);
+    fn write_uint(out: &mut Vec<u8>, v: u64);
+    /// Writes any integer, including non-negative ones.
+    fn write_int(out: &mut Vec<u8>, v: i64);
+    fn write_f32(out: &mut Vec<u8>, v: f32);
+    fn write_f64(out: &mut Vec<u8>, v: f64);
+    fn write_str(out: &mut Vec<u8>, v: &str) -> Result<(), Error>;
+    fn write_bytes(out: &mut Vec<u8>, v: &[u8]) -> Result<(), Error>;
+    /// Writes the header of an array or map with `len` elements or entries.
+    fn write_header(out: &mut Vec<u8>, container: Container, len: usize) -> Result<(), Error>;
+    /// Writes the start of an array or map whose length isn't known up front.
+    fn begin_unsized(out: &mut Vec<u8>, container: Container);
+    /// Ends a container started with `begin_unsized`. `start` is the length of the output when
+    /// it was started, and `count` the number of elements or entries that were written.
+    fn end_unsized(
+        out: &mut Vec<u8>,
+        container: Container,
+        start: usize,
+        count: usize,
+    ) -> Result<(), Error>;
+}
+
+/// Encodes `value` with the given encoding.
+pub fn to_vec<E: Encoding, T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+    let mut ser = Serializer::<E> { out: Vec::new(), encoding: PhantomData };
+    value.serialize(&mut ser)?;
+    Ok(ser.out)
+}
+
+pub struct Serializer<E> {
+    out: Vec<u8>,
+    encoding: PhantomData<E>,
+}
+
+impl<E: Encoding> Serializer<E> {
+    fn begin(
+        &mut self,
+        container: Container,
+        len: Option<usize>,
+    ) -> Result<Compound<'_, E>, Error> {
+        let unsized_start = match len {
+            Some(len) => {
+                E::write_header(&mut self.out, container, len)?;
+                None
+            }
+            None => {
+                let start = self.out.len();
+                E::begin_unsized(&mut self.out, container);
+                Some(start)
+            }
+        };
+        Ok(Compound { ser: self, container, unsized_start, count: 0 })
+    }
+
+    /// Enum variants with contents are encoded as a map with a single entry.
+    fn begin_variant(&mut self, variant: &str) -> Result<(), Error> {
+        E::write_header(&mut self.out, Container::Map, 1)?;
+        E::write_str(&mut self.out, variant)
+    }
+}
+
+pub struct Compound<'a, E> {
+    ser: &'a mut Serializer<E>,
+    container: Container,
+    /// Where the header of a container of unknown length was written.
+    unsized_start: Option<usize>,
+    count: usize,
+}
+
+impl<E: Encoding> Compound<'_, E> {
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.count += 1;
+        value.serialize(&mut *self.ser)
+    }
+
+    fn entry<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Error> {
+        self.count += 1;
+        E::write_str(&mut self.ser.out, key)?;
+        value.serialize(&mut *self.ser)
+    }
+
+    fn finish(self) -> Result<(), Error> {
+        match self.unsized_start {
+            Some(pos) => E::end_unsized(&mut self.ser.out, self.container, pos, self.count),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a, E: Encoding> ser::Serializer for &'a mut Serializer<E> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Compound<'a, E>;
+    type SerializeTuple = Compound<'a, E>;
+    type SerializeTupleStruct = Compound<'a, E>;
+    type SerializeTupleVariant = Compound<'a, E>;
+    type SerializeMap = Compound<'a, E>;
+    type SerializeStruct = Compound<'a, E>;
+    type SerializeStructVariant = Compound<'a, E>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), Error> {
+        E::write_bool(&mut self.out, v);
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), Error> {
+        E::write_int(&mut self.out, v);
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), Error> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), Error> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), Error> {
+        self.serialize_u64(u64::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), Error> {
+        E::write_uint(&mut self.out, v);
+        Ok(())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<(), Error> {
+        E::write_f32(&mut self.out, v);
+        Ok(())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Error> {
+        E::write_f64(&mut self.out, v);
+        Ok(())
+    }
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        E::write_str(&mut self.out, v.encode_utf8(&mut [0; 4]))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        E::write_str(&mut self.out, v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+        E::write_bytes(&mut self.out, v)
+    }
+
+    fn serialize_none(self) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Error> {
+        E::write_nil(&mut self.out);
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        E::write_str(&mut self.out, variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.begin_variant(variant)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Compound<'a, E>, Error> {
+        self.begin(Container::Array, len)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Compound<'a, E>, Error> {
+        self.begin(Container::Array, Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Compound<'a, E>, Error> {
+        self.begin(Container::Array, Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Compound<'a, E>, Error> {
+        self.begin_variant(variant)?;
+        self.begin(Container::Array, Some(len))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Compound<'a, E>, Error> {
+        self.begin(Container::Map, len)
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Compound<'a, E>, Error> {
+        self.begin(Container::Map, Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Compound<'a, E>, Error> {
+        self.begin_variant(variant)?;
+        self.begin(Container::Map, Some(len))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+impl<E: Encoding> ser::SerializeSeq for Compound<'_, E> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl<E: Encoding> ser::SerializeTuple for Compound<'_, E> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl<E: Encoding> ser::SerializeTupleStruct for Compound<'_, E> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl<E: Encoding> ser::SerializeTupleVariant for Compound<'_, E> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl<E: Encoding> ser::SerializeMap for Compound<'_, E> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.element(key)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        value.serialize(&mut *self.ser)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl<E: Encoding> ser::SerializeStruct for Compound<'_, E> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.entry(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+impl<E: Encoding> ser::SerializeStructVariant for Compound<'_, E> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.entry(key, value)
+    }
+
+    fn end(self) -> Result<(), Error> {
+        self.finish()
+    }
+}
@@ -0,0 +1,119 @@
+//! The [CBOR](https://cbor.io) encoding, used by `--output-format cbor`. Integers, strings and
+//! containers use the shortest form of their header, and containers of unknown length use CBOR's
+//! indefinite-length encoding.
+
+use serde::Serialize;
+
+use crate::json::binary::{self, Container, Encoding, Error};
+
+#[cfg(test)]
+mod tests;
+
+/// Encodes `value` as CBOR.
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+    binary::to_vec::<Cbor, T>(value)
+}
+
+pub enum Cbor {}
+
+const UNSIGNED: u8 = 0;
+const NEGATIVE: u8 = 1;
+const BYTES: u8 = 2;
+const TEXT: u8 = 3;
+const ARRAY: u8 = 4;
+const MAP: u8 = 5;
+
+/// Marks the end of a container of indefinite length.
+const BREAK: u8 = 0xff;
+
+/// Writes the initial byte of an item of the given major type, followed by `v` in the smallest
+/// size that fits.
+fn write_head(out: &mut Vec<u8>, major: u8, v: u64) {
+    let major = major << 5;
+    if v < 24 {
+        out.push(major | v as u8);
+    } else if v <= u64::from(u8::MAX) {
+        out.push(major | 24);
+        out.push(v as u8);
+    } else if v <= u64::from(u16::MAX) {
+        out.push(major | 25);
+        out.extend_from_slice(&(v as u16).to_be_bytes());
+    } else if v <= u64::from(u32::MAX) {
+        out.push(major | 26);
+        out.extend_from_slice(&(v as u32).to_be_bytes());
+    } else {
+        out.push(major | 27);
+        out.extend_from_slice(&v.to_be_bytes());
+    }
+}
+
+fn container_major(container: Container) -> u8 {
+    match container {
+        Container::Array => ARRAY,
+        Container::Map => MAP,
+    }
+}
+
+impl Encoding for Cbor {
+    fn write_nil(out: &mut Vec<u8>) {
+        out.push(0xf6);
+    }
+
+    fn write_bool(out: &mut Vec<u8>, v: bool) {
+        out.push(if v { 0xf5 } else { 0xf4 });
+    }
+
+    fn write_uint(out: &mut Vec<u8>, v: u64) {
+        write_head(out, UNSIGNED, v);
+    }
+
+    fn write_int(out: &mut Vec<u8>, v: i64) {
+        if v >= 0 {
+            write_head(out, UNSIGNED, v as u64);
+        } else {
+            // Negative integers are stored as `-1 - v`.
+            write_head(out, NEGATIVE, !(v as u64));
+        }
+    }
+
+    fn write_f32(out: &mut Vec<u8>, v: f32) {
+        out.push(0xfa);
+        out.extend_from_slice(&v.to_be_bytes());
+    }
+
+    fn write_f64(out: &mut Vec<u8>, v: f64) {
+        out.push(0xfb);
+        out.extend_from_slice(&v.to_be_bytes());
+    }
+
+    fn write_str(out: &mut Vec<u8>, v: &str) -> Result<(), Error> {
+        write_head(out, TEXT, v.len() as u64);
+        out.extend_from_slice(v.as_bytes());
+        Ok(())
+    }
+
+    fn write_bytes(out: &mut Vec<u8>, v: &[u8]) -> Result<(), Error> {
+        write_head(out, BYTES, v.len() as u64);
+        out.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn write_header(out: &mut Vec<u8>, container: Container, len: usize) -> Result<(), Error> {
+        write_head(out, container_major(container), len as u64);
+        Ok(())
+    }
+
+    fn begin_unsized(out: &mut Vec<u8>, container: Container) {
+        out.push((container_major(container) << 5) | 31);
+    }
+
+    fn end_unsized(
+        out: &mut Vec<u8>,
+        _container: Container,
+        _start: usize,
+        _count: usize,
+    ) -> Result<(), Error> {
+        out.push(BREAK);
+        Ok(())
+    }
+}
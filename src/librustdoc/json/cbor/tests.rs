@@ -0,0 +1,54 @@
+use super::*;
+
+use std::collections::BTreeMap;
+
+use serde::ser::SerializeSeq;
+
+#[test]
+fn integers() {
+    assert_eq!(to_vec(&0u8).unwrap(), [0x00]);
+    assert_eq!(to_vec(&23u8).unwrap(), [0x17]);
+    assert_eq!(to_vec(&24u8).unwrap(), [0x18, 0x18]);
+    assert_eq!(to_vec(&1000u32).unwrap(), [0x19, 0x03, 0xe8]);
+    assert_eq!(to_vec(&1_000_000u32).unwrap(), [0x1a, 0x00, 0x0f, 0x42, 0x40]);
+    assert_eq!(to_vec(&-1i32).unwrap(), [0x20]);
+    assert_eq!(to_vec(&-100i32).unwrap(), [0x38, 0x63]);
+    assert_eq!(to_vec(&-1000i32).unwrap(), [0x39, 0x03, 0xe7]);
+}
+
+#[test]
+fn simple_values() {
+    assert_eq!(to_vec(&false).unwrap(), [0xf4]);
+    assert_eq!(to_vec(&true).unwrap(), [0xf5]);
+    assert_eq!(to_vec(&None::<u8>).unwrap(), [0xf6]);
+    assert_eq!(to_vec(&1.5f64).unwrap(), [0xfb, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn strings_and_containers() {
+    assert_eq!(to_vec("IETF").unwrap(), [0x64, 0x49, 0x45, 0x54, 0x46]);
+    assert_eq!(to_vec(&vec![1u8, 2, 3]).unwrap(), [0x83, 1, 2, 3]);
+    let mut map = BTreeMap::new();
+    map.insert("a", 1u8);
+    map.insert("b", 2u8);
+    assert_eq!(to_vec(&map).unwrap(), [0xa2, 0x61, b'a', 1, 0x61, b'b', 2]);
+    let out = to_vec(&vec![0u8; 25]).unwrap();
+    assert_eq!(out[..2], [0x98, 25]);
+}
+
+struct Unsized(Vec<u8>);
+
+impl Serialize for Unsized {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        for e in &self.0 {
+            seq.serialize_element(e)?;
+        }
+        seq.end()
+    }
+}
+
+#[test]
+fn unknown_lengths_are_indefinite() {
+    assert_eq!(to_vec(&Unsized(vec![1, 2])).unwrap(), [0x9f, 1, 2, 0xff]);
+}
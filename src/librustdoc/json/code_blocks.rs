@@ -0,0 +1,78 @@
+//! The code blocks of doc comments, extracted so that tools can run, show or index an item's
+//! examples without parsing its Markdown.
+
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag};
+
+use crate::doctest::Tester;
+use crate::html::markdown::{find_testable_code, map_line, opts, ErrorCodes, LangString};
+use crate::json::types;
+
+#[cfg(test)]
+mod tests;
+
+struct Doctests(Vec<types::Doctest>);
+
+impl Tester for Doctests {
+    fn add_test(&mut self, code: String, config: LangString, line: usize) {
+        self.0.push(types::Doctest { code, line, attrs: config.into() });
+    }
+}
+
+/// The code blocks of `docs` that `rustdoc --test` would run, in order. Their lines are counted
+/// from the start of `docs`.
+crate fn doctests(docs: &str, error_codes: ErrorCodes) -> Vec<types::Doctest> {
+    let mut tests = Doctests(Vec::new());
+    find_testable_code(docs, &mut tests, error_codes, false, None);
+    tests.0
+}
+
+/// Every code block of `docs`, in order.
+crate fn code_blocks(docs: &str) -> Vec<types::CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut parser = Parser::new_ext(docs, opts()).into_offset_iter();
+    while let Some((event, range)) = parser.next() {
+        let kind = match event {
+            Event::Start(Tag::CodeBlock(kind)) => kind,
+            _ => continue,
+        };
+        let (info, lang_string) = match kind {
+            // The JSON output is only available on nightly, where error codes are always read.
+            CodeBlockKind::Fenced(info) => {
+                let lang_string = LangString::parse_without_check(&info, ErrorCodes::Yes, false);
+                (info.into_string(), lang_string)
+            }
+            CodeBlockKind::Indented => (String::new(), LangString::all_false()),
+        };
+        let rust = lang_string.rust;
+        let lang = if rust {
+            Some("rust".to_owned())
+        } else {
+            info.split(|c: char| c == ',' || c.is_whitespace())
+                .find(|token| !token.is_empty())
+                .map(str::to_owned)
+        };
+        let mut text = String::new();
+        while let Some((Event::Text(s), _)) = parser.next() {
+            text.push_str(&s);
+        }
+        let code = if rust {
+            text.lines().filter_map(|l| map_line(l).for_html()).collect::<Vec<_>>().join("\n")
+        } else {
+            text.trim_end_matches('\n').to_owned()
+        };
+        blocks.push(types::CodeBlock {
+            lang,
+            info,
+            attrs: if rust { Some(lang_string.into()) } else { None },
+            code,
+            first_line: line_at(docs, range.start),
+            last_line: line_at(docs, docs[..range.end].trim_end_matches('\n').len()),
+        });
+    }
+    blocks
+}
+
+/// The line `offset` is on, counting from 1.
+fn line_at(docs: &str, offset: usize) -> usize {
+    docs[..offset].matches('\n').count() + 1
+}
@@ -0,0 +1,55 @@
+use super::*;
+
+#[test]
+fn only_rust_blocks() {
+    let docs = "Adds one.\n\n\
+                ```\n# let x = 1;\nassert_eq!(x + 1, 2);\n```\n\n\
+                ```text\nnot a test\n```\n\n\
+                ```should_panic,no_run\npanic!();\n```";
+    let tests = doctests(docs, ErrorCodes::Yes);
+    assert_eq!(tests.len(), 2);
+    assert_eq!(tests[0].code, "let x = 1;\nassert_eq!(x + 1, 2);");
+    assert_eq!(tests[0].line, 3);
+    assert!(!tests[0].attrs.should_panic && !tests[0].attrs.no_run && !tests[0].attrs.ignore);
+    assert_eq!(tests[1].line, 12);
+    assert!(tests[1].attrs.should_panic && tests[1].attrs.no_run);
+}
+
+#[test]
+fn ignored_and_failing() {
+    let docs = "```ignore\nfoo\n```\n\n```compile_fail,E0308\nlet x: u8 = \"\";\n```";
+    let tests = doctests(docs, ErrorCodes::Yes);
+    assert!(tests[0].attrs.ignore && !tests[0].attrs.compile_fail);
+    // `compile_fail` implies `no_run`.
+    assert!(tests[1].attrs.compile_fail && tests[1].attrs.no_run);
+    assert_eq!(tests[1].attrs.error_codes, ["E0308"]);
+}
+
+#[test]
+fn every_block() {
+    let docs = "Example:\n\n\
+                ```no_run\n# fn main() {\nrun();\n# }\n```\n\n\
+                ```toml, wrap\n[a]\n```\n\n    indented\n";
+    let blocks = code_blocks(docs);
+    assert_eq!(blocks.len(), 3);
+    assert_eq!(blocks[0].lang.as_deref(), Some("rust"));
+    assert_eq!(blocks[0].info, "no_run");
+    assert_eq!(blocks[0].code, "run();");
+    assert!(blocks[0].attrs.as_ref().unwrap().no_run);
+    assert_eq!((blocks[0].first_line, blocks[0].last_line), (3, 7));
+    assert_eq!(blocks[1].lang.as_deref(), Some("toml"));
+    assert_eq!(blocks[1].code, "[a]");
+    assert!(blocks[1].attrs.is_none());
+    assert_eq!((blocks[1].first_line, blocks[1].last_line), (9, 11));
+    assert_eq!(blocks[2].lang.as_deref(), Some("rust"));
+    assert_eq!(blocks[2].info, "");
+    assert_eq!((blocks[2].first_line, blocks[2].last_line), (13, 13));
+}
+
+#[test]
+fn editions() {
+    let blocks = code_blocks("```edition2018,ignore\nasync fn f() {}\n```");
+    let attrs = blocks[0].attrs.as_ref().unwrap();
+    assert_eq!(attrs.edition.as_deref(), Some("2018"));
+    assert!(attrs.ignore && !attrs.no_run);
+}
@@ -0,0 +1,1322 @@
+//! These from impls are used to create the JSON types which get serialized. They're very close to
+//! the `clean` types but with some fields removed or stringified to simplify the output and not
+//! expose unstable compiler internals. They borrow the `clean` types, so items don't need to be
+//! cloned to be converted.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::convert::{From, TryFrom, TryInto};
+
+use rustc_ast::ast;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_middle::middle::privacy::AccessLevel;
+use rustc_middle::ty;
+use rustc_span::def_id::DefId;
+use rustc_span::symbol::sym;
+use rustc_span::FileName;
+
+use crate::clean::cfg::Cfg as CleanCfg;
+use crate::clean::{self, AttributesExt, NestedAttributesExt};
+use crate::docfs::PathError;
+use crate::doctree;
+use crate::error::Error;
+use crate::formats::cache::cache;
+use crate::formats::item_type::ItemType;
+use crate::html::markdown::{Ignore, LangString};
+use crate::html::render::cache::ExternalLocation;
+use crate::json::code_blocks::code_blocks;
+use crate::json::markdown::summary;
+use crate::json::types::*;
+use crate::passes;
+
+impl TryFrom<&clean::Item> for Item {
+    type Error = Error;
+
+    fn try_from(item: &clean::Item) -> Result<Self, Error> {
+        let item_type = ItemType::from(item);
+        let is_non_exhaustive = item.is_non_exhaustive();
+        let must_use = item
+            .attrs
+            .other_attrs
+            .iter()
+            .find(|a| a.has_name(sym::must_use))
+            .map(|a| MustUse { message: a.value_str().map(|message| message.to_string()) });
+        let clean::Item {
+            source,
+            name,
+            attrs,
+            inner,
+            visibility,
+            def_id,
+            stability: _,
+            deprecation,
+        } = item;
+        let def_id = *def_id;
+        // `#[cfg]` attributes that are still around after expansion were satisfied, but they're
+        // still the best description of where the item is available. Fold them together with
+        // the (already propagated) `#[doc(cfg)]` of the item.
+        let cfg = attrs
+            .lists(sym::cfg)
+            .filter_map(|nested| nested.meta_item().and_then(|mi| CleanCfg::parse(mi).ok()))
+            .chain(attrs.cfg.as_deref().cloned())
+            .fold(None, |acc, cfg| match acc {
+                Some(acc) => Some(acc & cfg),
+                None => Some(cfg),
+            });
+        let docs = attrs.collapsed_doc_value().unwrap_or_default();
+        let mut aliases: Vec<_> = attrs.get_doc_aliases().into_iter().collect();
+        aliases.sort();
+        let repr = reprs(attrs);
+        let derived = is_derived(attrs);
+        let is_doc_hidden = attrs.lists(sym::doc).has_word(sym::hidden);
+        let stripped = match inner {
+            clean::StrippedItem(_, reason) => Some((*reason).into()),
+            _ => None,
+        };
+        let mut inner = inner.try_into().map_err(|e: Error| {
+            Error::new(
+                format!("skipped `{}`: {}", name.as_deref().unwrap_or("_"), e.error),
+                source.filename.to_string(),
+            )
+        })?;
+        match &mut inner {
+            ItemEnum::StructItem(s) => {
+                s.is_non_exhaustive = is_non_exhaustive;
+                s.must_use = must_use;
+                s.repr = repr;
+            }
+            ItemEnum::EnumItem(e) => {
+                e.is_non_exhaustive = is_non_exhaustive;
+                e.must_use = must_use;
+                e.repr = repr;
+            }
+            ItemEnum::VariantItem(v) => v.is_non_exhaustive = is_non_exhaustive,
+            ItemEnum::UnionItem(u) => {
+                u.must_use = must_use;
+                u.repr = repr;
+            }
+            ItemEnum::ImplItem(i) => i.is_derived = derived,
+            ItemEnum::FunctionItem(f) => {
+                f.must_use = must_use;
+                f.linkage = linkage(attrs);
+            }
+            ItemEnum::StaticItem(s) => s.linkage = linkage(attrs),
+            ItemEnum::ProcMacroItem(m) => {
+                if let MacroKind::Derive = m.kind {
+                    m.derived_trait = name.clone();
+                }
+            }
+            ItemEnum::MethodItem(m) => m.must_use = must_use,
+            ItemEnum::ExternCrateItem { crate_id, is_pub, macro_use, .. } => {
+                *crate_id = def_id.krate.as_u32();
+                *is_pub = *visibility == clean::Public;
+                let attr = attrs.other_attrs.iter().find(|a| a.has_name(sym::macro_use));
+                *macro_use = attr.map(|a| {
+                    let macros = a.meta_item_list().unwrap_or_default();
+                    macros.iter().filter_map(|m| m.ident()).map(|ident| ident.to_string()).collect()
+                });
+            }
+            _ => {}
+        }
+        Ok(Item {
+            id: from_def_id(def_id),
+            // Filled in by the renderer once everything else is.
+            hash: String::new(),
+            crate_id: def_id.krate.as_u32(),
+            // Filled in by the renderer, which knows where the item was found.
+            parent: None,
+            // Filled in by the renderer, which knows which trait the impl is for.
+            trait_item: None,
+            // Filled in by the renderer, which knows the `extern` blocks.
+            extern_block: None,
+            // Filled in by the renderer, which knows the macro invocations.
+            expansion: None,
+            name: name.clone(),
+            path: Vec::new(),
+            html_url: None,
+            source: source.into(),
+            visibility: visibility.into(),
+            // Filled in by the renderer, which has the access levels.
+            effective_visibility: None,
+            stripped,
+            is_doc_hidden,
+            // Filled in by the renderer when requested.
+            docs_html: None,
+            docs_ast: None,
+            doctests: None,
+            code_blocks: code_blocks(&docs),
+            docs_summary: summary(&docs),
+            docs,
+            links: links(attrs),
+            attrs: attrs
+                .other_attrs
+                .iter()
+                .filter(|attr| !is_cfg_attr(attr))
+                .map(rustc_ast_pretty::pprust::attribute_to_string)
+                .collect(),
+            aliases,
+            deprecation: deprecation.as_ref().map(Into::into),
+            cfg: cfg.as_ref().map(Into::into),
+            doc_cfg: attrs.cfg.as_deref().map(Into::into),
+            kind: match inner {
+                ItemEnum::DeclMacroItem(_) => ItemKind::DeclMacro,
+                _ => item_type.into(),
+            },
+            inner,
+        })
+    }
+}
+
+impl From<&clean::Span> for Option<Span> {
+    fn from(span: &clean::Span) -> Self {
+        let clean::Span { loline, locol, hiline, hicol, .. } = *span;
+        match &span.filename {
+            rustc_span::FileName::Real(name) => Some(Span {
+                filename: match name {
+                    rustc_span::RealFileName::Named(path) => path.clone(),
+                    rustc_span::RealFileName::Devirtualized { local_path, virtual_name: _ } => {
+                        local_path.clone()
+                    }
+                },
+                begin: (loline, locol),
+                end: (hiline, hicol),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the attribute is a `#[cfg(...)]` or `#[doc(cfg(...))]`, which are emitted through
+/// [`Item::cfg`] instead.
+fn is_cfg_attr(attr: &ast::Attribute) -> bool {
+    attr.has_name(sym::cfg)
+        || (attr.has_name(sym::doc)
+            && attr.meta().map_or(false, |mi| clean::Attributes::extract_cfg(&mi).is_some()))
+}
+
+/// Whether these are the attributes of an impl generated by one of the built-in derives, which
+/// mark their impls `#[automatically_derived]`.
+crate fn is_derived(attrs: &clean::Attributes) -> bool {
+    attrs.other_attrs.iter().any(|attr| attr.has_name(sym::automatically_derived))
+}
+
+/// The `#[no_mangle]`, `#[export_name]`, `#[link_name]` and `#[link_section]` of an item.
+fn linkage(attrs: &clean::Attributes) -> Linkage {
+    let value = |name| {
+        let attr = attrs.other_attrs.iter().find(|a| a.has_name(name))?;
+        attr.value_str().map(|value| value.to_string())
+    };
+    Linkage {
+        no_mangle: attrs.other_attrs.iter().any(|a| a.has_name(sym::no_mangle)),
+        export_name: value(sym::export_name),
+        link_name: value(sym::link_name),
+        link_section: value(sym::link_section),
+    }
+}
+
+/// The hints of the `#[repr]` attributes of an item, in order. These were already checked by the
+/// compiler, so hints it wouldn't accept are skipped rather than reported.
+fn reprs(attrs: &clean::Attributes) -> Vec<Repr> {
+    attrs
+        .lists(sym::repr)
+        .filter_map(|hint| {
+            if let Some((name, lit)) = hint.name_value_literal() {
+                let n = match lit.kind {
+                    ast::LitKind::Int(n, _) => n as u64,
+                    _ => return None,
+                };
+                return match name {
+                    sym::packed => Some(Repr::Packed(n)),
+                    sym::align => Some(Repr::Align(n)),
+                    _ => None,
+                };
+            }
+            match hint.name_or_empty() {
+                sym::C => Some(Repr::C),
+                sym::transparent => Some(Repr::Transparent),
+                sym::simd => Some(Repr::Simd),
+                sym::packed => Some(Repr::Packed(1)),
+                name @ (sym::i8
+                | sym::u8
+                | sym::i16
+                | sym::u16
+                | sym::i32
+                | sym::u32
+                | sym::i64
+                | sym::u64
+                | sym::i128
+                | sym::u128
+                | sym::isize
+                | sym::usize) => Some(Repr::Int(name.to_string())),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+impl From<&CleanCfg> for Cfg {
+    fn from(cfg: &CleanCfg) -> Self {
+        match cfg {
+            CleanCfg::True => Cfg::True,
+            CleanCfg::False => Cfg::False,
+            CleanCfg::Cfg(key, value) => {
+                Cfg::KeyValue { key: key.to_string(), value: value.map(|v| v.to_string()) }
+            }
+            CleanCfg::Not(cfg) => Cfg::Not(Box::new((&**cfg).into())),
+            CleanCfg::Any(cfgs) => Cfg::Any(cfgs.iter().map(Into::into).collect()),
+            CleanCfg::All(cfgs) => Cfg::All(cfgs.iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl From<&clean::Deprecation> for Deprecation {
+    fn from(deprecation: &clean::Deprecation) -> Self {
+        let clean::Deprecation { since, note, is_since_rustc_version: _ } = deprecation;
+        Deprecation { since: since.clone(), note: note.clone() }
+    }
+}
+
+impl From<&clean::Visibility> for Visibility {
+    fn from(v: &clean::Visibility) -> Self {
+        use clean::Visibility::*;
+        match v {
+            Public => Visibility::Public,
+            Inherited => Visibility::Default,
+            Crate => Visibility::Crate,
+            Restricted(did, path) => {
+                Visibility::Restricted { parent: from_def_id(*did), path: path.whole_name() }
+            }
+        }
+    }
+}
+
+impl From<clean::StripReason> for StripReason {
+    fn from(reason: clean::StripReason) -> Self {
+        match reason {
+            clean::StripReason::Private => StripReason::Private,
+            clean::StripReason::Hidden => StripReason::Hidden,
+        }
+    }
+}
+
+impl From<&clean::GenericArgs> for GenericArgs {
+    fn from(args: &clean::GenericArgs) -> Self {
+        use clean::GenericArgs::*;
+        match args {
+            AngleBracketed { args, bindings } => GenericArgs::AngleBracketed {
+                args: args.iter().map(Into::into).collect(),
+                bindings: bindings.iter().map(Into::into).collect(),
+            },
+            Parenthesized { inputs, output } => GenericArgs::Parenthesized {
+                inputs: inputs.iter().map(Into::into).collect(),
+                output: output.as_ref().map(Into::into),
+            },
+        }
+    }
+}
+
+impl From<&clean::GenericArg> for GenericArg {
+    fn from(arg: &clean::GenericArg) -> Self {
+        use clean::GenericArg::*;
+        match arg {
+            Lifetime(l) => GenericArg::Lifetime(l.0.clone()),
+            Type(t) => GenericArg::Type(t.into()),
+            Const(c) => GenericArg::Const(c.into()),
+        }
+    }
+}
+
+impl From<&clean::Constant> for Constant {
+    fn from(constant: &clean::Constant) -> Self {
+        let clean::Constant { type_, expr, value, is_literal } = constant;
+        Constant {
+            type_: type_.into(),
+            expr: expr.clone(),
+            value: value.clone(),
+            is_literal: *is_literal,
+        }
+    }
+}
+
+impl From<&clean::TypeBinding> for TypeBinding {
+    fn from(binding: &clean::TypeBinding) -> Self {
+        TypeBinding { name: binding.name.clone(), binding: (&binding.kind).into() }
+    }
+}
+
+impl From<&clean::TypeBindingKind> for TypeBindingKind {
+    fn from(kind: &clean::TypeBindingKind) -> Self {
+        use clean::TypeBindingKind::*;
+        match kind {
+            Equality { ty } => TypeBindingKind::Equality(ty.into()),
+            Constraint { bounds } => {
+                TypeBindingKind::Constraint(bounds.iter().map(Into::into).collect())
+            }
+        }
+    }
+}
+
+thread_local!(static COMPACT_IDS: RefCell<Option<CompactIds>> = Default::default());
+
+/// The IDs handed out with `--json-compact-ids`: sequential integers in the order items were
+/// first referenced, along with the ID each of them replaces.
+#[derive(Default)]
+struct CompactIds {
+    ids: FxHashMap<Id, Id>,
+    originals: Vec<Id>,
+}
+
+impl CompactIds {
+    fn get(&mut self, id: Id) -> Id {
+        if let Some(compact) = self.ids.get(&id) {
+            return compact.clone();
+        }
+        let compact = Id(self.originals.len().to_string());
+        self.originals.push(id.clone());
+        self.ids.insert(id, compact.clone());
+        compact
+    }
+}
+
+/// Makes [`from_def_id`] hand out sequential integers instead of def path hashes on this thread.
+crate fn enable_compact_ids() {
+    COMPACT_IDS.with(|c| *c.borrow_mut() = Some(CompactIds::default()));
+}
+
+/// Stops handing out compact IDs, returning the ID each of them replaces, indexed by the compact
+/// ID. Returns `None` if [`enable_compact_ids`] wasn't called.
+crate fn take_compact_ids() -> Option<Vec<Id>> {
+    COMPACT_IDS.with(|c| c.borrow_mut().take()).map(|c| c.originals)
+}
+
+/// The intra-doc links of the docs that resolved to an item, with the ID they resolved to.
+crate fn links(attrs: &clean::Attributes) -> BTreeMap<String, Id> {
+    attrs
+        .links
+        .iter()
+        .filter_map(|clean::ItemLink { link, did, .. }| {
+            did.map(|did| (link.clone(), from_def_id(did)))
+        })
+        .collect()
+}
+
+/// IDs are derived from the stable hash of the item's def path, so they stay the same between
+/// compilations as long as the item isn't moved or renamed. Synthetic impls have no def path and
+/// fall back to the crate number and index of their `DefId`.
+crate fn from_def_id(did: DefId) -> Id {
+    let id = match cache().def_path_hashes.get(&did) {
+        Some(hash) => {
+            let (hi, lo) = hash.0.as_value();
+            Id(format!("{:016x}{:016x}", hi, lo))
+        }
+        // The crate is given by its disambiguator, which unlike its number is the same in the
+        // output of every crate.
+        None => match cache().crate_disambiguators.get(&did.krate) {
+            Some(disambiguator) => Id(format!("{}:{}", disambiguator, u32::from(did.index))),
+            None => Id(format!("{}:{}", did.krate.as_u32(), u32::from(did.index))),
+        },
+    };
+    COMPACT_IDS.with(|c| match &mut *c.borrow_mut() {
+        Some(compact) => compact.get(id),
+        None => id,
+    })
+}
+
+impl TryFrom<&clean::ItemEnum> for ItemEnum {
+    type Error = Error;
+
+    fn try_from(item: &clean::ItemEnum) -> Result<Self, Error> {
+        use clean::ItemEnum::*;
+        Ok(match item {
+            ModuleItem(m) => ItemEnum::ModuleItem(m.into()),
+            ExternCrateItem(c, a) => ItemEnum::ExternCrateItem {
+                name: c.clone(),
+                rename: a.clone(),
+                // Read from the item
+                crate_id: 0,
+                is_pub: false,
+                macro_use: None,
+            },
+            ImportItem(i) => ItemEnum::ImportItem(i.into()),
+            StructItem(s) => ItemEnum::StructItem(s.into()),
+            UnionItem(u) => ItemEnum::UnionItem(u.into()),
+            StructFieldItem(f) => ItemEnum::StructFieldItem(f.into()),
+            EnumItem(e) => ItemEnum::EnumItem(e.into()),
+            VariantItem(v) => ItemEnum::VariantItem(v.into()),
+            FunctionItem(f) => ItemEnum::FunctionItem(f.into()),
+            ForeignFunctionItem(f) => ItemEnum::FunctionItem(f.into()),
+            TraitItem(t) => ItemEnum::TraitItem(t.into()),
+            TraitAliasItem(t) => ItemEnum::TraitAliasItem(t.into()),
+            MethodItem(m) => ItemEnum::MethodItem(m.into()),
+            TyMethodItem(m) => ItemEnum::MethodItem(m.into()),
+            ImplItem(i) => ItemEnum::ImplItem(i.into()),
+            StaticItem(s) => ItemEnum::StaticItem(s.into()),
+            ForeignStaticItem(s) => ItemEnum::StaticItem(s.into()),
+            ForeignTypeItem => ItemEnum::ForeignTypeItem,
+            TypedefItem(t, _) => ItemEnum::TypedefItem(t.into()),
+            OpaqueTyItem(t) => ItemEnum::OpaqueTyItem(t.into()),
+            ConstantItem(c) => ItemEnum::ConstantItem(c.into()),
+            MacroItem(m) if m.macro_rules => ItemEnum::MacroItem(m.into()),
+            MacroItem(m) => ItemEnum::DeclMacroItem(m.into()),
+            ProcMacroItem(m) => ItemEnum::ProcMacroItem(m.into()),
+            AssocConstItem(t, default, value) => ItemEnum::AssocConstItem {
+                type_: t.into(),
+                default: default.clone(),
+                value: value.clone(),
+            },
+            AssocTypeItem(g, t) => ItemEnum::AssocTypeItem {
+                bounds: g.iter().map(Into::into).collect(),
+                default: t.as_ref().map(Into::into),
+            },
+            StrippedItem(inner, _) => (&**inner).try_into()?,
+            PrimitiveItem(p) => ItemEnum::PrimitiveItem(Primitive {
+                name: p.as_str().to_string(),
+                impls: Vec::new(), // Added in JsonRenderer::item
+            }),
+            KeywordItem(k) => ItemEnum::KeywordItem(k.clone()),
+        })
+    }
+}
+
+impl From<&clean::Module> for Module {
+    fn from(module: &clean::Module) -> Self {
+        Module { is_crate: module.is_crate, items: ids(&module.items) }
+    }
+}
+
+impl From<&clean::Struct> for Struct {
+    fn from(struct_: &clean::Struct) -> Self {
+        let clean::Struct { struct_type, generics, fields, fields_stripped } = struct_;
+        Struct {
+            struct_type: (*struct_type).into(),
+            generics: generics.into(),
+            fields_stripped: *fields_stripped,
+            fields: ids(fields),
+            impls: Vec::new(), // Added in JsonRenderer::item
+            // Read from the attributes of the item
+            is_non_exhaustive: false,
+            must_use: None,
+            repr: Vec::new(),
+            derived: Vec::new(), // Added in JsonRenderer::item
+            layout: None,        // Added in JsonRenderer::item
+        }
+    }
+}
+
+impl From<&clean::Union> for Union {
+    fn from(union_: &clean::Union) -> Self {
+        let clean::Union { struct_type: _, generics, fields, fields_stripped } = union_;
+        Union {
+            generics: generics.into(),
+            fields_stripped: *fields_stripped,
+            fields: ids(fields),
+            impls: Vec::new(), // Added in JsonRenderer::item
+            // Read from the attributes of the item
+            must_use: None,
+            repr: Vec::new(),
+            derived: Vec::new(), // Added in JsonRenderer::item
+            layout: None,        // Added in JsonRenderer::item
+        }
+    }
+}
+
+impl From<doctree::StructType> for StructType {
+    fn from(struct_type: doctree::StructType) -> Self {
+        use doctree::StructType::*;
+        match struct_type {
+            Plain => StructType::Plain,
+            Tuple => StructType::Tuple,
+            Unit => StructType::Unit,
+        }
+    }
+}
+
+impl From<rustc_hir::FnHeader> for FnHeader {
+    fn from(header: rustc_hir::FnHeader) -> Self {
+        FnHeader {
+            is_unsafe: header.unsafety == rustc_hir::Unsafety::Unsafe,
+            is_const: header.constness == rustc_hir::Constness::Const,
+            is_async: header.asyncness == rustc_hir::IsAsync::Async,
+            abi: header.abi.into(),
+        }
+    }
+}
+
+impl From<&clean::Function> for Function {
+    fn from(function: &clean::Function) -> Self {
+        let clean::Function { decl, generics, header, all_types: _, ret_types: _ } = function;
+        Function {
+            decl: from_fn_decl(decl, header),
+            generics: generics.into(),
+            header: (*header).into(),
+            // Read from the attributes of the item
+            must_use: None,
+            linkage: Linkage::default(),
+        }
+    }
+}
+
+impl From<rustc_target::spec::abi::Abi> for Abi {
+    fn from(abi: rustc_target::spec::abi::Abi) -> Self {
+        use rustc_target::spec::abi::Abi::*;
+        match abi {
+            Rust => Abi::Rust,
+            C => Abi::C { unwind: false },
+            System => Abi::System { unwind: false },
+            Cdecl => Abi::Cdecl,
+            _ => Abi::Other(abi.name().to_owned()),
+        }
+    }
+}
+
+impl From<&clean::Generics> for Generics {
+    fn from(generics: &clean::Generics) -> Self {
+        Generics {
+            params: generics.params.iter().map(Into::into).collect(),
+            where_predicates: generics.where_predicates.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&clean::GenericParamDef> for GenericParamDef {
+    fn from(generic_param: &clean::GenericParamDef) -> Self {
+        let id = match generic_param.kind {
+            clean::GenericParamDefKind::Type { did, .. }
+            | clean::GenericParamDefKind::Const { did, .. } => Some(from_def_id(did)),
+            clean::GenericParamDefKind::Lifetime => None,
+        };
+        GenericParamDef {
+            id,
+            name: generic_param.name.clone(),
+            kind: (&generic_param.kind).into(),
+            // Filled in by the renderer for the parameters of types
+            variance: None,
+        }
+    }
+}
+
+impl From<ty::Variance> for Variance {
+    fn from(variance: ty::Variance) -> Self {
+        match variance {
+            ty::Variance::Covariant => Variance::Covariant,
+            ty::Variance::Invariant => Variance::Invariant,
+            ty::Variance::Contravariant => Variance::Contravariant,
+            ty::Variance::Bivariant => Variance::Bivariant,
+        }
+    }
+}
+
+impl From<&clean::GenericParamDefKind> for GenericParamDefKind {
+    fn from(kind: &clean::GenericParamDefKind) -> Self {
+        use clean::GenericParamDefKind::*;
+        match kind {
+            Lifetime => GenericParamDefKind::Lifetime,
+            Type { did: _, bounds, default, synthetic: _ } => GenericParamDefKind::Type {
+                bounds: bounds.iter().map(Into::into).collect(),
+                default: default.as_ref().map(Into::into),
+            },
+            Const { did: _, ty } => GenericParamDefKind::Const(ty.into()),
+        }
+    }
+}
+
+impl From<&clean::WherePredicate> for WherePredicate {
+    fn from(predicate: &clean::WherePredicate) -> Self {
+        use clean::WherePredicate::*;
+        match predicate {
+            BoundPredicate { ty, bounds } => WherePredicate::BoundPredicate {
+                ty: ty.into(),
+                bounds: bounds.iter().map(Into::into).collect(),
+            },
+            RegionPredicate { lifetime, bounds } => WherePredicate::RegionPredicate {
+                lifetime: lifetime.0.clone(),
+                bounds: bounds.iter().map(Into::into).collect(),
+            },
+            EqPredicate { lhs, rhs } => {
+                WherePredicate::EqPredicate { lhs: lhs.into(), rhs: rhs.into() }
+            }
+        }
+    }
+}
+
+impl From<&clean::GenericBound> for GenericBound {
+    fn from(bound: &clean::GenericBound) -> Self {
+        use clean::GenericBound::*;
+        match bound {
+            TraitBound(clean::PolyTrait { trait_, generic_params }, modifier) => {
+                GenericBound::TraitBound {
+                    trait_: trait_.into(),
+                    generic_params: generic_params.iter().map(Into::into).collect(),
+                    modifier: from_trait_bound_modifier(*modifier),
+                }
+            }
+            Outlives(lifetime) => GenericBound::Outlives(lifetime.0.clone()),
+        }
+    }
+}
+
+fn from_trait_bound_modifier(modifier: rustc_hir::TraitBoundModifier) -> TraitBoundModifier {
+    use rustc_hir::TraitBoundModifier::*;
+    match modifier {
+        None => TraitBoundModifier::None,
+        Maybe => TraitBoundModifier::Maybe,
+        MaybeConst => TraitBoundModifier::MaybeConst,
+    }
+}
+
+impl From<&clean::Type> for Type {
+    fn from(ty: &clean::Type) -> Self {
+        use clean::Type::*;
+        match ty {
+            // Trait objects are the path of their principal trait, along with their other bounds.
+            ResolvedPath { path, param_names: Some(bounds), did, is_generic: _ } => {
+                let principal = from_resolved_path(path, *did);
+                let mut traits = vec![PolyTrait { trait_: principal, generic_params: vec![] }];
+                let mut lifetime = None;
+                for bound in bounds {
+                    match bound {
+                        clean::GenericBound::TraitBound(poly_trait, _) => {
+                            traits.push(poly_trait.into())
+                        }
+                        clean::GenericBound::Outlives(l) => lifetime = Some(l.0.clone()),
+                    }
+                }
+                Type::DynTrait(DynTrait { traits, lifetime })
+            }
+            ResolvedPath { path, param_names: None, did, is_generic: _ } => {
+                from_resolved_path(path, *did)
+            }
+            Generic(s) => Type::Generic(s.clone()),
+            Primitive(p) => Type::Primitive(p.as_str().to_string()),
+            BareFunction(f) => Type::FunctionPointer(Box::new((&**f).into())),
+            Tuple(t) => Type::Tuple(t.iter().map(Into::into).collect()),
+            Slice(t) => Type::Slice(Box::new((&**t).into())),
+            Array(t, len, value) => {
+                Type::Array { type_: Box::new((&**t).into()), len: len.clone(), value: *value }
+            }
+            ImplTrait(g, position) => Type::ImplTrait {
+                bounds: g.iter().map(Into::into).collect(),
+                position: (*position).into(),
+            },
+            Never => Type::Never,
+            Infer => Type::Infer,
+            RawPointer(mutability, type_) => Type::RawPointer {
+                mutable: *mutability == ast::Mutability::Mut,
+                type_: Box::new((&**type_).into()),
+            },
+            BorrowedRef { lifetime, mutability, type_ } => Type::BorrowedRef {
+                lifetime: lifetime.as_ref().map(|l| l.0.clone()),
+                mutable: *mutability == ast::Mutability::Mut,
+                type_: Box::new((&**type_).into()),
+            },
+            QPath { name, args, self_type, trait_ } => Type::QualifiedPath {
+                name: name.clone(),
+                args: args.as_ref().map(|args| Box::new((&**args).into())),
+                self_type: Box::new((&**self_type).into()),
+                trait_: Box::new((&**trait_).into()),
+            },
+        }
+    }
+}
+
+/// Converts the path of a resolved type, keeping the generic arguments of its last segment.
+fn from_resolved_path(path: &clean::Path, did: DefId) -> Type {
+    Type::ResolvedPath {
+        name: path.whole_name(),
+        id: from_def_id(did),
+        args: path.segments.last().map(|segment| Box::new((&segment.args).into())),
+    }
+}
+
+impl From<&clean::PolyTrait> for PolyTrait {
+    fn from(poly_trait: &clean::PolyTrait) -> Self {
+        let clean::PolyTrait { trait_, generic_params } = poly_trait;
+        PolyTrait {
+            trait_: trait_.into(),
+            generic_params: generic_params.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<clean::ImplTraitPosition> for ImplTraitPosition {
+    fn from(position: clean::ImplTraitPosition) -> Self {
+        match position {
+            clean::ImplTraitPosition::Argument => ImplTraitPosition::Argument,
+            clean::ImplTraitPosition::Return => ImplTraitPosition::Return,
+            clean::ImplTraitPosition::Other => ImplTraitPosition::Other,
+        }
+    }
+}
+
+impl From<&clean::BareFunctionDecl> for FunctionPointer {
+    fn from(bare_decl: &clean::BareFunctionDecl) -> Self {
+        let clean::BareFunctionDecl { unsafety, generic_params, decl, abi } = bare_decl;
+        FunctionPointer {
+            is_unsafe: *unsafety == rustc_hir::Unsafety::Unsafe,
+            generic_params: generic_params.iter().map(Into::into).collect(),
+            decl: decl.into(),
+            abi: (*abi).into(),
+        }
+    }
+}
+
+impl From<&clean::FnDecl> for FnDecl {
+    fn from(decl: &clean::FnDecl) -> Self {
+        let clean::FnDecl { inputs, output, c_variadic, attrs: _ } = decl;
+        FnDecl {
+            inputs: inputs
+                .values
+                .iter()
+                .map(|arg| (arg.name.clone(), (&arg.type_).into()))
+                .collect(),
+            output: match output {
+                clean::FnRetTy::Return(t) => Some(t.into()),
+                clean::FnRetTy::DefaultReturn => None,
+            },
+            c_variadic: *c_variadic,
+        }
+    }
+}
+
+/// Converts the declaration of a function with the given header. Async functions are desugared
+/// to return `impl Future<Output = T>`, so their `output` is replaced by the `T` they were
+/// written with, as shown by the HTML backend.
+fn from_fn_decl(decl: &clean::FnDecl, header: &rustc_hir::FnHeader) -> FnDecl {
+    if header.asyncness != rustc_hir::IsAsync::Async {
+        return decl.into();
+    }
+    let output = match decl.sugared_async_return_type() {
+        // The desugaring always names the output, even for `async fn f()`.
+        clean::FnRetTy::Return(clean::Tuple(tys)) if tys.is_empty() => None,
+        clean::FnRetTy::Return(t) => Some((&t).into()),
+        clean::FnRetTy::DefaultReturn => None,
+    };
+    FnDecl { output, ..decl.into() }
+}
+
+impl From<&clean::Trait> for Trait {
+    fn from(trait_: &clean::Trait) -> Self {
+        let clean::Trait {
+            auto: _,
+            unsafety,
+            items,
+            generics,
+            bounds,
+            is_spotlight: _,
+            is_auto,
+            is_object_safe,
+            supertraits,
+            is_sealed,
+        } = trait_;
+        let mut required_items = Vec::new();
+        let mut provided_items = Vec::new();
+        for item in items.iter().filter(|item| !item.is_stripped()) {
+            let provided = match &item.inner {
+                clean::MethodItem(_) => true,
+                clean::AssocConstItem(_, default, _) => default.is_some(),
+                clean::AssocTypeItem(_, default) => default.is_some(),
+                _ => false,
+            };
+            let id = from_def_id(item.def_id);
+            if provided { provided_items.push(id) } else { required_items.push(id) }
+        }
+        Trait {
+            is_auto: *is_auto,
+            is_object_safe: *is_object_safe,
+            is_sealed: *is_sealed,
+            is_unsafe: *unsafety == rustc_hir::Unsafety::Unsafe,
+            items: ids(items),
+            required_items,
+            provided_items,
+            generics: generics.into(),
+            bounds: bounds.iter().map(Into::into).collect(),
+            supertraits: supertraits.iter().copied().map(from_def_id).collect(),
+            implementors: Vec::new(), // Added in JsonRenderer::item
+        }
+    }
+}
+
+impl From<&clean::Impl> for Impl {
+    fn from(impl_: &clean::Impl) -> Self {
+        let clean::Impl {
+            unsafety,
+            constness,
+            generics,
+            provided_trait_methods,
+            trait_,
+            for_,
+            items,
+            polarity,
+            synthetic,
+            blanket_impl,
+        } = impl_;
+        Impl {
+            is_unsafe: *unsafety == rustc_hir::Unsafety::Unsafe,
+            is_const: *constness == rustc_hir::Constness::Const,
+            generics: generics.into(),
+            provided_trait_methods: provided_trait_methods.iter().cloned().collect(),
+            trait_: trait_.as_ref().map(Into::into),
+            for_: for_.into(),
+            items: ids(items),
+            negative: *polarity == Some(clean::ImplPolarity::Negative),
+            synthetic: *synthetic,
+            blanket_impl: blanket_impl.as_ref().map(Into::into),
+            // Read from the attributes of the item
+            is_derived: false,
+        }
+    }
+}
+
+impl From<clean::SelfTy> for Receiver {
+    fn from(self_ty: clean::SelfTy) -> Self {
+        match self_ty {
+            clean::SelfValue => Receiver::Value,
+            clean::SelfBorrowed(lifetime, mutability) => Receiver::Borrowed {
+                lifetime: lifetime.map(|l| l.0),
+                mutable: mutability == ast::Mutability::Mut,
+            },
+            clean::SelfExplicit(ty) => Receiver::Explicit((&ty).into()),
+        }
+    }
+}
+
+impl From<&clean::Function> for Method {
+    fn from(function: &clean::Function) -> Self {
+        let clean::Function { header, decl, generics, all_types: _, ret_types: _ } = function;
+        Method {
+            receiver: decl.self_type().map(Into::into),
+            decl: from_fn_decl(decl, header),
+            generics: generics.into(),
+            header: (*header).into(),
+            has_body: true,
+            is_default: false,
+            // Read from the attributes of the item
+            must_use: None,
+        }
+    }
+}
+
+impl From<&clean::TyMethod> for Method {
+    fn from(method: &clean::TyMethod) -> Self {
+        let clean::TyMethod { header, decl, generics, all_types: _, ret_types: _ } = method;
+        Method {
+            receiver: decl.self_type().map(Into::into),
+            decl: from_fn_decl(decl, header),
+            generics: generics.into(),
+            header: (*header).into(),
+            has_body: false,
+            is_default: false,
+            // Read from the attributes of the item
+            must_use: None,
+        }
+    }
+}
+
+impl From<&clean::Method> for Method {
+    fn from(method: &clean::Method) -> Self {
+        let clean::Method { header, decl, generics, defaultness, all_types: _, ret_types: _ } =
+            method;
+        Method {
+            receiver: decl.self_type().map(Into::into),
+            decl: from_fn_decl(decl, header),
+            generics: generics.into(),
+            header: (*header).into(),
+            has_body: true,
+            is_default: defaultness.map_or(false, |d| d.is_default()),
+            // Read from the attributes of the item
+            must_use: None,
+        }
+    }
+}
+
+impl From<&clean::Enum> for Enum {
+    fn from(enum_: &clean::Enum) -> Self {
+        let clean::Enum { variants, generics, variants_stripped } = enum_;
+        Enum {
+            generics: generics.into(),
+            variants_stripped: *variants_stripped,
+            variants: ids(variants),
+            impls: Vec::new(), // Added in JsonRenderer::item
+            // Read from the attributes of the item
+            is_non_exhaustive: false,
+            must_use: None,
+            repr: Vec::new(),
+            derived: Vec::new(), // Added in JsonRenderer::item
+            layout: None,        // Added in JsonRenderer::item
+        }
+    }
+}
+
+impl From<&clean::VariantStruct> for Struct {
+    fn from(struct_: &clean::VariantStruct) -> Self {
+        let clean::VariantStruct { struct_type, fields, fields_stripped } = struct_;
+        Struct {
+            struct_type: (*struct_type).into(),
+            generics: Default::default(),
+            fields_stripped: *fields_stripped,
+            fields: ids(fields),
+            impls: Vec::new(),
+            is_non_exhaustive: false,
+            must_use: None,
+            repr: Vec::new(),
+            derived: Vec::new(),
+            layout: None,
+        }
+    }
+}
+
+impl From<&clean::Variant> for Variant {
+    fn from(variant: &clean::Variant) -> Self {
+        use clean::VariantKind::*;
+        let kind = match &variant.kind {
+            CLike => VariantKind::Plain,
+            Tuple(fields) => VariantKind::Tuple(ids(fields)),
+            Struct(s) => VariantKind::Struct(ids(&s.fields)),
+        };
+        // `is_non_exhaustive` is read from the attributes of the item.
+        Variant {
+            kind,
+            is_non_exhaustive: false,
+            discriminant: variant.discriminant.as_ref().map(Into::into),
+        }
+    }
+}
+
+impl From<&clean::TypeLayout> for TypeLayout {
+    fn from(layout: &clean::TypeLayout) -> Self {
+        let clean::TypeLayout { size, align, field_offsets, niche_values } = layout;
+        TypeLayout {
+            size: *size,
+            align: *align,
+            field_offsets: field_offsets
+                .iter()
+                .map(|&(did, offset)| (from_def_id(did), offset))
+                .collect(),
+            niche_values: niche_values.map(|n| n.to_string()),
+        }
+    }
+}
+
+impl From<&clean::ExternBlock> for ExternBlock {
+    fn from(block: &clean::ExternBlock) -> Self {
+        ExternBlock {
+            id: from_def_id(block.def_id),
+            abi: block.abi.into(),
+            attrs: block.attrs.iter().map(rustc_ast_pretty::pprust::attribute_to_string).collect(),
+        }
+    }
+}
+
+impl From<&clean::Expansion> for Expansion {
+    fn from(expansion: &clean::Expansion) -> Self {
+        Expansion {
+            macro_id: expansion.macro_def_id.map(from_def_id),
+            name: expansion.name.clone(),
+            kind: from_macro_kind(expansion.kind),
+            call_site: (&expansion.call_site).into(),
+            def_site: (&expansion.def_site).into(),
+        }
+    }
+}
+
+impl From<&clean::Discriminant> for Discriminant {
+    fn from(discriminant: &clean::Discriminant) -> Self {
+        let clean::Discriminant { expr, value } = discriminant;
+        Discriminant { expr: expr.clone(), value: value.clone() }
+    }
+}
+
+impl From<&clean::Import> for Import {
+    fn from(import: &clean::Import) -> Self {
+        use clean::ImportKind::*;
+        let reexports = import.source.reexports.iter().copied().map(from_def_id).collect();
+        match &import.kind {
+            Simple(s) => Import {
+                span: import.source.path.whole_name(),
+                name: s.clone(),
+                id: import.source.did.map(from_def_id),
+                glob: false,
+                inlined: !import.should_be_displayed,
+                reexports,
+                items: None,
+            },
+            Glob(items) => Import {
+                span: import.source.path.whole_name(),
+                name: import.source.path.last_name().to_string(),
+                id: import.source.did.map(from_def_id),
+                glob: true,
+                inlined: !import.should_be_displayed,
+                reexports,
+                items: Some(items.iter().copied().map(from_def_id).collect()),
+            },
+        }
+    }
+}
+
+impl From<&clean::Macro> for Macro {
+    fn from(mac: &clean::Macro) -> Self {
+        Macro { source: mac.source.clone(), rules: mac.rules.iter().map(Into::into).collect() }
+    }
+}
+
+impl From<&clean::MacroRule> for MacroRule {
+    fn from(rule: &clean::MacroRule) -> Self {
+        MacroRule {
+            matcher: rule.matcher.iter().map(Into::into).collect(),
+            has_transcriber: rule.has_transcriber,
+        }
+    }
+}
+
+impl From<&clean::MacroMatcher> for MacroMatcher {
+    fn from(matcher: &clean::MacroMatcher) -> Self {
+        use clean::MacroMatcher::*;
+        use rustc_ast::token::DelimToken;
+        match matcher {
+            Token(s) => MacroMatcher::Token(s.clone()),
+            Fragment { name, kind } => {
+                MacroMatcher::Fragment { name: name.clone(), kind: kind.clone() }
+            }
+            Repetition { matchers, separator, op } => MacroMatcher::Repetition {
+                matchers: matchers.iter().map(Into::into).collect(),
+                separator: separator.clone(),
+                op: op.to_string(),
+            },
+            Delimited(delim, matchers) => MacroMatcher::Delimited {
+                delimiter: match delim {
+                    DelimToken::Paren => MacroDelimiter::Parenthesis,
+                    DelimToken::Bracket => MacroDelimiter::Bracket,
+                    DelimToken::Brace | DelimToken::NoDelim => MacroDelimiter::Brace,
+                },
+                matchers: matchers.iter().map(Into::into).collect(),
+            },
+        }
+    }
+}
+
+impl From<&clean::ProcMacro> for ProcMacro {
+    fn from(mac: &clean::ProcMacro) -> Self {
+        ProcMacro {
+            kind: from_macro_kind(mac.kind),
+            // Read from the name of the item
+            derived_trait: None,
+            helpers: mac.helpers.clone(),
+        }
+    }
+}
+
+fn from_macro_kind(kind: rustc_span::hygiene::MacroKind) -> MacroKind {
+    use rustc_span::hygiene::MacroKind::*;
+    match kind {
+        Bang => MacroKind::Bang,
+        Attr => MacroKind::Attr,
+        Derive => MacroKind::Derive,
+    }
+}
+
+impl From<&clean::Typedef> for Typedef {
+    fn from(typedef: &clean::Typedef) -> Self {
+        let clean::Typedef { type_, generics, item_type: _ } = typedef;
+        Typedef { type_: type_.into(), generics: generics.into() }
+    }
+}
+
+impl From<&clean::OpaqueTy> for OpaqueTy {
+    fn from(opaque: &clean::OpaqueTy) -> Self {
+        OpaqueTy {
+            bounds: opaque.bounds.iter().map(Into::into).collect(),
+            generics: (&opaque.generics).into(),
+        }
+    }
+}
+
+impl From<&clean::Static> for Static {
+    fn from(stat: &clean::Static) -> Self {
+        Static {
+            type_: (&stat.type_).into(),
+            mutable: stat.mutability == ast::Mutability::Mut,
+            expr: stat.expr.clone(),
+            // Read from the attributes of the item
+            linkage: Linkage::default(),
+        }
+    }
+}
+
+impl From<&clean::TraitAlias> for TraitAlias {
+    fn from(alias: &clean::TraitAlias) -> Self {
+        TraitAlias {
+            generics: (&alias.generics).into(),
+            params: alias.bounds.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ItemType> for ItemKind {
+    fn from(kind: ItemType) -> Self {
+        use ItemType::*;
+        match kind {
+            Module => ItemKind::Module,
+            ExternCrate => ItemKind::ExternCrate,
+            Import => ItemKind::Import,
+            Struct => ItemKind::Struct,
+            Union => ItemKind::Union,
+            Enum => ItemKind::Enum,
+            Function => ItemKind::Function,
+            Typedef => ItemKind::Typedef,
+            OpaqueTy => ItemKind::OpaqueTy,
+            Static => ItemKind::Static,
+            Constant => ItemKind::Constant,
+            Trait => ItemKind::Trait,
+            Impl => ItemKind::Impl,
+            TyMethod | Method => ItemKind::Method,
+            StructField => ItemKind::StructField,
+            Variant => ItemKind::Variant,
+            Macro => ItemKind::Macro,
+            Primitive => ItemKind::Primitive,
+            AssocConst => ItemKind::AssocConst,
+            AssocType => ItemKind::AssocType,
+            ForeignType => ItemKind::ForeignType,
+            Keyword => ItemKind::Keyword,
+            TraitAlias => ItemKind::TraitAlias,
+            ProcAttribute => ItemKind::ProcAttribute,
+            ProcDerive => ItemKind::ProcDerive,
+        }
+    }
+}
+
+impl From<LangString> for CodeBlockAttrs {
+    fn from(lang_string: LangString) -> Self {
+        CodeBlockAttrs {
+            should_panic: lang_string.should_panic,
+            no_run: lang_string.no_run,
+            ignore: lang_string.ignore != Ignore::None,
+            compile_fail: lang_string.compile_fail,
+            error_codes: lang_string.error_codes,
+            test_harness: lang_string.test_harness,
+            allow_fail: lang_string.allow_fail,
+            edition: lang_string.edition.map(|e| e.to_string()),
+        }
+    }
+}
+
+impl From<passes::ItemCount> for CoverageCount {
+    fn from(count: passes::ItemCount) -> Self {
+        let passes::ItemCount { total, with_docs, total_examples, with_examples } = count;
+        CoverageCount { total, with_docs, total_examples, with_examples }
+    }
+}
+
+impl From<passes::ItemCoverage> for ItemCoverage {
+    fn from(item: passes::ItemCoverage) -> Self {
+        ItemCoverage {
+            has_docs: item.has_docs,
+            should_have_docs: item.should_have_docs,
+            has_example: item.has_doc_example,
+            should_have_example: item.should_have_doc_example,
+        }
+    }
+}
+
+crate fn from_access_level(level: Option<AccessLevel>) -> EffectiveVisibility {
+    match level {
+        None => EffectiveVisibility::Private,
+        Some(AccessLevel::ReachableFromImplTrait | AccessLevel::Reachable) => {
+            EffectiveVisibility::Reachable
+        }
+        Some(AccessLevel::Exported) => EffectiveVisibility::Exported,
+        Some(AccessLevel::Public) => EffectiveVisibility::Public,
+    }
+}
+
+/// Sums up the coverage of the given items for their modules and the whole crate.
+crate fn from_doc_coverage(
+    items: impl IntoIterator<Item = (DefId, passes::ItemCoverage)>,
+) -> Coverage {
+    let mut total = passes::ItemCount::default();
+    let mut modules: BTreeMap<Id, passes::ItemCount> = BTreeMap::new();
+    let mut coverage = Coverage::default();
+    for (did, item) in items {
+        let module = modules.entry(from_def_id(item.module)).or_default();
+        module.count_item(
+            item.has_docs,
+            item.has_doc_example,
+            item.should_have_doc_example,
+            item.should_have_docs,
+        );
+        coverage.items.insert(from_def_id(did), item.into());
+    }
+    for count in modules.values() {
+        total += *count;
+    }
+    coverage.total = total.into();
+    coverage.modules = modules.into_iter().map(|(id, count)| (id, count.into())).collect();
+    coverage
+}
+
+fn ids<'a>(items: impl IntoIterator<Item = &'a clean::Item>) -> Vec<Id> {
+    items.into_iter().filter(|x| !x.is_stripped()).map(|i| from_def_id(i.def_id)).collect()
+}
+
+crate fn from_external_crate(
+    krate: &clean::ExternalCrate,
+    location: &ExternalLocation,
+) -> ExternalCrate {
+    let (source, version) = match crate_source(&krate.src) {
+        Some((source, version)) => (Some(source), version),
+        None => (None, None),
+    };
+    ExternalCrate {
+        name: krate.name.clone(),
+        html_root_url: match location {
+            ExternalLocation::Remote(s) => Some(s.clone()),
+            _ => None,
+        },
+        // This is the same directory the HTML backend links to with `../<crate>/`.
+        html_local_path: match location {
+            ExternalLocation::Local => Some(format!("{}/", krate.name)),
+            _ => None,
+        },
+        version,
+        hash: krate.hash.map(|hash| hash.to_string()),
+        disambiguator: krate.disambiguator.to_string(),
+        source,
+    }
+}
+
+/// Works out where a crate came from based on where Cargo put its sources: registry crates are
+/// unpacked to `registry/src/<index>/<name>-<version>` and git dependencies are checked out to
+/// `git/checkouts/<repo>/<revision>`. Returns the version too if it's known.
+fn crate_source(src: &FileName) -> Option<(CrateSource, Option<String>)> {
+    let path = match src {
+        FileName::Real(path) => path.local_path(),
+        _ => return None,
+    };
+    let components: Vec<_> =
+        path.components().map(|c| c.as_os_str().to_string_lossy().into_owned()).collect();
+    let find = |first: &str, second: &str| {
+        components.windows(2).position(|w| w[0] == first && w[1] == second).map(|i| i + 2)
+    };
+    if let Some(i) = find("registry", "src") {
+        // The package directory comes after the one of the index.
+        let version = components.get(i + 1).and_then(|dir| {
+            // Package names can contain dashes too, so look for the one that starts the version.
+            dir.match_indices('-').map(|(i, _)| &dir[i + 1..]).find(|v| is_version(v))
+        });
+        Some((CrateSource::Registry, version.map(str::to_string)))
+    } else if find("git", "checkouts").is_some() {
+        Some((CrateSource::Git, None))
+    } else {
+        Some((CrateSource::Path(path.display().to_string()), None))
+    }
+}
+
+/// Whether `s` starts with a `major.minor.patch` version number.
+fn is_version(s: &str) -> bool {
+    let mut parts = s.splitn(3, '.');
+    let is_number = |part: Option<&str>| {
+        part.map_or(false, |p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()))
+    };
+    is_number(parts.next())
+        && is_number(parts.next())
+        && parts.next().map_or(false, |p| p.starts_with(|c: char| c.is_ascii_digit()))
+}
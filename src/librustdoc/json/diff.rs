@@ -0,0 +1,63 @@
+//! `--json-diff`: the items added, removed and changed between an earlier output of the crate and
+//! this one, as a starting point for tools looking for API changes.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::json::types::{Diff, Id, Item, ItemChange};
+
+#[cfg(test)]
+mod tests;
+
+/// The fields of [`Item`] that aren't compared: the ones that change whenever code is moved around
+/// in its file without the item itself changing, and the hash of the others.
+const IGNORED_FIELDS: &[&str] = &["source", "expansion", "hash"];
+
+/// Compares the items of two indexes, matched up by ID. Each list of the diff is sorted by path.
+crate fn diff(old: &BTreeMap<Id, Item>, new: &BTreeMap<Id, Item>) -> Diff {
+    let mut diff = Diff::default();
+    for (id, old_item) in old {
+        match new.get(id) {
+            Some(new_item) => {
+                let fields = changed_fields(&to_value(old_item), &to_value(new_item));
+                if !fields.is_empty() {
+                    diff.changed.push(change(new_item, fields));
+                }
+            }
+            None => diff.removed.push(change(old_item, Vec::new())),
+        }
+    }
+    for (id, new_item) in new {
+        if !old.contains_key(id) {
+            diff.added.push(change(new_item, Vec::new()));
+        }
+    }
+    // The lists are already sorted by ID, which breaks ties between items with the same path.
+    for changes in vec![&mut diff.added, &mut diff.removed, &mut diff.changed] {
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+    diff
+}
+
+fn change(item: &Item, fields: Vec<String>) -> ItemChange {
+    ItemChange { id: item.id.clone(), path: item.path.clone(), kind: item.kind.clone(), fields }
+}
+
+fn to_value(item: &Item) -> Value {
+    serde_json::to_value(item).expect("items always serialize to JSON")
+}
+
+/// The names of the fields that differ between two serialized items, leaving out the ones that
+/// only locate the item in the source and its hash.
+fn changed_fields(old: &Value, new: &Value) -> Vec<String> {
+    let (old, new) = match (old.as_object(), new.as_object()) {
+        (Some(old), Some(new)) => (old, new),
+        _ => return Vec::new(),
+    };
+    old.keys()
+        .chain(new.keys().filter(|name| !old.contains_key(*name)))
+        .filter(|name| !IGNORED_FIELDS.contains(&name.as_str()) && old.get(*name) != new.get(*name))
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,24 @@
+use serde_json::json;
+
+use super::*;
+
+#[test]
+fn changed_fields_ignore_spans() {
+    let old = json!({ "name": "f", "source": { "begin": [1, 0] }, "expansion": null, "hash": "0" });
+    let new = json!({ "name": "f", "source": { "begin": [2, 0] }, "expansion": {}, "hash": "1" });
+    assert!(changed_fields(&old, &new).is_empty());
+}
+
+#[test]
+fn changed_fields_are_named() {
+    let old = json!({ "name": "f", "docs": "Old.", "inner": { "function": { "abi": "Rust" } } });
+    let new = json!({ "name": "f", "docs": "New.", "inner": { "function": { "abi": "C" } } });
+    assert_eq!(changed_fields(&old, &new), ["docs", "inner"]);
+}
+
+#[test]
+fn added_fields_are_changed() {
+    let old = json!({ "name": "f" });
+    let new = json!({ "name": "f", "cfg": "unix" });
+    assert_eq!(changed_fields(&old, &new), ["cfg"]);
+}
@@ -0,0 +1,137 @@
+//! The subset of the index kept with `--json-ffi-only`: what a C header generator needs to
+//! describe the crate's C API, and nothing else.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::json::types::{
+    Abi, GenericArg, GenericArgs, Id, Item, ItemEnum, Repr, Type, TypeBindingKind, VariantKind,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Removes everything from `index` but the functions with a foreign ABI, the statics that are
+/// declared in `extern` blocks or exported under a symbol of their own, the types with a
+/// `#[repr]` giving them a stable layout, and the items all of those refer to.
+crate fn retain_ffi(index: &mut BTreeMap<Id, Item>) {
+    let mut kept = BTreeSet::new();
+    let mut queue: Vec<Id> =
+        index.iter().filter(|(_, item)| is_root(item)).map(|(id, _)| id.clone()).collect();
+    while let Some(id) = queue.pop() {
+        if !kept.insert(id.clone()) {
+            continue;
+        }
+        if let Some(item) = index.get(&id) {
+            queue.extend(dependencies(item));
+        }
+    }
+    index.retain(|id, _| kept.contains(id));
+}
+
+fn is_root(item: &Item) -> bool {
+    match &item.inner {
+        ItemEnum::FunctionItem(f) => is_foreign_abi(&f.header.abi),
+        ItemEnum::StaticItem(s) => {
+            item.extern_block.is_some() || s.linkage.no_mangle || s.linkage.export_name.is_some()
+        }
+        ItemEnum::StructItem(s) => has_stable_layout(&s.repr),
+        ItemEnum::UnionItem(u) => has_stable_layout(&u.repr),
+        ItemEnum::EnumItem(e) => has_stable_layout(&e.repr),
+        ItemEnum::ForeignTypeItem => true,
+        _ => false,
+    }
+}
+
+/// Whether functions with this ABI can be called from other languages. The ABIs of the compiler's
+/// intrinsics are named `rust-intrinsic`, `rust-call` and `platform-intrinsic`.
+fn is_foreign_abi(abi: &Abi) -> bool {
+    match abi {
+        Abi::Rust => false,
+        Abi::Other(name) => !name.starts_with("rust-") && name != "platform-intrinsic",
+        _ => true,
+    }
+}
+
+fn has_stable_layout(repr: &[Repr]) -> bool {
+    repr.iter().any(|hint| matches!(hint, Repr::C | Repr::Transparent | Repr::Int(_)))
+}
+
+/// The IDs of the fields and variants of `item`, and of the items named in its types.
+fn dependencies(item: &Item) -> Vec<Id> {
+    let mut ids = Vec::new();
+    match &item.inner {
+        ItemEnum::FunctionItem(f) => {
+            for (_, ty) in &f.decl.inputs {
+                type_ids(ty, &mut ids);
+            }
+            f.decl.output.iter().for_each(|ty| type_ids(ty, &mut ids));
+        }
+        ItemEnum::StaticItem(s) => type_ids(&s.type_, &mut ids),
+        ItemEnum::StructItem(s) => ids.extend(s.fields.iter().cloned()),
+        ItemEnum::UnionItem(u) => ids.extend(u.fields.iter().cloned()),
+        ItemEnum::EnumItem(e) => ids.extend(e.variants.iter().cloned()),
+        ItemEnum::VariantItem(v) => match &v.kind {
+            VariantKind::Plain => {}
+            VariantKind::Tuple(fields) | VariantKind::Struct(fields) => {
+                ids.extend(fields.iter().cloned())
+            }
+        },
+        ItemEnum::StructFieldItem(ty) => type_ids(ty, &mut ids),
+        ItemEnum::TypedefItem(t) => type_ids(&t.type_, &mut ids),
+        _ => {}
+    }
+    ids
+}
+
+/// Collects the IDs of the items `ty` names, including in its generic arguments.
+fn type_ids(ty: &Type, ids: &mut Vec<Id>) {
+    match ty {
+        Type::ResolvedPath { id, args, .. } => {
+            ids.push(id.clone());
+            if let Some(args) = args {
+                generic_args_ids(args, ids);
+            }
+        }
+        Type::FunctionPointer(f) => {
+            for (_, ty) in &f.decl.inputs {
+                type_ids(ty, ids);
+            }
+            f.decl.output.iter().for_each(|ty| type_ids(ty, ids));
+        }
+        Type::Tuple(tys) => tys.iter().for_each(|ty| type_ids(ty, ids)),
+        Type::Slice(ty)
+        | Type::Array { type_: ty, .. }
+        | Type::RawPointer { type_: ty, .. }
+        | Type::BorrowedRef { type_: ty, .. } => type_ids(ty, ids),
+        Type::QualifiedPath { self_type, trait_, .. } => {
+            type_ids(self_type, ids);
+            type_ids(trait_, ids);
+        }
+        Type::DynTrait(_)
+        | Type::Generic(_)
+        | Type::Primitive(_)
+        | Type::ImplTrait { .. }
+        | Type::Never
+        | Type::Infer => {}
+    }
+}
+
+fn generic_args_ids(args: &GenericArgs, ids: &mut Vec<Id>) {
+    match args {
+        GenericArgs::AngleBracketed { args, bindings } => {
+            for arg in args {
+                if let GenericArg::Type(ty) = arg {
+                    type_ids(ty, ids);
+                }
+            }
+            for binding in bindings {
+                if let TypeBindingKind::Equality(ty) = &binding.binding {
+                    type_ids(ty, ids);
+                }
+            }
+        }
+        GenericArgs::Parenthesized { inputs, output } => {
+            inputs.iter().chain(output).for_each(|ty| type_ids(ty, ids));
+        }
+    }
+}
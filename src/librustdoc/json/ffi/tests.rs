@@ -0,0 +1,38 @@
+use super::*;
+
+fn path(id: &str, args: Vec<Type>) -> Type {
+    let args = args.into_iter().map(GenericArg::Type).collect();
+    Type::ResolvedPath {
+        name: id.to_owned(),
+        id: Id(id.to_owned()),
+        args: Some(Box::new(GenericArgs::AngleBracketed { args, bindings: Vec::new() })),
+    }
+}
+
+#[test]
+fn foreign_abis() {
+    assert!(!is_foreign_abi(&Abi::Rust));
+    assert!(is_foreign_abi(&Abi::C { unwind: false }));
+    assert!(is_foreign_abi(&Abi::Other("stdcall".to_owned())));
+    assert!(!is_foreign_abi(&Abi::Other("rust-intrinsic".to_owned())));
+    assert!(!is_foreign_abi(&Abi::Other("platform-intrinsic".to_owned())));
+}
+
+#[test]
+fn stable_layouts() {
+    assert!(has_stable_layout(&[Repr::C]));
+    assert!(has_stable_layout(&[Repr::Align(8), Repr::Int("u8".to_owned())]));
+    assert!(!has_stable_layout(&[]));
+    assert!(!has_stable_layout(&[Repr::Packed(1)]));
+}
+
+#[test]
+fn ids_in_types() {
+    let ty = Type::RawPointer {
+        mutable: false,
+        type_: Box::new(path("0:1", vec![Type::Tuple(vec![path("0:2", vec![])])])),
+    };
+    let mut ids = Vec::new();
+    type_ids(&ty, &mut ids);
+    assert_eq!(ids, vec![Id("0:1".to_owned()), Id("0:2".to_owned())]);
+}
@@ -0,0 +1,50 @@
+//! The hashes in the output, which tell consumers whether anything changed since an earlier
+//! output without comparing the items themselves: the `hash` of each item, and the `api_hash` of
+//! the crate, which build systems can compare to tell whether the crates depending on it need to
+//! be checked again.
+
+use std::collections::BTreeMap;
+use std::hash::Hash;
+
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::stable_hasher::StableHasher;
+
+use crate::json::semver::is_public;
+use crate::json::types::{Id, Item};
+
+/// The fields of an item that make up its signature. The others, like its docs and where it is in
+/// the source, can change without the API changing.
+const SIGNATURE_FIELDS: &[&str] =
+    &["name", "path", "visibility", "effective_visibility", "kind", "inner"];
+
+/// Hashes the signature of `item`, if it's part of the public API.
+crate fn item_hash(item: &Item) -> Option<Fingerprint> {
+    if !is_public(item) {
+        return None;
+    }
+    let value = serde_json::to_value(item).ok()?;
+    let mut hasher = StableHasher::new();
+    item.id.0.hash(&mut hasher);
+    for &field in SIGNATURE_FIELDS {
+        value.get(field).map(|value| value.to_string()).hash(&mut hasher);
+    }
+    Some(hasher.finish())
+}
+
+/// Hashes everything in `item` but its `hash`. The fields are hashed as JSON, whose objects have
+/// sorted keys, so the hash doesn't depend on the order they're declared in.
+crate fn content_hash(item: &Item) -> String {
+    let mut value = serde_json::to_value(item).expect("items always serialize to JSON");
+    if let Some(fields) = value.as_object_mut() {
+        fields.remove("hash");
+    }
+    let mut hasher = StableHasher::new();
+    value.to_string().hash(&mut hasher);
+    let hash: Fingerprint = hasher.finish();
+    hash.to_hex()
+}
+
+/// Combines the hashes of all the public items into the hash of the API.
+crate fn api_hash(item_hashes: &BTreeMap<Id, Fingerprint>) -> String {
+    item_hashes.values().fold(Fingerprint::ZERO, |hash, &item| hash.combine(item)).to_hex()
+}
@@ -0,0 +1,98 @@
+//! `--json-incremental`: the items whose HIR didn't change since the last run are taken from its
+//! output rather than converted again. The fields the renderer fills in from the rest of the crate
+//! (like `parent` and `impls`) are still worked out from scratch, and so are the ones the
+//! conversion takes from other items (like the targets of intra-doc links), which the hash of the
+//! HIR doesn't cover.
+//!
+//! The hash of the HIR each item was converted from is written to `<crate>.hashes.json` next to
+//! the output.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+
+use rustc_data_structures::fingerprint::Fingerprint;
+
+use crate::clean;
+use crate::config::OutputFormat;
+use crate::error::Error;
+use crate::json::conversions::{from_def_id, links};
+use crate::json::types::{Crate, Id, Item, ItemEnum};
+
+#[cfg(test)]
+mod tests;
+
+/// The output of the last run, with the hashes its items were converted from.
+crate struct Previous {
+    hashes: BTreeMap<Id, String>,
+    index: BTreeMap<Id, Item>,
+}
+
+impl Previous {
+    /// Reads the last output of the crate from `out_path`, if there's one that can be read.
+    ///
+    /// The hashes are removed once read, so that they never outlive the output they describe if
+    /// this run doesn't get to write its own.
+    crate fn read(out_path: &Path, name: &str) -> Option<Self> {
+        let hashes_path = hashes_path(out_path, name);
+        let file = BufReader::new(File::open(&hashes_path).ok()?);
+        let hashes = serde_json::from_reader(file).ok();
+        fs::remove_file(&hashes_path).ok()?;
+        let output_path = super::output_path(out_path, name, OutputFormat::Json, false, None);
+        let file = BufReader::new(File::open(&output_path).ok()?);
+        let krate: Crate = serde_json::from_reader(file).ok()?;
+        Some(Previous { hashes: hashes?, index: krate.index })
+    }
+
+    /// The item from the last output, if `item` was converted from HIR with the same hash. The
+    /// fields that depend on other items are taken from `item` again: its links, whether it's
+    /// stripped (which depends on the visibility of its parents), what its imports resolve to and
+    /// the supertraits of a trait.
+    ///
+    /// Modules are never reused, since the items inlined into them by glob imports of private
+    /// modules aren't part of their HIR.
+    crate fn item(&self, item: &clean::Item, hash: Fingerprint) -> Option<Item> {
+        let (inner, stripped) = match &item.inner {
+            clean::StrippedItem(inner, reason) => (&**inner, Some((*reason).into())),
+            inner => (inner, None),
+        };
+        if let clean::ModuleItem(_) = inner {
+            return None;
+        }
+        let id = from_def_id(item.def_id);
+        if *self.hashes.get(&id)? != hash.to_hex() {
+            return None;
+        }
+        let mut reused = self.index.get(&id)?.clone();
+        reused.links = links(&item.attrs);
+        reused.stripped = stripped;
+        match (&mut reused.inner, inner) {
+            (ItemEnum::ImportItem(import), clean::ImportItem(new)) => *import = new.into(),
+            (ItemEnum::TraitItem(trait_), clean::TraitItem(new)) => {
+                trait_.is_object_safe = new.is_object_safe;
+                trait_.is_sealed = new.is_sealed;
+                trait_.supertraits = new.supertraits.iter().copied().map(from_def_id).collect();
+            }
+            _ => {}
+        }
+        Some(reused)
+    }
+}
+
+/// Writes the hashes of the items in the output, for the next run to compare against.
+crate fn write_hashes(
+    out_path: &Path,
+    name: &str,
+    hashes: impl Iterator<Item = (Id, Fingerprint)>,
+) -> Result<(), Error> {
+    let hashes: BTreeMap<Id, String> = hashes.map(|(id, hash)| (id, hash.to_hex())).collect();
+    let p = hashes_path(out_path, name);
+    let file = BufWriter::new(try_err!(File::create(&p), &p));
+    try_err!(serde_json::ser::to_writer(file, &hashes), &p);
+    Ok(())
+}
+
+fn hashes_path(out_path: &Path, name: &str) -> PathBuf {
+    out_path.join(format!("{}.hashes.json", name))
+}
@@ -0,0 +1,85 @@
+use std::convert::TryInto;
+
+use rustc_hir::def_id::{DefId, DefIndex, LOCAL_CRATE};
+
+use crate::json::types::StripReason;
+
+use super::*;
+
+fn def_id(index: u32) -> DefId {
+    DefId { krate: LOCAL_CRATE, index: DefIndex::from_u32(index) }
+}
+
+/// A trait whose docs link to `link` and which inherits from `supertrait`.
+fn trait_(link: u32, supertrait: u32) -> clean::Item {
+    let mut attrs = clean::Attributes::default();
+    attrs.links.push(clean::ItemLink {
+        link: "Target".to_owned(),
+        link_text: "Target".to_owned(),
+        did: Some(def_id(link)),
+        fragment: None,
+    });
+    clean::Item {
+        source: clean::Span::empty(),
+        name: Some("Trait".to_owned()),
+        attrs,
+        inner: clean::TraitItem(clean::Trait {
+            auto: false,
+            unsafety: rustc_hir::Unsafety::Normal,
+            items: Vec::new(),
+            generics: clean::Generics::default(),
+            bounds: Vec::new(),
+            is_spotlight: false,
+            is_auto: false,
+            is_object_safe: true,
+            supertraits: vec![def_id(supertrait)],
+            is_sealed: false,
+        }),
+        visibility: clean::Public,
+        def_id: def_id(1),
+        stability: None,
+        deprecation: None,
+    }
+}
+
+/// The output of a run that converted `old` from HIR with `hash`.
+fn last_run(old: &clean::Item, hash: Fingerprint) -> Previous {
+    Previous {
+        hashes: vec![(from_def_id(old.def_id), hash.to_hex())].into_iter().collect(),
+        index: vec![(from_def_id(old.def_id), old.try_into().unwrap())].into_iter().collect(),
+    }
+}
+
+#[test]
+fn reused_items_follow_other_items() {
+    let hash = Fingerprint::from_smaller_hash(1);
+    let previous = last_run(&trait_(2, 3), hash);
+
+    // Only the link target and the supertrait changed, which the hash of the HIR doesn't cover.
+    let item = previous.item(&trait_(4, 5), hash).unwrap();
+    assert_eq!(item.links.get("Target"), Some(&from_def_id(def_id(4))));
+    match item.inner {
+        ItemEnum::TraitItem(t) => assert_eq!(t.supertraits, vec![from_def_id(def_id(5))]),
+        inner => panic!("expected a trait, found {:?}", inner),
+    }
+
+    assert!(previous.item(&trait_(4, 5), Fingerprint::from_smaller_hash(2)).is_none());
+}
+
+#[test]
+fn reused_items_follow_the_visibility_of_their_parents() {
+    let hash = Fingerprint::from_smaller_hash(1);
+    let previous = last_run(&trait_(2, 3), hash);
+
+    // The module of the trait was made private, which only changes the HIR of the module.
+    let mut stripped = trait_(2, 3);
+    stripped.inner = clean::StrippedItem(box stripped.inner, clean::StripReason::Private);
+    let item = previous.item(&stripped, hash).unwrap();
+    assert_eq!(item.stripped, Some(StripReason::Private));
+    assert!(matches!(item.inner, ItemEnum::TraitItem(_)));
+
+    let mut module = trait_(2, 3);
+    module.inner = clean::ModuleItem(clean::Module { items: Vec::new(), is_crate: false });
+    let previous = last_run(&module, hash);
+    assert!(previous.item(&module, hash).is_none());
+}
@@ -0,0 +1,174 @@
+//! The [LSIF](https://microsoft.github.io/language-server-protocol/specifications/lsif/0.5.0/specification/)
+//! dump written by `--output-format lsif`, for code intelligence indexers.
+//!
+//! It records the definition of every local item with a source location, with the item's docs
+//! as hover text and its path as an exported moniker so that indexes of dependent crates can
+//! link to it. Rustdoc doesn't see where items are used, so there are no references. Modules
+//! are left out since their range would span every other item of the file.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::json::types;
+use crate::markdown_output::nest_docs;
+
+#[cfg(test)]
+mod tests;
+
+/// Writes the LSIF dump of `krate` to `out`, one vertex or edge per line. The source paths of
+/// items are relative to `root`, the directory rustdoc was invoked in.
+crate fn write(out: &mut impl Write, krate: &types::Crate, root: &Path) -> io::Result<()> {
+    let mut dump = Dump { out, next_id: 0 };
+    dump.emit(json!({
+        "type": "vertex",
+        "label": "metaData",
+        "version": "0.5.0",
+        "projectRoot": file_uri(root),
+        "positionEncoding": "utf-16",
+        "toolInfo": { "name": "rustdoc", "version": krate.rustdoc_version },
+    }))?;
+    let project = dump.emit(json!({
+        "type": "vertex",
+        "label": "project",
+        "kind": "rust",
+        "name": krate.name,
+    }))?;
+
+    // The ranges of each file, with the definition results they belong to. Documents are
+    // written once all their ranges are known.
+    let mut documents: BTreeMap<&Path, Vec<(u64, u64)>> = BTreeMap::new();
+    for item in krate.index.values() {
+        if item.crate_id != krate.crate_num
+            || item.kind == types::ItemKind::Module
+            || item.name.is_none()
+        {
+            continue;
+        }
+        let span = match &item.source {
+            Some(span) => span,
+            None => continue,
+        };
+        let result_set = dump.emit(json!({ "type": "vertex", "label": "resultSet" }))?;
+        let range = dump.emit(json!({
+            "type": "vertex",
+            "label": "range",
+            "start": position(span.begin),
+            "end": position(span.end),
+        }))?;
+        dump.emit(edge("next", range, result_set))?;
+
+        let hover = dump.emit(json!({
+            "type": "vertex",
+            "label": "hoverResult",
+            "result": { "contents": { "kind": "markdown", "value": hover_text(item) } },
+        }))?;
+        dump.emit(edge("textDocument/hover", result_set, hover))?;
+
+        if let types::Visibility::Public = item.visibility {
+            let moniker = dump.emit(json!({
+                "type": "vertex",
+                "label": "moniker",
+                "scheme": "rustdoc",
+                "identifier": item.path.join("::"),
+                "kind": "export",
+            }))?;
+            dump.emit(edge("moniker", result_set, moniker))?;
+        }
+
+        let definition = dump.emit(json!({ "type": "vertex", "label": "definitionResult" }))?;
+        dump.emit(edge("textDocument/definition", result_set, definition))?;
+        documents.entry(span.filename.as_path()).or_default().push((range, definition));
+    }
+
+    let mut document_ids = Vec::new();
+    for (path, ranges) in documents {
+        let document = dump.emit(json!({
+            "type": "vertex",
+            "label": "document",
+            "uri": file_uri(&root.join(path)),
+            "languageId": "rust",
+        }))?;
+        let range_ids: Vec<_> = ranges.iter().map(|&(range, _)| range).collect();
+        dump.emit(json!({
+            "type": "edge",
+            "label": "contains",
+            "outV": document,
+            "inVs": range_ids,
+        }))?;
+        for (range, definition) in ranges {
+            dump.emit(json!({
+                "type": "edge",
+                "label": "item",
+                "outV": definition,
+                "inVs": [range],
+                "document": document,
+            }))?;
+        }
+        document_ids.push(document);
+    }
+    dump.emit(json!({
+        "type": "edge",
+        "label": "contains",
+        "outV": project,
+        "inVs": document_ids,
+    }))?;
+    Ok(())
+}
+
+struct Dump<'a, W> {
+    out: &'a mut W,
+    next_id: u64,
+}
+
+impl<W: Write> Dump<'_, W> {
+    /// Writes `element` with the next free ID, and returns that ID.
+    fn emit(&mut self, mut element: Value) -> io::Result<u64> {
+        self.next_id += 1;
+        element["id"] = self.next_id.into();
+        serde_json::to_writer(&mut *self.out, &element)?;
+        self.out.write_all(b"\n")?;
+        Ok(self.next_id)
+    }
+}
+
+fn edge(label: &str, out_v: u64, in_v: u64) -> Value {
+    json!({ "type": "edge", "label": label, "outV": out_v, "inV": in_v })
+}
+
+/// Converts the one-based line and zero-based column of a span to an LSIF position.
+fn position((line, column): (usize, usize)) -> Value {
+    json!({ "line": line.saturating_sub(1), "character": column })
+}
+
+/// The kind and path of `item`, followed by its docs.
+fn hover_text(item: &types::Item) -> String {
+    let kind = match serde_json::to_value(&item.kind) {
+        Ok(Value::String(kind)) => kind,
+        _ => String::new(),
+    };
+    let mut text = format!("```rust\n{} {}\n```", kind, item.path.join("::"));
+    if !item.docs.is_empty() {
+        text.push_str("\n\n");
+        text.push_str(&nest_docs(&item.docs, 0));
+    }
+    text
+}
+
+/// The `file://` URI of `path`, percent-encoding everything but the unreserved characters and
+/// separators.
+fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from(if path.starts_with('/') { "file://" } else { "file:///" });
+    for b in path.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+                uri.push(b as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    uri
+}
@@ -0,0 +1,28 @@
+use super::*;
+
+#[test]
+fn file_uris() {
+    assert_eq!(
+        file_uri(Path::new("/home/me/my crate/src/lib.rs")),
+        "file:///home/me/my%20crate/src/lib.rs"
+    );
+    assert_eq!(file_uri(Path::new("C:\\src\\lib.rs")), "file:///C:/src/lib.rs");
+}
+
+#[test]
+fn positions_are_zero_based() {
+    assert_eq!(position((1, 4)), json!({ "line": 0, "character": 4 }));
+}
+
+#[test]
+fn elements_get_increasing_ids() {
+    let mut out = Vec::new();
+    let mut dump = Dump { out: &mut out, next_id: 0 };
+    assert_eq!(dump.emit(json!({ "type": "vertex", "label": "resultSet" })).unwrap(), 1);
+    assert_eq!(dump.emit(edge("next", 3, 1)).unwrap(), 2);
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "{\"id\":1,\"label\":\"resultSet\",\"type\":\"vertex\"}\n\
+         {\"id\":2,\"inV\":1,\"label\":\"next\",\"outV\":3,\"type\":\"edge\"}\n"
+    );
+}
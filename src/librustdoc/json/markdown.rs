@@ -0,0 +1,129 @@
+//! The Markdown of doc comments, taken apart so that tools get the structure of the docs
+//! without parsing them the way rustdoc does: their summary line, and their whole tree with
+//! `--json-docs-ast`.
+
+use std::collections::BTreeMap;
+
+use pulldown_cmark::{Alignment, BrokenLink, CodeBlockKind, CowStr, Event, Parser, Tag};
+
+use crate::html::markdown::opts;
+use crate::json::types::{self, DocNode};
+
+#[cfg(test)]
+mod tests;
+
+/// The Markdown of the first paragraph of `docs` on a single line, which the HTML backend shows
+/// in the item tables of modules. Like there, this is empty if the docs start with a code block.
+crate fn summary(docs: &str) -> String {
+    let mut parser = Parser::new_ext(docs, opts()).into_offset_iter();
+    let source = match parser.next() {
+        Some((Event::Start(Tag::Heading(_)), range)) => {
+            docs[range].trim().trim_matches('#').trim_end_matches(|c| c == '=' || c == '-')
+        }
+        Some((Event::Start(Tag::CodeBlock(_)), _)) | None => "",
+        Some((_, range)) => &docs[range],
+    };
+    source.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses `docs` into a tree. Intra-doc links found in `links` (see [`types::Item::links`])
+/// become links whose URL is the link as written, so that they can be looked up there.
+crate fn parse(docs: &str, links: &BTreeMap<String, types::Id>) -> Vec<DocNode> {
+    let mut resolve = |link: BrokenLink<'_>| {
+        if links.contains_key(link.reference) {
+            Some((CowStr::from(link.reference.to_owned()), CowStr::from("")))
+        } else {
+            None
+        }
+    };
+    let parser = Parser::new_with_broken_link_callback(docs, opts(), Some(&mut resolve));
+    // The tags we're in, with the nodes found in each so far.
+    let mut stack: Vec<(Tag<'_>, Vec<DocNode>)> = Vec::new();
+    let mut nodes = Vec::new();
+    for event in parser {
+        let node = match event {
+            Event::Start(tag) => {
+                stack.push((tag, Vec::new()));
+                continue;
+            }
+            Event::End(_) => {
+                let (tag, children) = stack.pop().expect("unbalanced Markdown events");
+                tag_node(tag, children)
+            }
+            Event::Text(text) => DocNode::Text(text.into_string()),
+            Event::Code(code) => DocNode::Code(code.into_string()),
+            Event::Html(html) => DocNode::Html(html.into_string()),
+            Event::FootnoteReference(label) => DocNode::FootnoteReference(label.into_string()),
+            Event::SoftBreak => DocNode::SoftBreak,
+            Event::HardBreak => DocNode::HardBreak,
+            Event::Rule => DocNode::Rule,
+            // Task lists aren't enabled.
+            Event::TaskListMarker(_) => continue,
+        };
+        let siblings = match stack.last_mut() {
+            Some((_, children)) => children,
+            None => &mut nodes,
+        };
+        // The parser splits text around characters that could have started some markup.
+        if let DocNode::Text(more) = &node {
+            if let Some(DocNode::Text(text)) = siblings.last_mut() {
+                text.push_str(more);
+                continue;
+            }
+        }
+        siblings.push(node);
+    }
+    nodes
+}
+
+/// The node of an element, from its tag and the nodes it contains.
+fn tag_node(tag: Tag<'_>, children: Vec<DocNode>) -> DocNode {
+    match tag {
+        Tag::Paragraph => DocNode::Paragraph(children),
+        Tag::Heading(level) => DocNode::Heading { level, children },
+        Tag::BlockQuote => DocNode::BlockQuote(children),
+        Tag::CodeBlock(kind) => {
+            let info = match kind {
+                CodeBlockKind::Fenced(info) => Some(info.into_string()),
+                CodeBlockKind::Indented => None,
+            };
+            let code = children
+                .into_iter()
+                .filter_map(|child| match child {
+                    DocNode::Text(text) => Some(text),
+                    _ => None,
+                })
+                .collect();
+            DocNode::CodeBlock { info, code }
+        }
+        Tag::List(start) => DocNode::List { start, children },
+        Tag::Item => DocNode::ListItem(children),
+        Tag::FootnoteDefinition(label) => {
+            DocNode::FootnoteDefinition { label: label.into_string(), children }
+        }
+        Tag::Table(alignments) => DocNode::Table {
+            alignments: alignments
+                .into_iter()
+                .map(|alignment| match alignment {
+                    Alignment::None => types::Alignment::None,
+                    Alignment::Left => types::Alignment::Left,
+                    Alignment::Center => types::Alignment::Center,
+                    Alignment::Right => types::Alignment::Right,
+                })
+                .collect(),
+            children,
+        },
+        Tag::TableHead => DocNode::TableHead(children),
+        Tag::TableRow => DocNode::TableRow(children),
+        Tag::TableCell => DocNode::TableCell(children),
+        Tag::Emphasis => DocNode::Emphasis(children),
+        Tag::Strong => DocNode::Strong(children),
+        Tag::Strikethrough => DocNode::Strikethrough(children),
+        Tag::Link(_, url, title) => {
+            DocNode::Link { url: url.into_string(), title: title.into_string(), children }
+        }
+        Tag::Image(_, url, title) => {
+            DocNode::Image { url: url.into_string(), title: title.into_string(), children }
+        }
+    }
+}
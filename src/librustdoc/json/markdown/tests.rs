@@ -0,0 +1,46 @@
+use super::*;
+
+fn to_json(docs: &str, links: &BTreeMap<String, types::Id>) -> String {
+    serde_json::to_string(&parse(docs, links)).unwrap()
+}
+
+#[test]
+fn blocks_and_inlines() {
+    assert_eq!(
+        to_json("# Title\n\nSome *text* and `code`.", &BTreeMap::new()),
+        r#"[{"heading":{"level":1,"children":[{"text":"Title"}]}},"#.to_owned()
+            + r#"{"paragraph":[{"text":"Some "},{"emphasis":[{"text":"text"}]},"#
+            + r#"{"text":" and "},{"code":"code"},{"text":"."}]}]"#,
+    );
+    assert_eq!(
+        to_json("1. a\n2. b\n\n```rust\n# hidden\nshown\n```", &BTreeMap::new()),
+        r#"[{"list":{"start":1,"children":[{"list_item":[{"text":"a"}]},"#.to_owned()
+            + r#"{"list_item":[{"text":"b"}]}]}},"#
+            + r##"{"code_block":{"info":"rust","code":"# hidden\nshown\n"}}]"##,
+    );
+}
+
+#[test]
+fn adjacent_text_is_merged() {
+    assert_eq!(to_json("a [b] c", &BTreeMap::new()), r#"[{"paragraph":[{"text":"a [b] c"}]}]"#);
+}
+
+#[test]
+fn intra_doc_links() {
+    let mut links = BTreeMap::new();
+    links.insert("Vec".to_owned(), types::Id("1:2".to_owned()));
+    assert_eq!(
+        to_json("See [Vec] and [other].", &links),
+        r#"[{"paragraph":[{"text":"See "},"#.to_owned()
+            + r#"{"link":{"url":"Vec","title":"","children":[{"text":"Vec"}]}},"#
+            + r#"{"text":" and [other]."}]}]"#,
+    );
+}
+
+#[test]
+fn summaries() {
+    assert_eq!(summary("Adds `a`\nand *b*.\n\nMore."), "Adds `a` and *b*.");
+    assert_eq!(summary("# The `Foo` type\n\nMore."), "The `Foo` type");
+    assert_eq!(summary("```\nlet x = 1;\n```\n\nMore."), "");
+    assert_eq!(summary(""), "");
+}
@@ -1,47 +1,953 @@
-use crate::clean;
-use crate::config::{RenderInfo, RenderOptions};
+//! Rustdoc's JSON backend
+//!
+//! This module contains the logic for rendering a crate as JSON rather than the normal static HTML
+//! output. See [the RFC](https://github.com/rust-lang/rfcs/pull/2963) and the [`types`] module
+//! docs for usage and details.
+
+mod binary;
+mod cbor;
+mod code_blocks;
+mod conversions;
+mod diff;
+mod ffi;
+mod hash;
+mod incremental;
+mod lsif;
+mod markdown;
+mod msgpack;
+mod msgpack_indexed;
+mod output;
+mod schema;
+mod semver;
+mod spool;
+mod stats;
+#[cfg(test)]
+mod test_utils;
+
+pub use rustdoc_json_types as types;
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use rustc_data_structures::fingerprint::Fingerprint;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_feature::UnstableFeatures;
+use rustc_hir::def_id::{DefId, DefPathHash, CRATE_DEF_INDEX, LOCAL_CRATE};
+use rustc_middle::ty;
+use rustc_span::edition::Edition;
+
+use crate::clean::{self, GetDefId};
+use crate::config::{JsonCompression, OutputFormat, RenderInfo, RenderOptions};
+use crate::docfs::PathError;
 use crate::error::Error;
 use crate::formats::cache::Cache;
-use crate::formats::FormatRenderer;
+use crate::formats::item_type::ItemType;
+use crate::formats::registry::Backend;
+use crate::formats::{run_format, FormatRenderer};
+use crate::html::format::href;
+use crate::html::markdown::{ErrorCodes, IdMap, Markdown};
+use crate::json::conversions::{
+    enable_compact_ids, from_access_level, from_def_id, from_doc_coverage, from_external_crate,
+    is_derived, take_compact_ids,
+};
+use crate::json::output::OutputFile;
+use crate::passes;
 
-use rustc_span::edition::Edition;
+/// The file the external crates and items are written to in the output directory with
+/// `--json-externs-file`.
+const EXTERNS_FILE: &str = "externs.json";
 
 #[derive(Clone)]
-pub struct JsonRenderer {}
+pub struct JsonRenderer {
+    /// A mapping of IDs that contains all local items for this crate which gets output as a top
+    /// level field of the JSON blob. Unused when the items are spooled.
+    index: Rc<RefCell<FxHashMap<types::Id, types::Item>>>,
+    /// The directory where the blob will be written to.
+    out_path: PathBuf,
+    /// Items that couldn't be converted to JSON. These are left out of the output and reported as
+    /// warnings in `after_run`.
+    skipped: Rc<RefCell<Vec<Error>>>,
+    /// Whether to indent the output rather than writing it on a single line.
+    pretty: bool,
+    /// Set when writing newline-delimited JSON, in which case items are written out as soon as
+    /// they're converted instead of being collected into `index`.
+    lines: Option<Rc<RefCell<LinesWriter>>>,
+    /// Set when writing compact JSON, unless the whole index is needed in memory once every item
+    /// was converted, in which case items are spooled to a file as they're converted instead of
+    /// being collected into `index`.
+    spool: Option<Rc<RefCell<spool::Spool>>>,
+    compression: Option<JsonCompression>,
+    /// The encoding of the output: JSON, or a binary format with the same structure.
+    format: OutputFormat,
+    /// Whether to write the JSON Schema of the output next to it.
+    emit_schema: bool,
+    locations: Rc<Locations>,
+    target_triple: String,
+    is_proc_macro_crate: bool,
+    /// Set with `--json-doctests`, in which case each item's doctests are extracted from its docs.
+    doctests: bool,
+    /// Set with `--json-render-docs`, in which case each item's docs are also rendered to HTML.
+    render_docs: bool,
+    /// Set with `--json-docs-ast`, in which case each item's docs are also written as a tree.
+    docs_ast: bool,
+    /// Whether the error codes of `compile_fail` tests are read.
+    error_codes: ErrorCodes,
+    /// The default edition of doctests, which the rendered docs mark the code blocks with.
+    edition: Edition,
+    /// The documentation coverage of each local item, summed up in [`types::Crate::coverage`].
+    doc_coverage: Rc<FxHashMap<DefId, passes::ItemCoverage>>,
+    /// The layouts computed with `--json-type-layout`.
+    type_layouts: Rc<FxHashMap<DefId, clean::TypeLayout>>,
+    /// The variance of the generic parameters of each type, by name.
+    variances: Rc<FxHashMap<DefId, FxHashMap<String, ty::Variance>>>,
+    /// The `extern` block of each local foreign item.
+    extern_blocks: Rc<FxHashMap<DefId, clean::ExternBlock>>,
+    /// The macro invocation each local item generated by a macro comes from.
+    expansions: Rc<FxHashMap<DefId, clean::Expansion>>,
+    /// Set with `--json-ffi-only`, in which case the index is cut down to the C API of the crate.
+    ffi_only: bool,
+    /// Set with `--document-hidden-items`, in which case the `strip-hidden` pass didn't run.
+    document_hidden: bool,
+    /// Set with `--json-kinds`, in which case the items of other kinds are left out of the index.
+    kinds: Option<Rc<Vec<types::ItemKind>>>,
+    /// Set with `--json-only-path`, in which case only the items under this path are in the index.
+    only_path: Option<Rc<Vec<String>>>,
+    /// Set with `--json-no-docs`, in which case the docs of items are removed before they're
+    /// converted, along with everything derived from them.
+    no_docs: bool,
+    /// Set with `--json-api-surface`, in which case stripped items are left out, as well as
+    /// everything about items but their signatures.
+    api_surface: bool,
+    /// Set with `--json-shard`: maps the items to the module they were found in, whose file
+    /// they're written to.
+    shards: Option<Rc<RefCell<FxHashMap<types::Id, DefId>>>>,
+    /// The module being rendered. Each module gets its own copy of the renderer.
+    module: Option<DefId>,
+    /// Set with `--json-docs-file`: the docs taken out of the items, written to their own file.
+    docs: Option<Rc<RefCell<BTreeMap<types::Id, String>>>>,
+    /// Whether the external crates and items are written to [`EXTERNS_FILE`] rather than in the
+    /// output of the crate.
+    externs_file: bool,
+    /// The hashes of the HIR of the local items, see [`RenderInfo::hir_hashes`].
+    hir_hashes: Rc<FxHashMap<DefId, Fingerprint>>,
+    /// Set with `--json-incremental`: the output of the last run, if there's one.
+    previous: Option<Rc<incremental::Previous>>,
+    incremental: bool,
+    /// Set with `--json-diff`: the earlier output to compare the items with.
+    diff_with: Option<PathBuf>,
+    semver: bool,
+    /// The hashes of the signatures of the public items, see [`hash::api_hash`].
+    api_hashes: Rc<RefCell<BTreeMap<types::Id, Fingerprint>>>,
+    /// What's counted of each item for the stats of the output.
+    counted: Rc<RefCell<FxHashMap<types::Id, stats::Counted>>>,
+}
+
+/// Writes each item on its own line as it's converted. Some items (like impls) can be reached
+/// more than once, so this keeps track of which ones were already written.
+///
+/// Binary formats are self-delimiting, so with those the values are simply concatenated.
+struct LinesWriter {
+    out: OutputFile,
+    path: PathBuf,
+    format: OutputFormat,
+    written: FxHashSet<types::Id>,
+}
+
+impl LinesWriter {
+    fn write<T: serde::Serialize>(&mut self, value: &T) -> Result<(), Error> {
+        write_value(&mut self.out, &self.path, self.format, false, value)?;
+        if self.format.is_json() {
+            try_err!(self.out.write_all(b"\n"), &self.path);
+        }
+        Ok(())
+    }
+
+    fn write_item(&mut self, id: types::Id, item: &types::Item) -> Result<(), Error> {
+        if self.written.insert(id) { self.write(item) } else { Ok(()) }
+    }
+}
+
+/// Encodes `value` in the given format and writes it to `out`.
+fn write_value<T: serde::Serialize>(
+    out: &mut OutputFile,
+    path: &Path,
+    format: OutputFormat,
+    pretty: bool,
+    value: &T,
+) -> Result<(), Error> {
+    match format {
+        OutputFormat::Msgpack => {
+            let bytes = try_err!(msgpack::to_vec(value), path);
+            try_err!(out.write_all(&bytes), path);
+        }
+        OutputFormat::Cbor => {
+            let bytes = try_err!(cbor::to_vec(value), path);
+            try_err!(out.write_all(&bytes), path);
+        }
+        _ if pretty => try_err!(serde_json::ser::to_writer_pretty(out, value), path),
+        _ => try_err!(serde_json::ser::to_writer(out, value), path),
+    }
+    Ok(())
+}
+
+/// The path of the file the crate (or a shard of it) is written to, e.g. `<out>/std.json.gz`.
+fn output_path(
+    out_path: &Path,
+    name: &str,
+    format: OutputFormat,
+    lines: bool,
+    compression: Option<JsonCompression>,
+) -> PathBuf {
+    let extension = match format {
+        OutputFormat::Msgpack => "msgpack",
+        OutputFormat::Cbor => "cbor",
+        OutputFormat::MsgpackIndexed => "rdidx",
+        OutputFormat::Lsif => "lsif",
+        OutputFormat::Sqlite => "db",
+        _ if lines => "ndjson",
+        _ => "json",
+    };
+    let mut path = out_path.join(name);
+    match compression {
+        Some(c) => path.set_extension(format!("{}.{}", extension, c.extension())),
+        None => path.set_extension(extension),
+    };
+    path
+}
+
+/// Where each item is listed in the crate. This is worked out up front since items are converted
+/// in no particular order, and impls in particular are reached through the types and traits
+/// they're implemented for.
+#[derive(Default)]
+struct Locations {
+    /// Maps every item to the one it's listed in, see [`types::Item::parent`].
+    parents: FxHashMap<DefId, DefId>,
+    /// The fully qualified path of every item, see [`types::Item::path`].
+    paths: FxHashMap<DefId, Vec<String>>,
+    /// Maps the items the HTML backend doesn't give a page of their own to the item whose page
+    /// they're documented on, and their anchor there.
+    pages: FxHashMap<DefId, (DefId, Option<String>)>,
+    /// Maps the items of trait impls to the item of the trait they implement, see
+    /// [`types::Item::trait_item`].
+    trait_items: FxHashMap<DefId, DefId>,
+}
+
+/// The anchor of an associated item on the page of its trait or type, see `naive_assoc_href`.
+fn assoc_anchor(item: &clean::Item) -> Option<String> {
+    let ty = match item.type_() {
+        ItemType::Typedef | ItemType::AssocType => ItemType::AssocType,
+        ty => ty,
+    };
+    Some(format!("{}.{}", ty, item.name.as_ref()?))
+}
+
+/// Whether `item` is an associated type, as opposed to an associated function or constant.
+fn is_assoc_type(item: &clean::Item) -> bool {
+    matches!(item.inner, clean::AssocTypeItem(..) | clean::TypedefItem(_, true))
+}
+
+impl Locations {
+    fn new(krate: &clean::Crate, cache: &Cache) -> Self {
+        let mut locations = Locations::default();
+        if let Some(module) = &krate.module {
+            locations.collect(module, &[], cache);
+        }
+        for (&id, trait_) in &cache.traits {
+            let path = Locations::cached_path(id, cache).unwrap_or_default();
+            for item in &trait_.items {
+                locations.parents.entry(item.def_id).or_insert(id);
+                locations.pages.entry(item.def_id).or_insert((id, assoc_anchor(item)));
+                locations.collect(item, &path, cache);
+            }
+        }
+        for impl_ in cache.impls.values().flatten() {
+            let trait_ = match impl_.trait_did().and_then(|did| cache.traits.get(&did)) {
+                Some(trait_) => trait_,
+                None => continue,
+            };
+            for item in &impl_.inner_impl().items {
+                // A trait can have an associated type and a method or constant of the same name.
+                let declaration = trait_
+                    .items
+                    .iter()
+                    .find(|i| i.name == item.name && is_assoc_type(i) == is_assoc_type(item));
+                if let Some(declaration) = declaration {
+                    locations.trait_items.entry(item.def_id).or_insert(declaration.def_id);
+                }
+            }
+        }
+        locations
+    }
+
+    fn cached_path(id: DefId, cache: &Cache) -> Option<Vec<String>> {
+        cache.paths.get(&id).or_else(|| cache.external_paths.get(&id)).map(|(p, _)| p.clone())
+    }
+
+    /// Records the path of `item` and the location of each of the items it contains,
+    /// recursively. An item listed in several places (like an inlined re-export) keeps the first
+    /// location it was found at.
+    fn collect(&mut self, item: &clean::Item, parent_path: &[String], cache: &Cache) {
+        // Items in the cache use the path they're documented at. Other items are named after
+        // where they're listed, and the items of impls after the type they're implemented for.
+        let path = match (Locations::cached_path(item.def_id, cache), &item.inner) {
+            (Some(path), _) => path,
+            (None, clean::ImplItem(i)) => i
+                .for_
+                .def_id()
+                .and_then(|did| Locations::cached_path(did, cache))
+                .unwrap_or_else(|| parent_path.to_vec()),
+            (None, _) => parent_path.iter().cloned().chain(item.name.clone()).collect(),
+        };
+        for i in item.inner.inner_items() {
+            self.parents.entry(i.def_id).or_insert(item.def_id);
+            if !self.pages.contains_key(&i.def_id) {
+                if let Some(page) = self.page(i, item) {
+                    self.pages.insert(i.def_id, page);
+                }
+            }
+            self.collect(i, &path, cache);
+        }
+        self.paths.entry(item.def_id).or_insert(path);
+    }
+
+    /// The page `item` is documented on by the HTML backend if it doesn't get one of its own, and
+    /// its anchor there. Impls are shown on the page of their type, without an anchor since
+    /// those depend on what else is on the page.
+    fn page(&self, item: &clean::Item, parent: &clean::Item) -> Option<(DefId, Option<String>)> {
+        let name = item.name.as_ref();
+        Some(match (&item.inner, &parent.inner) {
+            (clean::ImplItem(i), _) => (i.for_.def_id()?, None),
+            (clean::StructFieldItem(_), clean::VariantItem(_)) => (
+                self.pages.get(&parent.def_id)?.0,
+                Some(format!("variant.{}.field.{}", parent.name.as_ref()?, name?)),
+            ),
+            (clean::StructFieldItem(_), _) => {
+                (parent.def_id, Some(format!("structfield.{}", name?)))
+            }
+            (clean::VariantItem(_), _) => (parent.def_id, Some(format!("variant.{}", name?))),
+            (_, clean::ImplItem(_)) => (self.pages.get(&parent.def_id)?.0, assoc_anchor(item)),
+            (_, clean::TraitItem(_)) => (parent.def_id, assoc_anchor(item)),
+            _ => return None,
+        })
+    }
+
+    /// The URL of the HTML documentation of an item, see [`types::Item::html_url`].
+    fn html_url(&self, id: DefId) -> Option<String> {
+        match self.pages.get(&id) {
+            Some((page, anchor)) => {
+                let (url, ..) = href(*page)?;
+                Some(match anchor {
+                    Some(anchor) => format!("{}#{}", url, anchor),
+                    None => url,
+                })
+            }
+            None => href(id).map(|(url, ..)| url),
+        }
+    }
+}
+
+/// The formats that share the JSON types, which only differ in how [`JsonRenderer`] encodes
+/// them.
+crate static BACKENDS: [Backend; 5] = [
+    Backend {
+        name: "json",
+        format: OutputFormat::Json,
+        timer: "render_json",
+        run: run_format::<JsonRenderer>,
+    },
+    Backend {
+        name: "msgpack",
+        format: OutputFormat::Msgpack,
+        timer: "render_msgpack",
+        run: run_format::<JsonRenderer>,
+    },
+    Backend {
+        name: "cbor",
+        format: OutputFormat::Cbor,
+        timer: "render_cbor",
+        run: run_format::<JsonRenderer>,
+    },
+    Backend {
+        name: "msgpack-indexed",
+        format: OutputFormat::MsgpackIndexed,
+        timer: "render_msgpack_indexed",
+        run: run_format::<JsonRenderer>,
+    },
+    Backend {
+        name: "lsif",
+        format: OutputFormat::Lsif,
+        timer: "render_lsif",
+        run: run_format::<JsonRenderer>,
+    },
+];
+
+/// The key items are visited in: the hash of their def path, falling back to the `DefId` for
+/// synthetic impls that don't have one. Visiting items in the order the compiler happened to
+/// number them would make the order of `impls` (and the compact IDs handed out) change between
+/// runs.
+fn def_path_key(did: DefId, cache: &Cache) -> (Option<DefPathHash>, DefId) {
+    (cache.def_path_hashes.get(&did).copied(), did)
+}
+
+/// The names of the traits implemented for the type `id` with `#[derive]`, in the order of its
+/// `impls`.
+fn derived_traits(id: DefId, cache: &Cache) -> Vec<String> {
+    let mut derived: Vec<_> = cache
+        .impls
+        .get(&id)
+        .into_iter()
+        .flatten()
+        .filter(|i| i.impl_item.def_id.is_local() && is_derived(&i.impl_item.attrs))
+        .collect();
+    derived.sort_by_key(|i| def_path_key(i.impl_item.def_id, cache));
+    derived
+        .into_iter()
+        .filter_map(|i| match &i.inner_impl().trait_ {
+            Some(clean::ResolvedPath { path, .. }) => Some(path.last_name().to_owned()),
+            _ => None,
+        })
+        .collect()
+}
+
+impl JsonRenderer {
+    fn get_trait_implementors(
+        &mut self,
+        id: DefId,
+        cache: &Cache,
+    ) -> Result<Vec<types::Id>, Error> {
+        let mut impls: Vec<_> = cache.implementors.get(&id).into_iter().flatten().collect();
+        impls.sort_by_key(|i| def_path_key(i.impl_item.def_id, cache));
+        let mut implementors = Vec::new();
+        for i in impls {
+            let item = &i.impl_item;
+            self.insert(item, cache)?;
+            implementors.push(from_def_id(item.def_id));
+        }
+        Ok(implementors)
+    }
+
+    fn type_layout(&self, id: DefId) -> Option<types::TypeLayout> {
+        self.type_layouts.get(&id).map(Into::into)
+    }
+
+    /// Moves the index of `output` to one file per module for `--json-shard`, listed in
+    /// `output.shards`. The files go in `<out>/<crate>-shards`, which can't be the directory of
+    /// another crate since crate names have no dashes.
+    fn write_shards(
+        &self,
+        krate: &clean::Crate,
+        output: &mut types::Crate,
+        modules: &FxHashMap<types::Id, DefId>,
+    ) -> Result<(), Error> {
+        let mut shards: BTreeMap<String, BTreeMap<types::Id, types::Item>> = BTreeMap::new();
+        for (id, item) in std::mem::take(&mut output.index) {
+            let path = modules
+                .get(&id)
+                .and_then(|module| self.locations.paths.get(module))
+                .map_or_else(|| krate.name.clone(), |path| path.join("::"));
+            shards.entry(path).or_default().insert(id, item);
+        }
+        let dir_name = format!("{}-shards", krate.name);
+        let dir = self.out_path.join(&dir_name);
+        try_err!(fs::create_dir_all(&dir), &dir);
+        for (module, index) in shards {
+            let name = module.replace("::", "-");
+            let p = output_path(&dir, &name, self.format, false, self.compression);
+            let mut file = try_err!(OutputFile::create(&p, self.compression), &p);
+            write_value(&mut file, &p, self.format, self.pretty, &index)?;
+            try_err!(file.finish(), &p);
+            let file_name = p.file_name().unwrap_or_default().to_string_lossy();
+            output.shards.insert(module, format!("{}/{}", dir_name, file_name));
+        }
+        Ok(())
+    }
+
+    /// Moves the external crates and items of `output` to [`EXTERNS_FILE`], adding them to the
+    /// ones written there by other crates.
+    fn write_externs(&self, output: &mut types::Crate) -> Result<(), Error> {
+        let p = self.out_path.join(EXTERNS_FILE);
+        let mut externs: types::Externs = if p.exists() {
+            let file = BufReader::new(try_err!(File::open(&p), &p));
+            try_err!(serde_json::from_reader(file), &p)
+        } else {
+            types::Externs::default()
+        };
+        for krate in output.external_crates.values_mut() {
+            let name = types::ExternalCrate {
+                name: krate.name.clone(),
+                html_root_url: None,
+                html_local_path: None,
+                version: None,
+                hash: None,
+                disambiguator: krate.disambiguator.clone(),
+                source: None,
+            };
+            let krate = std::mem::replace(krate, name);
+            externs.crates.entry(krate.disambiguator.clone()).or_insert(krate);
+        }
+        let crate_num = output.crate_num;
+        let (local, external) = std::mem::take(&mut output.paths)
+            .into_iter()
+            .partition(|(_, summary)| summary.crate_id == crate_num);
+        output.paths = local;
+        for (id, summary) in external {
+            externs.paths.entry(id).or_insert(summary);
+        }
+        let file = BufWriter::new(try_err!(File::create(&p), &p));
+        try_err!(serde_json::ser::to_writer(file, &externs), &p);
+        Ok(())
+    }
+
+    fn add_variances(&self, id: DefId, generics: &mut types::Generics) {
+        if let Some(variances) = self.variances.get(&id) {
+            for param in &mut generics.params {
+                param.variance = variances.get(&param.name).map(|&variance| variance.into());
+            }
+        }
+    }
+
+    fn get_impls(&mut self, id: DefId, cache: &Cache) -> Result<Vec<types::Id>, Error> {
+        let mut local_impls: Vec<_> = cache
+            .impls
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .filter(|i| i.impl_item.def_id.is_local())
+            .collect();
+        local_impls.sort_by_key(|i| def_path_key(i.impl_item.def_id, cache));
+        let mut impls = Vec::new();
+        for i in local_impls {
+            let item = &i.impl_item;
+            self.insert(item, cache)?;
+            impls.push(from_def_id(item.def_id));
+        }
+        Ok(impls)
+    }
+
+    /// Converts every trait in the cache into its JSON form. The items of external traits aren't
+    /// part of the local crate, so they're added to the index here so that the trait's `items`
+    /// can be resolved by consumers.
+    fn get_traits(&mut self, cache: &Cache) -> Result<BTreeMap<types::Id, types::Trait>, Error> {
+        let mut ids: Vec<DefId> = cache.traits.keys().copied().collect();
+        ids.sort_by_key(|&id| def_path_key(id, cache));
+        let mut traits = BTreeMap::new();
+        for id in ids {
+            let trait_item = &cache.traits[&id];
+            if !id.is_local() {
+                for item in &trait_item.items {
+                    self.insert(item, cache)?;
+                }
+            }
+            let mut trait_: types::Trait = trait_item.into();
+            trait_.implementors = self.get_trait_implementors(id, cache)?;
+            traits.insert(from_def_id(id), trait_);
+        }
+        Ok(traits)
+    }
+
+    /// Inserts an item into the index. This should be used rather than directly calling insert on
+    /// the hashmap because certain items (traits and types) need to have their mappings for trait
+    /// implementations filled out before they're inserted.
+    fn insert(&mut self, item: &clean::Item, cache: &Cache) -> Result<(), Error> {
+        // Flatten items that recursively store other items
+        for i in item.inner.inner_items() {
+            self.insert(i, cache)?;
+        }
+
+        let id = item.def_id;
+        let links = if self.render_docs { Some(item.links()) } else { None };
+        let reused = match (&self.previous, self.hir_hashes.get(&id)) {
+            (Some(previous), Some(&hash)) => previous.item(item, hash),
+            _ => None,
+        };
+        let mut new_item: types::Item = match reused.map_or_else(|| item.try_into(), Ok) {
+            Ok(item) => item,
+            Err(e) => {
+                self.skipped.borrow_mut().push(e);
+                return Ok(());
+            }
+        };
+        if self.no_docs {
+            new_item.docs.clear();
+            new_item.docs_summary.clear();
+            new_item.code_blocks.clear();
+            new_item.links.clear();
+        }
+        if let Some(kinds) = &self.kinds {
+            if !kinds.contains(&new_item.kind) {
+                return Ok(());
+            }
+        }
+        if self.api_surface {
+            if new_item.stripped.is_some() {
+                return Ok(());
+            }
+            new_item.source = None;
+            new_item.attrs.clear();
+            new_item.aliases.clear();
+        }
+        new_item.parent = self.locations.parents.get(&id).map(|&parent| from_def_id(parent));
+        new_item.path = self.locations.paths.get(&id).cloned().unwrap_or_default();
+        if let Some(prefix) = &self.only_path {
+            if !new_item.path.starts_with(prefix) {
+                return Ok(());
+            }
+        }
+        new_item.html_url = self.locations.html_url(id);
+        new_item.trait_item = self.locations.trait_items.get(&id).map(|&did| from_def_id(did));
+        new_item.extern_block = self.extern_blocks.get(&id).map(Into::into);
+        if id.is_local() {
+            let level = cache.access_levels.map.get(&id).copied();
+            new_item.effective_visibility = Some(from_access_level(level));
+        }
+        if !self.api_surface {
+            new_item.expansion = self.expansions.get(&id).map(Into::into);
+        }
+        if self.doctests {
+            new_item.doctests = Some(code_blocks::doctests(&new_item.docs, self.error_codes));
+        }
+        if let Some(links) = links {
+            let mut ids = IdMap::new();
+            let md =
+                Markdown(&new_item.docs, &links, &mut ids, self.error_codes, self.edition, &None);
+            new_item.docs_html = Some(md.into_string());
+        }
+        if self.docs_ast {
+            new_item.docs_ast = Some(markdown::parse(&new_item.docs, &new_item.links));
+        }
+        if let types::ItemEnum::TraitItem(ref mut t) = new_item.inner {
+            t.implementors = self.get_trait_implementors(id, cache)?;
+        } else if let types::ItemEnum::StructItem(ref mut s) = new_item.inner {
+            s.impls = self.get_impls(id, cache)?;
+            s.derived = derived_traits(id, cache);
+            s.layout = self.type_layout(id);
+            self.add_variances(id, &mut s.generics);
+        } else if let types::ItemEnum::UnionItem(ref mut u) = new_item.inner {
+            u.impls = self.get_impls(id, cache)?;
+            u.derived = derived_traits(id, cache);
+            u.layout = self.type_layout(id);
+            self.add_variances(id, &mut u.generics);
+        } else if let types::ItemEnum::EnumItem(ref mut e) = new_item.inner {
+            e.impls = self.get_impls(id, cache)?;
+            e.derived = derived_traits(id, cache);
+            e.layout = self.type_layout(id);
+            self.add_variances(id, &mut e.generics);
+        } else if let types::ItemEnum::PrimitiveItem(ref mut p) = new_item.inner {
+            p.impls = self.get_impls(id, cache)?;
+        }
+
+        if let Some(hash) = hash::item_hash(&new_item) {
+            self.api_hashes.borrow_mut().insert(from_def_id(id), hash);
+        }
+        // The docs are hashed and counted even when they're written to a file of their own.
+        new_item.hash = hash::content_hash(&new_item);
+        self.counted.borrow_mut().insert(from_def_id(id), stats::Counted::new(&new_item));
+        if let Some(docs) = &self.docs {
+            if !new_item.docs.is_empty() {
+                docs.borrow_mut().insert(from_def_id(id), std::mem::take(&mut new_item.docs));
+            }
+        }
+        if let (Some(shards), Some(module)) = (&self.shards, self.module) {
+            shards.borrow_mut().entry(from_def_id(id)).or_insert(module);
+        }
+        match (&self.lines, &self.spool) {
+            (Some(lines), _) => lines.borrow_mut().write_item(from_def_id(id), &new_item)?,
+            (None, Some(spool)) => {
+                try_err!(spool.borrow_mut().write(from_def_id(id), &new_item), &self.out_path);
+            }
+            (None, None) => {
+                self.index.borrow_mut().insert(from_def_id(id), new_item);
+            }
+        }
+        Ok(())
+    }
+}
 
 impl FormatRenderer for JsonRenderer {
     fn init(
-        _krate: clean::Crate,
-        _options: RenderOptions,
-        _render_info: RenderInfo,
-        _edition: Edition,
-        _cache: &mut Cache,
+        krate: clean::Crate,
+        options: RenderOptions,
+        render_info: RenderInfo,
+        edition: Edition,
+        cache: &mut Cache,
     ) -> Result<(Self, clean::Crate), Error> {
-        unimplemented!()
+        debug!("Initializing json renderer");
+        let out_path = options.output;
+        let format = render_info.output_format.unwrap_or(OutputFormat::Json);
+        try_err!(fs::create_dir_all(&out_path), &out_path);
+        if options.json_compact_ids {
+            enable_compact_ids();
+        }
+        let lines = if options.json_lines {
+            let path = output_path(&out_path, &krate.name, format, true, options.json_compression);
+            let out = try_err!(OutputFile::create(&path, options.json_compression), &path);
+            let written = FxHashSet::default();
+            Some(Rc::new(RefCell::new(LinesWriter { out, path, format, written })))
+        } else {
+            None
+        };
+        // Pruning, diffing and splitting the index need all of it at once. The spooled items are
+        // copied into the output as they are, which only works if it's compact JSON too.
+        let needs_index =
+            options.json_shard || options.json_ffi_only || options.json_diff.is_some();
+        let compact_json = format == OutputFormat::Json && !options.json_pretty;
+        let spool = if options.json_lines || needs_index || !compact_json {
+            None
+        } else {
+            let spool = try_err!(spool::Spool::new(&out_path), &out_path);
+            Some(Rc::new(RefCell::new(spool)))
+        };
+        let locations = Locations::new(&krate, cache);
+        let previous = if options.json_incremental {
+            incremental::Previous::read(&out_path, &krate.name).map(Rc::new)
+        } else {
+            None
+        };
+        Ok((
+            JsonRenderer {
+                index: Rc::new(RefCell::new(FxHashMap::default())),
+                out_path,
+                skipped: Rc::new(RefCell::new(Vec::new())),
+                pretty: options.json_pretty,
+                lines,
+                spool,
+                compression: options.json_compression,
+                format,
+                emit_schema: options.emit_schema,
+                locations: Rc::new(locations),
+                target_triple: render_info.target_triple,
+                is_proc_macro_crate: render_info.is_proc_macro_crate,
+                doctests: options.json_doctests,
+                render_docs: options.json_render_docs,
+                docs_ast: options.json_docs_ast,
+                error_codes: ErrorCodes::from(
+                    UnstableFeatures::from_environment().is_nightly_build(),
+                ),
+                edition,
+                doc_coverage: Rc::new(render_info.doc_coverage),
+                type_layouts: Rc::new(render_info.type_layouts),
+                variances: Rc::new(render_info.variances),
+                extern_blocks: Rc::new(render_info.extern_blocks),
+                expansions: Rc::new(render_info.expansions),
+                ffi_only: options.json_ffi_only,
+                document_hidden: options.document_hidden,
+                kinds: options.json_kinds.map(Rc::new),
+                only_path: options.json_only_path.map(|mut path| {
+                    if path[0] == "crate" {
+                        path[0] = krate.name.clone();
+                    }
+                    Rc::new(path)
+                }),
+                no_docs: options.json_no_docs || options.json_api_surface,
+                api_surface: options.json_api_surface,
+                shards: if options.json_shard { Some(Default::default()) } else { None },
+                module: None,
+                docs: if options.json_docs_file { Some(Default::default()) } else { None },
+                externs_file: options.json_externs_file,
+                hir_hashes: Rc::new(render_info.hir_hashes),
+                previous,
+                incremental: options.json_incremental,
+                diff_with: options.json_diff,
+                semver: options.json_semver,
+                api_hashes: Rc::new(RefCell::new(BTreeMap::new())),
+                counted: Rc::new(RefCell::new(FxHashMap::default())),
+            },
+            krate,
+        ))
     }
 
-    fn item(&mut self, _item: clean::Item, _cache: &Cache) -> Result<(), Error> {
-        unimplemented!()
+    fn item(&mut self, item: clean::Item, cache: &Cache) -> Result<(), Error> {
+        self.insert(&item, cache)
     }
 
     fn mod_item_in(
         &mut self,
-        _item: &clean::Item,
-        _item_name: &str,
-        _cache: &Cache,
+        item: &clean::Item,
+        _module_name: &str,
+        cache: &Cache,
     ) -> Result<(), Error> {
-        unimplemented!()
+        use clean::types::ItemEnum::*;
+        self.module = Some(item.def_id);
+        if let ModuleItem(m) = &item.inner {
+            for item in &m.items {
+                match &item.inner {
+                    // These don't have names so they don't get added to the output by default
+                    ImportItem(_) => self.insert(item, cache)?,
+                    ExternCrateItem(_, _) => self.insert(item, cache)?,
+                    ImplItem(i) => {
+                        for i in &i.items {
+                            self.insert(i, cache)?;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        self.insert(item, cache)
     }
 
     fn mod_item_out(&mut self, _item_name: &str) -> Result<(), Error> {
-        unimplemented!()
+        Ok(())
     }
 
-    fn after_krate(&mut self, _krate: &clean::Crate, _cache: &Cache) -> Result<(), Error> {
-        unimplemented!()
+    fn after_krate(&mut self, krate: &clean::Crate, cache: &Cache) -> Result<(), Error> {
+        debug!("Done with crate");
+        for item in &krate.reexported_items {
+            self.insert(item, cache)?;
+        }
+        if self.emit_schema {
+            let p = self.out_path.join(schema::SCHEMA_FILE);
+            let file = try_err!(File::create(&p), &p);
+            try_err!(serde_json::ser::to_writer_pretty(&file, &schema::schema()), &p);
+        }
+        let traits = self.get_traits(cache)?;
+        let index = std::mem::take(&mut *self.index.borrow_mut()).into_iter().collect();
+        let mut paths: Vec<_> = cache.paths.iter().chain(cache.external_paths.iter()).collect();
+        paths.sort_by_key(|(k, _)| def_path_key(**k, cache));
+        let mut doc_coverage: Vec<_> = self.doc_coverage.iter().map(|(&k, &v)| (k, v)).collect();
+        doc_coverage.sort_by_key(|&(k, _)| def_path_key(k, cache));
+        let version = option_env!("CFG_VERSION").unwrap_or("unknown version");
+        let mut output = types::Crate {
+            name: krate.name.clone(),
+            crate_num: LOCAL_CRATE.as_u32(),
+            disambiguator: cache.crate_disambiguators[&LOCAL_CRATE].to_string(),
+            root: from_def_id(DefId { krate: LOCAL_CRATE, index: CRATE_DEF_INDEX }),
+            crate_version: krate.version.clone(),
+            includes_private: cache.document_private,
+            includes_hidden: self.document_hidden,
+            index,
+            shards: BTreeMap::new(),
+            traits,
+            paths: paths
+                .into_iter()
+                .map(|(&k, (path, kind))| {
+                    (
+                        from_def_id(k),
+                        types::ItemSummary {
+                            crate_id: k.krate.as_u32(),
+                            path: path.clone(),
+                            kind: (*kind).into(),
+                        },
+                    )
+                })
+                .collect(),
+            external_crates: krate
+                .externs
+                .iter()
+                .map(|(k, e)| (k.as_u32(), from_external_crate(e, &cache.extern_locations[k].2)))
+                .collect(),
+            coverage: if self.api_surface {
+                types::Coverage::default()
+            } else {
+                from_doc_coverage(doc_coverage)
+            },
+            api_hash: hash::api_hash(&self.api_hashes.borrow()),
+            // Filled in below, once the index is final.
+            stats: types::Stats::default(),
+            rustc_version: format!("rustc {}", version),
+            rustdoc_version: format!("rustdoc {}", version),
+            target: self.target_triple.clone(),
+            is_proc_macro: self.is_proc_macro_crate,
+            format_version: 1,
+        };
+        // The cache doesn't tell `macro` items apart from `macro_rules!` macros.
+        for (id, item) in self.counted.borrow().iter() {
+            if item.kind == types::ItemKind::DeclMacro {
+                if let Some(summary) = output.paths.get_mut(id) {
+                    summary.kind = types::ItemKind::DeclMacro;
+                }
+            }
+        }
+        if self.ffi_only {
+            ffi::retain_ffi(&mut output.index);
+            if let Some(docs) = &self.docs {
+                docs.borrow_mut().retain(|id, _| output.index.contains_key(id));
+            }
+            self.counted.borrow_mut().retain(|id, _| output.index.contains_key(id));
+        }
+        output.stats = stats::stats(self.counted.borrow().values());
+        // Every ID has been handed out by now, including the ones of the crate metadata.
+        if let Some(originals) = take_compact_ids() {
+            let p = self.out_path.join(format!("{}.ids.json", krate.name));
+            let file = BufWriter::new(try_err!(File::create(&p), &p));
+            try_err!(serde_json::ser::to_writer(file, &originals), &p);
+        }
+        if let Some(docs) = &self.docs {
+            let p = self.out_path.join(format!("{}.docs.json", krate.name));
+            let file = BufWriter::new(try_err!(File::create(&p), &p));
+            try_err!(serde_json::ser::to_writer(file, &*docs.borrow()), &p);
+        }
+        if self.externs_file {
+            self.write_externs(&mut output)?;
+        }
+        if let Some(old_path) = &self.diff_with {
+            let file = BufReader::new(try_err!(File::open(old_path), old_path));
+            let old: types::Crate = try_err!(serde_json::from_reader(file), old_path);
+            let diff = diff::diff(&old.index, &output.index);
+            if self.semver {
+                let report = semver::classify(&old.index, &output.index, &diff);
+                let p = self.out_path.join(format!("{}.semver.json", krate.name));
+                let file = BufWriter::new(try_err!(File::create(&p), &p));
+                try_err!(serde_json::ser::to_writer(file, &report), &p);
+            }
+            let p = self.out_path.join(format!("{}.diff.json", krate.name));
+            let file = BufWriter::new(try_err!(File::create(&p), &p));
+            try_err!(serde_json::ser::to_writer(file, &diff), &p);
+        }
+        if let Some(lines) = &self.lines {
+            let mut lines = lines.borrow_mut();
+            lines.write(&output)?;
+            try_err!(lines.out.finish(), &lines.path);
+            return Ok(());
+        }
+        if let Some(shards) = &self.shards {
+            self.write_shards(krate, &mut output, &shards.borrow())?;
+        }
+        let p = output_path(&self.out_path, &krate.name, self.format, false, self.compression);
+        let mut file = try_err!(OutputFile::create(&p, self.compression), &p);
+        if self.format == OutputFormat::MsgpackIndexed {
+            let index = std::mem::take(&mut output.index);
+            let items = index.iter().map(|(id, item)| (id.0.as_str(), item));
+            let bytes = try_err!(msgpack_indexed::to_vec(items, &output), &p);
+            try_err!(file.write_all(&bytes), &p);
+        } else if self.format == OutputFormat::Lsif {
+            let root = try_err!(std::env::current_dir(), &p);
+            try_err!(lsif::write(&mut file, &output, &root), &p);
+        } else if self.format == OutputFormat::Sqlite {
+            try_err!(crate::sqlite::write(&mut file, &output), &p);
+        } else if let Some(spool) = &self.spool {
+            let mut spool = spool.borrow_mut();
+            let index = try_err!(spool.index(), &p);
+            let krate = std::mem::take(&mut output).map_index(|_| index);
+            write_value(&mut file, &p, self.format, self.pretty, &krate)?;
+        } else {
+            write_value(&mut file, &p, self.format, self.pretty, &output)?;
+        }
+        try_err!(file.finish(), &p);
+        if self.incremental {
+            let written = |id: &types::Id| match &self.spool {
+                Some(spool) => spool.borrow().contains(id),
+                None => output.index.contains_key(id),
+            };
+            let hashes = self
+                .hir_hashes
+                .iter()
+                .map(|(&did, &hash)| (from_def_id(did), hash))
+                .filter(|(id, _)| written(id));
+            incremental::write_hashes(&self.out_path, &krate.name, hashes)?;
+        }
+        Ok(())
     }
 
-    fn after_run(&mut self, _diag: &rustc_errors::Handler) -> Result<(), Error> {
-        unimplemented!()
+    fn after_run(&mut self, diag: &rustc_errors::Handler) -> Result<(), Error> {
+        for e in self.skipped.borrow().iter() {
+            diag.struct_warn(&format!("item left out of the JSON output: {}", e)).emit();
+        }
+        Ok(())
     }
 }
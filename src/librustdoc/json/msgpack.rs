@@ -0,0 +1,153 @@
+//! The [MessagePack](https://msgpack.org) encoding, used by `--output-format msgpack`. Integers,
+//! strings and containers always use their smallest encoding.
+
+use std::convert::TryFrom;
+
+use serde::Serialize;
+
+use crate::json::binary::{self, Container, Encoding, Error};
+
+#[cfg(test)]
+mod tests;
+
+/// Encodes `value` as MessagePack.
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, Error> {
+    binary::to_vec::<MessagePack, T>(value)
+}
+
+pub enum MessagePack {}
+
+fn len32(len: usize) -> Result<u32, Error> {
+    u32::try_from(len).map_err(|_| Error(format!("length {} is too large for MessagePack", len)))
+}
+
+/// Writes a header using the smallest of the given markers that can hold `len`: the fix variant
+/// (which stores the length in the low bits of the marker), then 8, 16 and 32 bit lengths. A
+/// `None` marker means that size isn't available for this type.
+fn write_marker_header(
+    out: &mut Vec<u8>,
+    len: usize,
+    fix: Option<(u8, u32)>,
+    sized: [Option<u8>; 3],
+) -> Result<(), Error> {
+    let len = len32(len)?;
+    match (fix, sized) {
+        (Some((marker, max)), _) if len <= max => out.push(marker | len as u8),
+        (_, [Some(marker), _, _]) if len <= u32::from(u8::MAX) => {
+            out.push(marker);
+            out.push(len as u8);
+        }
+        (_, [_, Some(marker), _]) if len <= u32::from(u16::MAX) => {
+            out.push(marker);
+            out.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        (_, [_, _, Some(marker)]) => {
+            out.push(marker);
+            out.extend_from_slice(&len.to_be_bytes());
+        }
+        _ => unreachable!("every MessagePack header has a 32 bit form"),
+    }
+    Ok(())
+}
+
+impl Encoding for MessagePack {
+    fn write_nil(out: &mut Vec<u8>) {
+        out.push(0xc0);
+    }
+
+    fn write_bool(out: &mut Vec<u8>, v: bool) {
+        out.push(if v { 0xc3 } else { 0xc2 });
+    }
+
+    fn write_uint(out: &mut Vec<u8>, v: u64) {
+        if v < 0x80 {
+            out.push(v as u8);
+        } else if v <= u64::from(u8::MAX) {
+            out.push(0xcc);
+            out.push(v as u8);
+        } else if v <= u64::from(u16::MAX) {
+            out.push(0xcd);
+            out.extend_from_slice(&(v as u16).to_be_bytes());
+        } else if v <= u64::from(u32::MAX) {
+            out.push(0xce);
+            out.extend_from_slice(&(v as u32).to_be_bytes());
+        } else {
+            out.push(0xcf);
+            out.extend_from_slice(&v.to_be_bytes());
+        }
+    }
+
+    fn write_int(out: &mut Vec<u8>, v: i64) {
+        if v >= 0 {
+            Self::write_uint(out, v as u64);
+        } else if v >= -32 {
+            // negative fixint
+            out.push(v as i8 as u8);
+        } else if v >= i64::from(i8::MIN) {
+            out.push(0xd0);
+            out.push(v as i8 as u8);
+        } else if v >= i64::from(i16::MIN) {
+            out.push(0xd1);
+            out.extend_from_slice(&(v as i16).to_be_bytes());
+        } else if v >= i64::from(i32::MIN) {
+            out.push(0xd2);
+            out.extend_from_slice(&(v as i32).to_be_bytes());
+        } else {
+            out.push(0xd3);
+            out.extend_from_slice(&v.to_be_bytes());
+        }
+    }
+
+    fn write_f32(out: &mut Vec<u8>, v: f32) {
+        out.push(0xca);
+        out.extend_from_slice(&v.to_be_bytes());
+    }
+
+    fn write_f64(out: &mut Vec<u8>, v: f64) {
+        out.push(0xcb);
+        out.extend_from_slice(&v.to_be_bytes());
+    }
+
+    fn write_str(out: &mut Vec<u8>, v: &str) -> Result<(), Error> {
+        write_marker_header(out, v.len(), Some((0xa0, 31)), [Some(0xd9), Some(0xda), Some(0xdb)])?;
+        out.extend_from_slice(v.as_bytes());
+        Ok(())
+    }
+
+    fn write_bytes(out: &mut Vec<u8>, v: &[u8]) -> Result<(), Error> {
+        write_marker_header(out, v.len(), None, [Some(0xc4), Some(0xc5), Some(0xc6)])?;
+        out.extend_from_slice(v);
+        Ok(())
+    }
+
+    fn write_header(out: &mut Vec<u8>, container: Container, len: usize) -> Result<(), Error> {
+        match container {
+            Container::Array => {
+                write_marker_header(out, len, Some((0x90, 15)), [None, Some(0xdc), Some(0xdd)])
+            }
+            Container::Map => {
+                write_marker_header(out, len, Some((0x80, 15)), [None, Some(0xde), Some(0xdf)])
+            }
+        }
+    }
+
+    /// MessagePack has no marker for containers of unknown length, so this writes a 32 bit
+    /// header that's patched once the length is known.
+    fn begin_unsized(out: &mut Vec<u8>, container: Container) {
+        out.push(match container {
+            Container::Array => 0xdd,
+            Container::Map => 0xdf,
+        });
+        out.extend_from_slice(&[0; 4]);
+    }
+
+    fn end_unsized(
+        out: &mut Vec<u8>,
+        _container: Container,
+        start: usize,
+        count: usize,
+    ) -> Result<(), Error> {
+        out[start + 1..start + 5].copy_from_slice(&len32(count)?.to_be_bytes());
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use super::*;
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+#[test]
+fn integers_use_smallest_encoding() {
+    assert_eq!(to_vec(&5u64).unwrap(), [0x05]);
+    assert_eq!(to_vec(&200u32).unwrap(), [0xcc, 200]);
+    assert_eq!(to_vec(&0x1234u32).unwrap(), [0xcd, 0x12, 0x34]);
+    assert_eq!(to_vec(&0x1_0000u32).unwrap(), [0xce, 0, 1, 0, 0]);
+    assert_eq!(to_vec(&-1i32).unwrap(), [0xff]);
+    assert_eq!(to_vec(&-32i32).unwrap(), [0xe0]);
+    assert_eq!(to_vec(&-33i32).unwrap(), [0xd0, 0xdf]);
+    assert_eq!(to_vec(&-200i32).unwrap(), [0xd1, 0xff, 0x38]);
+}
+
+#[test]
+fn strings() {
+    assert_eq!(to_vec("abc").unwrap(), [0xa3, b'a', b'b', b'c']);
+    let long = "x".repeat(40);
+    let out = to_vec(&long).unwrap();
+    assert_eq!(out[..2], [0xd9, 40]);
+    assert_eq!(out.len(), 42);
+}
+
+#[test]
+fn options_and_units() {
+    assert_eq!(to_vec(&None::<u8>).unwrap(), [0xc0]);
+    assert_eq!(to_vec(&Some(true)).unwrap(), [0xc3]);
+}
+
+#[test]
+fn sequences_and_maps() {
+    assert_eq!(to_vec(&vec![1u8, 2, 3]).unwrap(), [0x93, 1, 2, 3]);
+    assert_eq!(to_vec(&(1u8, "a")).unwrap(), [0x92, 1, 0xa1, b'a']);
+    let mut map = BTreeMap::new();
+    map.insert(1u32, false);
+    assert_eq!(to_vec(&map).unwrap(), [0x81, 1, 0xc2]);
+    let out = to_vec(&vec![0u8; 16]).unwrap();
+    assert_eq!(out[..3], [0xdc, 0, 16]);
+}
+
+#[derive(Serialize)]
+struct Point {
+    x: u8,
+    y: u8,
+}
+
+#[derive(Serialize)]
+enum Shape {
+    Empty,
+    Circle(u8),
+    Line(Point, Point),
+    Rect { w: u8, h: u8 },
+}
+
+#[test]
+fn structs_are_maps() {
+    assert_eq!(to_vec(&Point { x: 1, y: 2 }).unwrap(), [0x82, 0xa1, b'x', 1, 0xa1, b'y', 2]);
+}
+
+#[test]
+fn enums_are_externally_tagged() {
+    assert_eq!(to_vec(&Shape::Empty).unwrap(), [0xa5, b'E', b'm', b'p', b't', b'y']);
+    assert_eq!(
+        to_vec(&Shape::Circle(3)).unwrap(),
+        [0x81, 0xa6, b'C', b'i', b'r', b'c', b'l', b'e', 3]
+    );
+    assert_eq!(
+        to_vec(&Shape::Line(Point { x: 0, y: 0 }, Point { x: 1, y: 1 })).unwrap()[..7],
+        [0x81, 0xa4, b'L', b'i', b'n', b'e', 0x92]
+    );
+    assert_eq!(
+        to_vec(&Shape::Rect { w: 1, h: 2 }).unwrap(),
+        [0x81, 0xa4, b'R', b'e', b'c', b't', 0x82, 0xa1, b'w', 1, 0xa1, b'h', 2]
+    );
+}
+
+struct Unsized(Vec<u8>);
+
+impl Serialize for Unsized {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+        let mut seq = serializer.serialize_seq(None)?;
+        for e in &self.0 {
+            seq.serialize_element(e)?;
+        }
+        seq.end()
+    }
+}
+
+#[test]
+fn unknown_lengths_are_patched() {
+    assert_eq!(to_vec(&Unsized(vec![7, 8])).unwrap(), [0xdd, 0, 0, 0, 2, 7, 8]);
+}
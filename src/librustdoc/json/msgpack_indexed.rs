@@ -0,0 +1,88 @@
+//! A random-access MessagePack container for the output of the JSON backend, used by
+//! `--output-format msgpack-indexed`.
+//!
+//! Instead of decoding the whole crate up front, consumers binary search the table of entries for
+//! the ID they want and only decode that item. This isn't a zero-copy format: only the header and
+//! the entry table are fixed-width, and can be read in place since all their integers are
+//! little-endian and 8-byte aligned. Each item is still MessagePack that has to be decoded.
+//!
+//! ```text
+//! header:   magic b"RDOCIDX\0", version: u32, entry count: u32,
+//!           metadata offset: u64, metadata length: u64
+//! entries:  { id offset: u64, id length: u64, item offset: u64, item length: u64 },
+//!           one per item, sorted by the bytes of the ID
+//! ids:      the UTF-8 bytes of every ID, padded to a multiple of 8 bytes
+//! items:    every item, encoded as MessagePack
+//! metadata: the `Crate` without its `index`, encoded as MessagePack
+//! ```
+//!
+//! Offsets are from the start of the file.
+
+use std::convert::TryFrom;
+
+use serde::Serialize;
+
+use crate::json::binary::Error;
+use crate::json::msgpack;
+
+#[cfg(test)]
+mod tests;
+
+crate const MAGIC: &[u8; 8] = b"RDOCIDX\0";
+/// Bumped whenever the layout of the container changes.
+crate const VERSION: u32 = 1;
+
+const HEADER_LEN: usize = 32;
+const ENTRY_LEN: usize = 32;
+
+fn push_u64(out: &mut Vec<u8>, v: usize) {
+    out.extend_from_slice(&(v as u64).to_le_bytes());
+}
+
+/// Writes the container for the given `(id, item)` pairs and crate metadata.
+crate fn to_vec<'a, I, T, M>(items: I, metadata: &M) -> Result<Vec<u8>, Error>
+where
+    I: IntoIterator<Item = (&'a str, &'a T)>,
+    T: Serialize + 'a,
+    M: Serialize,
+{
+    let mut items: Vec<_> = items.into_iter().collect();
+    items.sort_by(|(a, _), (b, _)| a.as_bytes().cmp(b.as_bytes()));
+    let count = u32::try_from(items.len())
+        .map_err(|_| Error(format!("too many items for the msgpack-indexed format: {}", items.len())))?;
+
+    // Offsets in `entries` are relative to the start of their section until everything was
+    // encoded and the size of each section is known.
+    let mut entries = Vec::with_capacity(items.len());
+    let mut ids = Vec::new();
+    let mut encoded = Vec::new();
+    for (id, item) in items {
+        let item = msgpack::to_vec(item)?;
+        entries.push((ids.len(), id.len(), encoded.len(), item.len()));
+        ids.extend_from_slice(id.as_bytes());
+        encoded.extend_from_slice(&item);
+    }
+    ids.resize((ids.len() + 7) / 8 * 8, 0);
+    let metadata = msgpack::to_vec(metadata)?;
+
+    let ids_start = HEADER_LEN + ENTRY_LEN * entries.len();
+    let items_start = ids_start + ids.len();
+    let metadata_start = items_start + encoded.len();
+
+    let mut out = Vec::with_capacity(metadata_start + metadata.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&VERSION.to_le_bytes());
+    out.extend_from_slice(&count.to_le_bytes());
+    push_u64(&mut out, metadata_start);
+    push_u64(&mut out, metadata.len());
+    for (id_offset, id_len, item_offset, item_len) in entries {
+        push_u64(&mut out, ids_start + id_offset);
+        push_u64(&mut out, id_len);
+        push_u64(&mut out, items_start + item_offset);
+        push_u64(&mut out, item_len);
+    }
+    out.extend_from_slice(&ids);
+    out.extend_from_slice(&encoded);
+    out.extend_from_slice(&metadata);
+    Ok(out)
+}
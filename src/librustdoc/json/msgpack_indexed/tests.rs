@@ -0,0 +1,57 @@
+use super::*;
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(<[u8; 4]>::try_from(&bytes[at..at + 4]).unwrap())
+}
+
+fn read_u64(bytes: &[u8], at: usize) -> usize {
+    u64::from_le_bytes(<[u8; 8]>::try_from(&bytes[at..at + 8]).unwrap()) as usize
+}
+
+/// Looks up `id` the way a consumer would: a binary search over the entry table.
+fn find<'a>(bytes: &'a [u8], id: &str) -> Option<&'a [u8]> {
+    let count = read_u32(bytes, 12) as usize;
+    let entry = |i: usize| {
+        let at = HEADER_LEN + i * ENTRY_LEN;
+        let id_start = read_u64(bytes, at);
+        let id = &bytes[id_start..id_start + read_u64(bytes, at + 8)];
+        let item_start = read_u64(bytes, at + 16);
+        (id, &bytes[item_start..item_start + read_u64(bytes, at + 24)])
+    };
+    let (mut lo, mut hi) = (0, count);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let (mid_id, item) = entry(mid);
+        match mid_id.cmp(id.as_bytes()) {
+            std::cmp::Ordering::Less => lo = mid + 1,
+            std::cmp::Ordering::Greater => hi = mid,
+            std::cmp::Ordering::Equal => return Some(item),
+        }
+    }
+    None
+}
+
+#[test]
+fn header() {
+    let bytes = to_vec(Vec::<(&str, &u8)>::new(), &true).unwrap();
+    assert_eq!(&bytes[..8], MAGIC);
+    assert_eq!(read_u32(&bytes, 8), VERSION);
+    assert_eq!(read_u32(&bytes, 12), 0);
+    assert_eq!(read_u64(&bytes, 16), HEADER_LEN);
+    assert_eq!(read_u64(&bytes, 24), 1);
+    assert_eq!(bytes[HEADER_LEN..], [0xc3]);
+}
+
+#[test]
+fn lookup() {
+    let items = vec![("0:3", &3u32), ("0:10", &10u32), ("1:0", &100u32), ("0:0", &0u32)];
+    let bytes = to_vec(items, &"meta").unwrap();
+    assert_eq!(read_u32(&bytes, 12), 4);
+    assert_eq!(find(&bytes, "0:0"), Some(&[0x00][..]));
+    assert_eq!(find(&bytes, "0:10"), Some(&[0x0a][..]));
+    assert_eq!(find(&bytes, "0:3"), Some(&[0x03][..]));
+    assert_eq!(find(&bytes, "1:0"), Some(&[0x64][..]));
+    assert_eq!(find(&bytes, "2:0"), None);
+    let metadata_start = read_u64(&bytes, 16);
+    assert_eq!(bytes[metadata_start..], [0xa4, b'm', b'e', b't', b'a']);
+}
@@ -0,0 +1,54 @@
+//! The file the JSON backend writes to, optionally wrapped in a compressor.
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use flate2::write::GzEncoder;
+
+use crate::config::JsonCompression;
+
+crate enum OutputFile {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+}
+
+impl OutputFile {
+    crate fn create(path: &Path, compression: Option<JsonCompression>) -> io::Result<Self> {
+        let file = BufWriter::new(File::create(path)?);
+        Ok(match compression {
+            None => OutputFile::Plain(file),
+            Some(JsonCompression::Gzip) => {
+                OutputFile::Gzip(GzEncoder::new(file, flate2::Compression::default()))
+            }
+        })
+    }
+
+    /// Writes out anything still buffered, including the trailer of compressed formats. This
+    /// must be called once everything was written, since errors on drop are ignored.
+    crate fn finish(&mut self) -> io::Result<()> {
+        match self {
+            OutputFile::Plain(file) => file.flush(),
+            OutputFile::Gzip(gz) => {
+                gz.try_finish()?;
+                gz.get_mut().flush()
+            }
+        }
+    }
+}
+
+impl Write for OutputFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            OutputFile::Plain(file) => file.write(buf),
+            OutputFile::Gzip(gz) => gz.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            OutputFile::Plain(file) => file.flush(),
+            OutputFile::Gzip(gz) => gz.flush(),
+        }
+    }
+}
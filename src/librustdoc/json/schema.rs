@@ -0,0 +1,981 @@
+//! A [JSON Schema](https://json-schema.org) describing the output of the JSON backend, written
+//! next to the crate with `--emit-schema`.
+//!
+//! The schema is maintained by hand: any change to the [`types`](super::types) module needs a
+//! matching change here. The tests check that every field and variant of the types is described.
+
+use serde_json::{json, Map, Value};
+
+#[cfg(test)]
+mod tests;
+
+/// The file name the schema is written to in the output directory.
+crate const SCHEMA_FILE: &str = "rustdoc-json.schema.json";
+
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/definitions/{}", name) })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
+
+fn integer() -> Value {
+    json!({ "type": "integer", "minimum": 0 })
+}
+
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+fn nullable(schema: Value) -> Value {
+    json!({ "oneOf": [{ "type": "null" }, schema] })
+}
+
+/// A map with arbitrary keys. Integer keys are written as strings in JSON.
+fn map(values: Value) -> Value {
+    json!({ "type": "object", "additionalProperties": values })
+}
+
+/// A struct: all of its fields are always present, including `None` ones (which are `null`).
+fn object(fields: Vec<(&str, Value)>) -> Value {
+    let required: Vec<&str> = fields.iter().map(|(name, _)| *name).collect();
+    let properties: Map<String, Value> =
+        fields.into_iter().map(|(name, schema)| (name.to_string(), schema)).collect();
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// Enums whose variants have no fields are written as the name of the variant.
+fn unit_variants(names: &[&str]) -> Value {
+    json!({ "type": "string", "enum": names })
+}
+
+/// Other enum variants are written as a map from the name of the variant to its contents.
+fn variant(name: &str, contents: Value) -> Value {
+    object(vec![(name, contents)])
+}
+
+/// A variant of an enum using `#[serde(tag = "...", content = "...")]`.
+fn tagged(tag: &str, content: &str, name: &str, contents: Option<Value>) -> Value {
+    let mut fields = vec![(tag, json!({ "const": name }))];
+    if let Some(contents) = contents {
+        fields.push((content, contents));
+    }
+    object(fields)
+}
+
+fn one_of(schemas: Vec<Value>) -> Value {
+    json!({ "oneOf": schemas })
+}
+
+fn definitions() -> Vec<(&'static str, Value)> {
+    vec![
+        (
+            "Crate",
+            object(vec![
+                ("name", string()),
+                ("crate_num", integer()),
+                ("disambiguator", string()),
+                ("root", reference("Id")),
+                ("crate_version", nullable(string())),
+                ("includes_private", boolean()),
+                ("includes_hidden", boolean()),
+                ("index", map(reference("Item"))),
+                ("shards", map(string())),
+                ("traits", map(reference("Trait"))),
+                ("paths", map(reference("ItemSummary"))),
+                ("external_crates", map(reference("ExternalCrate"))),
+                ("coverage", reference("Coverage")),
+                ("api_hash", string()),
+                ("stats", reference("Stats")),
+                ("rustc_version", string()),
+                ("rustdoc_version", string()),
+                ("target", string()),
+                ("is_proc_macro", boolean()),
+                ("format_version", integer()),
+            ]),
+        ),
+        (
+            "ExternalCrate",
+            object(vec![
+                ("name", string()),
+                ("html_root_url", nullable(string())),
+                ("html_local_path", nullable(string())),
+                ("version", nullable(string())),
+                ("hash", nullable(string())),
+                ("disambiguator", string()),
+                ("source", nullable(reference("CrateSource"))),
+            ]),
+        ),
+        (
+            "Externs",
+            object(vec![
+                ("crates", map(reference("ExternalCrate"))),
+                ("paths", map(reference("ItemSummary"))),
+            ]),
+        ),
+        (
+            "Diff",
+            object(vec![
+                ("added", array(reference("ItemChange"))),
+                ("removed", array(reference("ItemChange"))),
+                ("changed", array(reference("ItemChange"))),
+            ]),
+        ),
+        (
+            "ItemChange",
+            object(vec![
+                ("id", reference("Id")),
+                ("path", array(string())),
+                ("kind", reference("ItemKind")),
+                ("fields", array(string())),
+            ]),
+        ),
+        (
+            "SemverReport",
+            object(vec![
+                ("verdict", reference("SemverBump")),
+                ("changes", array(reference("SemverChange"))),
+            ]),
+        ),
+        ("SemverBump", unit_variants(&["patch", "minor", "major"])),
+        (
+            "SemverChange",
+            object(vec![
+                ("id", reference("Id")),
+                ("path", array(string())),
+                ("kind", reference("ItemKind")),
+                ("rule", reference("SemverRule")),
+                ("bump", reference("SemverBump")),
+            ]),
+        ),
+        (
+            "SemverRule",
+            unit_variants(&[
+                "removed",
+                "added",
+                "variant_added",
+                "field_added",
+                "fields_made_private",
+                "required_item_added",
+                "non_exhaustive_added",
+                "signature_changed",
+                "type_changed",
+                "generics_changed",
+                "trait_changed",
+                "kind_changed",
+                "deprecated",
+            ]),
+        ),
+        (
+            "CrateSource",
+            one_of(vec![unit_variants(&["registry", "git"]), variant("path", string())]),
+        ),
+        (
+            "Coverage",
+            object(vec![
+                ("total", reference("CoverageCount")),
+                ("modules", map(reference("CoverageCount"))),
+                ("items", map(reference("ItemCoverage"))),
+            ]),
+        ),
+        (
+            "Stats",
+            object(vec![
+                ("kinds", map(integer())),
+                ("impls", integer()),
+                ("trait_impls", integer()),
+                ("stripped", integer()),
+                ("documented", integer()),
+                ("undocumented", integer()),
+            ]),
+        ),
+        (
+            "CoverageCount",
+            object(vec![
+                ("total", integer()),
+                ("with_docs", integer()),
+                ("total_examples", integer()),
+                ("with_examples", integer()),
+            ]),
+        ),
+        (
+            "ItemCoverage",
+            object(vec![
+                ("has_docs", boolean()),
+                ("should_have_docs", boolean()),
+                ("has_example", boolean()),
+                ("should_have_example", boolean()),
+            ]),
+        ),
+        (
+            "ItemSummary",
+            object(vec![
+                ("crate_id", integer()),
+                ("path", array(string())),
+                ("kind", reference("ItemKind")),
+            ]),
+        ),
+        (
+            "Item",
+            object(vec![
+                ("id", reference("Id")),
+                ("hash", string()),
+                ("crate_id", integer()),
+                ("parent", nullable(reference("Id"))),
+                ("trait_item", nullable(reference("Id"))),
+                ("extern_block", nullable(reference("ExternBlock"))),
+                ("expansion", nullable(reference("Expansion"))),
+                ("name", nullable(string())),
+                ("path", array(string())),
+                ("html_url", nullable(string())),
+                ("source", nullable(reference("Span"))),
+                ("visibility", reference("Visibility")),
+                ("effective_visibility", nullable(reference("EffectiveVisibility"))),
+                ("stripped", nullable(reference("StripReason"))),
+                ("is_doc_hidden", boolean()),
+                ("docs", string()),
+                ("docs_summary", string()),
+                ("docs_html", nullable(string())),
+                ("docs_ast", nullable(array(reference("DocNode")))),
+                ("doctests", nullable(array(reference("Doctest")))),
+                ("code_blocks", array(reference("CodeBlock"))),
+                ("links", map(reference("Id"))),
+                ("attrs", array(string())),
+                ("aliases", array(string())),
+                ("deprecation", nullable(reference("Deprecation"))),
+                ("cfg", nullable(reference("Cfg"))),
+                ("doc_cfg", nullable(reference("Cfg"))),
+                ("kind", reference("ItemKind")),
+                ("inner", reference("ItemEnum")),
+            ]),
+        ),
+        (
+            "ExternBlock",
+            object(vec![
+                ("id", reference("Id")),
+                ("abi", reference("Abi")),
+                ("attrs", array(string())),
+            ]),
+        ),
+        (
+            "Expansion",
+            object(vec![
+                ("macro_id", nullable(reference("Id"))),
+                ("name", string()),
+                ("kind", reference("MacroKind")),
+                ("call_site", nullable(reference("Span"))),
+                ("def_site", nullable(reference("Span"))),
+            ]),
+        ),
+        (
+            "DocNode",
+            one_of(vec![
+                unit_variants(&["soft_break", "hard_break", "rule"]),
+                variant("paragraph", array(reference("DocNode"))),
+                variant(
+                    "heading",
+                    object(vec![("level", integer()), ("children", array(reference("DocNode")))]),
+                ),
+                variant("block_quote", array(reference("DocNode"))),
+                variant(
+                    "code_block",
+                    object(vec![("info", nullable(string())), ("code", string())]),
+                ),
+                variant(
+                    "list",
+                    object(vec![
+                        ("start", nullable(integer())),
+                        ("children", array(reference("DocNode"))),
+                    ]),
+                ),
+                variant("list_item", array(reference("DocNode"))),
+                variant(
+                    "footnote_definition",
+                    object(vec![("label", string()), ("children", array(reference("DocNode")))]),
+                ),
+                variant(
+                    "table",
+                    object(vec![
+                        ("alignments", array(reference("Alignment"))),
+                        ("children", array(reference("DocNode"))),
+                    ]),
+                ),
+                variant("table_head", array(reference("DocNode"))),
+                variant("table_row", array(reference("DocNode"))),
+                variant("table_cell", array(reference("DocNode"))),
+                variant("emphasis", array(reference("DocNode"))),
+                variant("strong", array(reference("DocNode"))),
+                variant("strikethrough", array(reference("DocNode"))),
+                variant(
+                    "link",
+                    object(vec![
+                        ("url", string()),
+                        ("title", string()),
+                        ("children", array(reference("DocNode"))),
+                    ]),
+                ),
+                variant(
+                    "image",
+                    object(vec![
+                        ("url", string()),
+                        ("title", string()),
+                        ("children", array(reference("DocNode"))),
+                    ]),
+                ),
+                variant("text", string()),
+                variant("code", string()),
+                variant("html", string()),
+                variant("footnote_reference", string()),
+            ]),
+        ),
+        ("Alignment", unit_variants(&["none", "left", "center", "right"])),
+        (
+            "Doctest",
+            object(vec![
+                ("code", string()),
+                ("line", integer()),
+                ("attrs", reference("CodeBlockAttrs")),
+            ]),
+        ),
+        (
+            "CodeBlockAttrs",
+            object(vec![
+                ("should_panic", boolean()),
+                ("no_run", boolean()),
+                ("ignore", boolean()),
+                ("compile_fail", boolean()),
+                ("error_codes", array(string())),
+                ("test_harness", boolean()),
+                ("allow_fail", boolean()),
+                ("edition", nullable(string())),
+            ]),
+        ),
+        (
+            "CodeBlock",
+            object(vec![
+                ("lang", nullable(string())),
+                ("info", string()),
+                ("attrs", nullable(reference("CodeBlockAttrs"))),
+                ("code", string()),
+                ("first_line", integer()),
+                ("last_line", integer()),
+            ]),
+        ),
+        (
+            "Span",
+            object(vec![
+                ("filename", string()),
+                ("begin", reference("LineColumn")),
+                ("end", reference("LineColumn")),
+            ]),
+        ),
+        (
+            "LineColumn",
+            json!({
+                "type": "array",
+                "items": [integer(), integer()],
+                "minItems": 2,
+                "maxItems": 2,
+            }),
+        ),
+        (
+            "Cfg",
+            one_of(vec![
+                unit_variants(&["true", "false"]),
+                variant(
+                    "key_value",
+                    object(vec![("key", string()), ("value", nullable(string()))]),
+                ),
+                variant("not", reference("Cfg")),
+                variant("any", array(reference("Cfg"))),
+                variant("all", array(reference("Cfg"))),
+            ]),
+        ),
+        ("Deprecation", object(vec![("since", nullable(string())), ("note", nullable(string()))])),
+        ("EffectiveVisibility", unit_variants(&["private", "reachable", "exported", "public"])),
+        ("StripReason", unit_variants(&["private", "hidden"])),
+        (
+            "Visibility",
+            one_of(vec![
+                unit_variants(&["public", "default", "crate"]),
+                variant(
+                    "restricted",
+                    object(vec![("parent", reference("Id")), ("path", string())]),
+                ),
+            ]),
+        ),
+        (
+            "GenericArgs",
+            one_of(vec![
+                variant(
+                    "angle_bracketed",
+                    object(vec![
+                        ("args", array(reference("GenericArg"))),
+                        ("bindings", array(reference("TypeBinding"))),
+                    ]),
+                ),
+                variant(
+                    "parenthesized",
+                    object(vec![
+                        ("inputs", array(reference("Type"))),
+                        ("output", nullable(reference("Type"))),
+                    ]),
+                ),
+            ]),
+        ),
+        (
+            "GenericArg",
+            one_of(vec![
+                variant("lifetime", string()),
+                variant("type", reference("Type")),
+                variant("const", reference("Constant")),
+            ]),
+        ),
+        (
+            "Constant",
+            object(vec![
+                ("type", reference("Type")),
+                ("expr", string()),
+                ("value", nullable(string())),
+                ("is_literal", boolean()),
+            ]),
+        ),
+        (
+            "TypeBinding",
+            object(vec![("name", string()), ("binding", reference("TypeBindingKind"))]),
+        ),
+        (
+            "TypeBindingKind",
+            one_of(vec![
+                variant("equality", reference("Type")),
+                variant("constraint", array(reference("GenericBound"))),
+            ]),
+        ),
+        ("Id", string()),
+        (
+            "ItemKind",
+            unit_variants(&[
+                "module",
+                "extern_crate",
+                "import",
+                "struct",
+                "struct_field",
+                "union",
+                "enum",
+                "variant",
+                "function",
+                "typedef",
+                "opaque_ty",
+                "constant",
+                "trait",
+                "trait_alias",
+                "method",
+                "impl",
+                "static",
+                "foreign_type",
+                "macro",
+                "decl_macro",
+                "proc_attribute",
+                "proc_derive",
+                "assoc_const",
+                "assoc_type",
+                "primitive",
+                "keyword",
+            ]),
+        ),
+        (
+            // Untagged: which of these is used depends on the `kind` of the item. Several of
+            // them have the same shape, so this can't be a `oneOf`.
+            "ItemEnum",
+            json!({ "anyOf": [
+                reference("Module"),
+                object(vec![
+                    ("name", string()),
+                    ("rename", nullable(string())),
+                    ("crate_id", integer()),
+                    ("is_pub", boolean()),
+                    ("macro_use", nullable(array(string()))),
+                ]),
+                reference("Import"),
+                reference("Struct"),
+                reference("Union"),
+                reference("Type"),
+                reference("Enum"),
+                reference("Variant"),
+                reference("Function"),
+                reference("Trait"),
+                reference("TraitAlias"),
+                reference("Method"),
+                reference("Impl"),
+                reference("Typedef"),
+                reference("OpaqueTy"),
+                reference("Constant"),
+                reference("Static"),
+                { "type": "null" },
+                // Used by both `macro_rules!` macros and `macro` items.
+                reference("Macro"),
+                reference("ProcMacro"),
+                reference("Primitive"),
+                string(),
+                object(vec![
+                    ("type", reference("Type")),
+                    ("default", nullable(string())),
+                    ("value", nullable(string())),
+                ]),
+                object(vec![
+                    ("bounds", array(reference("GenericBound"))),
+                    ("default", nullable(reference("Type"))),
+                ]),
+            ]}),
+        ),
+        ("Module", object(vec![("is_crate", boolean()), ("items", array(reference("Id")))])),
+        (
+            "Struct",
+            object(vec![
+                ("struct_type", reference("StructType")),
+                ("generics", reference("Generics")),
+                ("fields_stripped", boolean()),
+                ("fields", array(reference("Id"))),
+                ("impls", array(reference("Id"))),
+                ("is_non_exhaustive", boolean()),
+                ("must_use", nullable(reference("MustUse"))),
+                ("repr", array(reference("Repr"))),
+                ("derived", array(string())),
+                ("layout", nullable(reference("TypeLayout"))),
+            ]),
+        ),
+        (
+            "Union",
+            object(vec![
+                ("generics", reference("Generics")),
+                ("fields_stripped", boolean()),
+                ("fields", array(reference("Id"))),
+                ("impls", array(reference("Id"))),
+                ("must_use", nullable(reference("MustUse"))),
+                ("repr", array(reference("Repr"))),
+                ("derived", array(string())),
+                ("layout", nullable(reference("TypeLayout"))),
+            ]),
+        ),
+        (
+            "Enum",
+            object(vec![
+                ("generics", reference("Generics")),
+                ("variants_stripped", boolean()),
+                ("variants", array(reference("Id"))),
+                ("impls", array(reference("Id"))),
+                ("is_non_exhaustive", boolean()),
+                ("must_use", nullable(reference("MustUse"))),
+                ("repr", array(reference("Repr"))),
+                ("derived", array(string())),
+                ("layout", nullable(reference("TypeLayout"))),
+            ]),
+        ),
+        (
+            "Variant",
+            object(vec![
+                ("kind", reference("VariantKind")),
+                ("is_non_exhaustive", boolean()),
+                ("discriminant", nullable(reference("Discriminant"))),
+            ]),
+        ),
+        ("Discriminant", object(vec![("expr", nullable(string())), ("value", nullable(string()))])),
+        (
+            "VariantKind",
+            one_of(vec![
+                tagged("variant_kind", "variant_inner", "plain", None),
+                tagged("variant_kind", "variant_inner", "tuple", Some(array(reference("Id")))),
+                tagged("variant_kind", "variant_inner", "struct", Some(array(reference("Id")))),
+            ]),
+        ),
+        ("StructType", unit_variants(&["plain", "tuple", "unit"])),
+        (
+            "TypeLayout",
+            object(vec![
+                ("size", integer()),
+                ("align", integer()),
+                ("field_offsets", map(integer())),
+                ("niche_values", nullable(string())),
+            ]),
+        ),
+        (
+            "Repr",
+            one_of(vec![
+                unit_variants(&["c", "transparent", "simd"]),
+                variant("packed", integer()),
+                variant("align", integer()),
+                variant("int", string()),
+            ]),
+        ),
+        (
+            "Function",
+            object(vec![
+                ("decl", reference("FnDecl")),
+                ("generics", reference("Generics")),
+                ("header", reference("FnHeader")),
+                ("must_use", nullable(reference("MustUse"))),
+                ("linkage", reference("Linkage")),
+            ]),
+        ),
+        (
+            "Linkage",
+            object(vec![
+                ("no_mangle", boolean()),
+                ("export_name", nullable(string())),
+                ("link_name", nullable(string())),
+                ("link_section", nullable(string())),
+            ]),
+        ),
+        (
+            "Method",
+            object(vec![
+                ("decl", reference("FnDecl")),
+                ("generics", reference("Generics")),
+                ("header", reference("FnHeader")),
+                ("has_body", boolean()),
+                ("is_default", boolean()),
+                ("must_use", nullable(reference("MustUse"))),
+                ("receiver", nullable(reference("Receiver"))),
+            ]),
+        ),
+        (
+            "Receiver",
+            one_of(vec![
+                unit_variants(&["value"]),
+                variant(
+                    "borrowed",
+                    object(vec![("lifetime", nullable(string())), ("mutable", boolean())]),
+                ),
+                variant("explicit", reference("Type")),
+            ]),
+        ),
+        (
+            "FnHeader",
+            object(vec![
+                ("is_unsafe", boolean()),
+                ("is_const", boolean()),
+                ("is_async", boolean()),
+                ("abi", reference("Abi")),
+            ]),
+        ),
+        (
+            "Abi",
+            one_of(vec![
+                unit_variants(&["rust", "cdecl"]),
+                variant("c", object(vec![("unwind", boolean())])),
+                variant("system", object(vec![("unwind", boolean())])),
+                variant("other", string()),
+            ]),
+        ),
+        ("MustUse", object(vec![("message", nullable(string()))])),
+        (
+            "Generics",
+            object(vec![
+                ("params", array(reference("GenericParamDef"))),
+                ("where_predicates", array(reference("WherePredicate"))),
+            ]),
+        ),
+        (
+            "GenericParamDef",
+            object(vec![
+                ("id", nullable(reference("Id"))),
+                ("name", string()),
+                ("kind", reference("GenericParamDefKind")),
+                ("variance", nullable(reference("Variance"))),
+            ]),
+        ),
+        ("Variance", unit_variants(&["covariant", "invariant", "contravariant", "bivariant"])),
+        (
+            "GenericParamDefKind",
+            one_of(vec![
+                unit_variants(&["lifetime"]),
+                variant(
+                    "type",
+                    object(vec![
+                        ("bounds", array(reference("GenericBound"))),
+                        ("default", nullable(reference("Type"))),
+                    ]),
+                ),
+                variant("const", reference("Type")),
+            ]),
+        ),
+        (
+            "WherePredicate",
+            one_of(vec![
+                variant(
+                    "bound_predicate",
+                    object(vec![
+                        ("ty", reference("Type")),
+                        ("bounds", array(reference("GenericBound"))),
+                    ]),
+                ),
+                variant(
+                    "region_predicate",
+                    object(vec![
+                        ("lifetime", string()),
+                        ("bounds", array(reference("GenericBound"))),
+                    ]),
+                ),
+                variant(
+                    "eq_predicate",
+                    object(vec![("lhs", reference("Type")), ("rhs", reference("Type"))]),
+                ),
+            ]),
+        ),
+        (
+            "GenericBound",
+            one_of(vec![
+                variant(
+                    "trait_bound",
+                    object(vec![
+                        ("trait", reference("Type")),
+                        ("generic_params", array(reference("GenericParamDef"))),
+                        ("modifier", reference("TraitBoundModifier")),
+                    ]),
+                ),
+                variant("outlives", string()),
+            ]),
+        ),
+        ("TraitBoundModifier", unit_variants(&["none", "maybe", "maybe_const"])),
+        (
+            "Type",
+            one_of(vec![
+                tagged(
+                    "kind",
+                    "inner",
+                    "resolved_path",
+                    Some(object(vec![
+                        ("name", string()),
+                        ("id", reference("Id")),
+                        ("args", nullable(reference("GenericArgs"))),
+                    ])),
+                ),
+                tagged("kind", "inner", "dyn_trait", Some(reference("DynTrait"))),
+                tagged("kind", "inner", "generic", Some(string())),
+                tagged("kind", "inner", "primitive", Some(string())),
+                tagged("kind", "inner", "function_pointer", Some(reference("FunctionPointer"))),
+                tagged("kind", "inner", "tuple", Some(array(reference("Type")))),
+                tagged("kind", "inner", "slice", Some(reference("Type"))),
+                tagged(
+                    "kind",
+                    "inner",
+                    "array",
+                    Some(object(vec![
+                        ("type", reference("Type")),
+                        ("len", string()),
+                        ("value", nullable(integer())),
+                    ])),
+                ),
+                tagged(
+                    "kind",
+                    "inner",
+                    "impl_trait",
+                    Some(object(vec![
+                        ("bounds", array(reference("GenericBound"))),
+                        ("position", reference("ImplTraitPosition")),
+                    ])),
+                ),
+                tagged("kind", "inner", "never", None),
+                tagged("kind", "inner", "infer", None),
+                tagged(
+                    "kind",
+                    "inner",
+                    "raw_pointer",
+                    Some(object(vec![("mutable", boolean()), ("type", reference("Type"))])),
+                ),
+                tagged(
+                    "kind",
+                    "inner",
+                    "borrowed_ref",
+                    Some(object(vec![
+                        ("lifetime", nullable(string())),
+                        ("mutable", boolean()),
+                        ("type", reference("Type")),
+                    ])),
+                ),
+                tagged(
+                    "kind",
+                    "inner",
+                    "qualified_path",
+                    Some(object(vec![
+                        ("name", string()),
+                        ("args", nullable(reference("GenericArgs"))),
+                        ("self_type", reference("Type")),
+                        ("trait", reference("Type")),
+                    ])),
+                ),
+            ]),
+        ),
+        (
+            "DynTrait",
+            object(vec![
+                ("traits", array(reference("PolyTrait"))),
+                ("lifetime", nullable(string())),
+            ]),
+        ),
+        (
+            "PolyTrait",
+            object(vec![
+                ("trait", reference("Type")),
+                ("generic_params", array(reference("GenericParamDef"))),
+            ]),
+        ),
+        ("ImplTraitPosition", unit_variants(&["argument", "return", "other"])),
+        (
+            "FunctionPointer",
+            object(vec![
+                ("is_unsafe", boolean()),
+                ("generic_params", array(reference("GenericParamDef"))),
+                ("decl", reference("FnDecl")),
+                ("abi", reference("Abi")),
+            ]),
+        ),
+        (
+            "FnDecl",
+            object(vec![
+                (
+                    "inputs",
+                    array(json!({
+                        "type": "array",
+                        "items": [string(), reference("Type")],
+                        "minItems": 2,
+                        "maxItems": 2,
+                    })),
+                ),
+                ("output", nullable(reference("Type"))),
+                ("c_variadic", boolean()),
+            ]),
+        ),
+        (
+            "Trait",
+            object(vec![
+                ("is_auto", boolean()),
+                ("is_object_safe", boolean()),
+                ("is_sealed", boolean()),
+                ("is_unsafe", boolean()),
+                ("items", array(reference("Id"))),
+                ("required_items", array(reference("Id"))),
+                ("provided_items", array(reference("Id"))),
+                ("generics", reference("Generics")),
+                ("bounds", array(reference("GenericBound"))),
+                ("supertraits", array(reference("Id"))),
+                ("implementors", array(reference("Id"))),
+            ]),
+        ),
+        (
+            "TraitAlias",
+            object(vec![
+                ("generics", reference("Generics")),
+                ("params", array(reference("GenericBound"))),
+            ]),
+        ),
+        (
+            "Impl",
+            object(vec![
+                ("is_unsafe", boolean()),
+                ("is_const", boolean()),
+                ("generics", reference("Generics")),
+                ("provided_trait_methods", array(string())),
+                ("trait", nullable(reference("Type"))),
+                ("for", reference("Type")),
+                ("items", array(reference("Id"))),
+                ("negative", boolean()),
+                ("synthetic", boolean()),
+                ("blanket_impl", nullable(reference("Type"))),
+                ("is_derived", boolean()),
+            ]),
+        ),
+        (
+            "Import",
+            object(vec![
+                ("span", string()),
+                ("name", string()),
+                ("id", nullable(reference("Id"))),
+                ("glob", boolean()),
+                ("inlined", boolean()),
+                ("reexports", array(reference("Id"))),
+                ("items", nullable(array(reference("Id")))),
+            ]),
+        ),
+        ("Macro", object(vec![("source", string()), ("rules", array(reference("MacroRule")))])),
+        (
+            "MacroRule",
+            object(vec![
+                ("matcher", array(reference("MacroMatcher"))),
+                ("has_transcriber", boolean()),
+            ]),
+        ),
+        (
+            "MacroMatcher",
+            one_of(vec![
+                variant("token", string()),
+                variant("fragment", object(vec![("name", string()), ("kind", string())])),
+                variant(
+                    "repetition",
+                    object(vec![
+                        ("matchers", array(reference("MacroMatcher"))),
+                        ("separator", nullable(string())),
+                        ("op", json!({ "type": "string", "enum": ["*", "+", "?"] })),
+                    ]),
+                ),
+                variant(
+                    "delimited",
+                    object(vec![
+                        ("delimiter", reference("MacroDelimiter")),
+                        ("matchers", array(reference("MacroMatcher"))),
+                    ]),
+                ),
+            ]),
+        ),
+        ("MacroDelimiter", unit_variants(&["parenthesis", "bracket", "brace"])),
+        (
+            "ProcMacro",
+            object(vec![
+                ("kind", reference("MacroKind")),
+                ("derived_trait", nullable(string())),
+                ("helpers", array(string())),
+            ]),
+        ),
+        ("MacroKind", unit_variants(&["bang", "attr", "derive"])),
+        ("Primitive", object(vec![("name", string()), ("impls", array(reference("Id")))])),
+        ("Typedef", object(vec![("type", reference("Type")), ("generics", reference("Generics"))])),
+        (
+            "OpaqueTy",
+            object(vec![
+                ("bounds", array(reference("GenericBound"))),
+                ("generics", reference("Generics")),
+            ]),
+        ),
+        (
+            "Static",
+            object(vec![
+                ("type", reference("Type")),
+                ("mutable", boolean()),
+                ("expr", string()),
+                ("linkage", reference("Linkage")),
+            ]),
+        ),
+    ]
+}
+
+/// The schema of the [`Crate`](super::types::Crate) written by the JSON backend.
+crate fn schema() -> Value {
+    let definitions: Map<String, Value> =
+        definitions().into_iter().map(|(name, schema)| (name.to_string(), schema)).collect();
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "rustdoc JSON output",
+        "allOf": [reference("Crate")],
+        "definitions": definitions,
+    })
+}
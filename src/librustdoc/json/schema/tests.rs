@@ -0,0 +1,228 @@
+use crate::json::types::Crate;
+
+use super::*;
+
+fn collect_refs<'a>(value: &'a Value, refs: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(r)) = map.get("$ref") {
+                refs.push(r);
+            }
+            map.values().for_each(|v| collect_refs(v, refs));
+        }
+        Value::Array(values) => values.iter().for_each(|v| collect_refs(v, refs)),
+        _ => {}
+    }
+}
+
+#[test]
+fn references_resolve() {
+    let schema = schema();
+    let mut refs = Vec::new();
+    collect_refs(&schema, &mut refs);
+    assert!(!refs.is_empty());
+    for r in refs {
+        let name = r.strip_prefix("#/definitions/").unwrap();
+        assert!(schema["definitions"].get(name).is_some(), "`{}` isn't defined", r);
+    }
+}
+
+#[test]
+fn definitions_are_unique() {
+    let mut names: Vec<_> = definitions().into_iter().map(|(name, _)| name).collect();
+    let len = names.len();
+    names.sort_unstable();
+    names.dedup();
+    assert_eq!(names.len(), len);
+}
+
+#[test]
+fn structs_require_all_fields() {
+    let schema = schema();
+    let crate_ = &schema["definitions"]["Crate"];
+    let fields = serde_json::to_value(<Crate>::default()).unwrap();
+    let mut fields: Vec<_> = fields.as_object().unwrap().keys().map(String::as_str).collect();
+    let mut required: Vec<_> =
+        crate_["required"].as_array().unwrap().iter().map(|f| f.as_str().unwrap()).collect();
+    fields.sort_unstable();
+    required.sort_unstable();
+    assert_eq!(required, fields);
+    assert_eq!(crate_["additionalProperties"], false);
+}
+
+/// The source of the types the schema describes. It's formatted with rustfmt, which is relied on
+/// to find the fields of each struct and the variants of each enum line by line.
+const TYPES: &str = include_str!("../../../rustdoc-json-types/lib.rs");
+
+enum Shape<'a> {
+    Unit,
+    Tuple(&'a str),
+    Struct(Vec<&'a str>),
+}
+
+fn ident(s: &str) -> &str {
+    let end = s.find(|c: char| !c.is_alphanumeric() && c != '_').unwrap_or(s.len());
+    &s[..end]
+}
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if i > 0 && c.is_uppercase() {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// The fields declared by the lines up to `end`, as they're named in JSON.
+fn fields<'a>(lines: &mut impl Iterator<Item = &'a str>, indent: &str, end: &str) -> Vec<&'a str> {
+    let mut fields = Vec::new();
+    let mut rename = None;
+    for line in lines.take_while(|line| *line != end) {
+        let line = match line.strip_prefix(indent) {
+            Some(line) => line.strip_prefix("pub ").unwrap_or(line),
+            None => continue,
+        };
+        if let Some(name) = line.strip_prefix("#[serde(rename = \"") {
+            rename = name.split('"').next();
+        } else if line.starts_with(|c: char| c.is_ascii_lowercase()) && line.contains(':') {
+            fields.push(rename.take().unwrap_or_else(|| ident(line)));
+        }
+    }
+    fields.sort_unstable();
+    fields
+}
+
+fn variants<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Vec<(&'a str, Shape<'a>)> {
+    let mut variants = Vec::new();
+    while let Some(line) = lines.next().filter(|line| *line != "}") {
+        let line = match line.strip_prefix("    ") {
+            Some(line) if line.starts_with(|c: char| c.is_ascii_uppercase()) => line,
+            _ => continue,
+        };
+        let name = ident(line);
+        let rest = &line[name.len()..];
+        let shape = if rest == " {" {
+            Shape::Struct(fields(lines, "        ", "    },"))
+        } else if let Some(inline) = rest.strip_prefix(" { ").and_then(|r| r.strip_suffix(" },")) {
+            let mut fields: Vec<_> =
+                inline.split(", ").filter(|field| field.contains(':')).map(ident).collect();
+            fields.sort_unstable();
+            Shape::Struct(fields)
+        } else if let Some(ty) = rest.strip_prefix('(').and_then(|r| r.strip_suffix("),")) {
+            Shape::Tuple(ty)
+        } else {
+            Shape::Unit
+        };
+        variants.push((name, shape));
+    }
+    variants
+}
+
+fn resolve<'a>(definitions: &'a Value, mut schema: &'a Value) -> &'a Value {
+    while let Some(Value::String(r)) = schema.get("$ref") {
+        schema = &definitions[r.strip_prefix("#/definitions/").unwrap()];
+    }
+    schema
+}
+
+/// The schemas a value may match, looking through `oneOf`, `anyOf` and references.
+fn alternatives<'a>(definitions: &'a Value, schema: &'a Value, out: &mut Vec<&'a Value>) {
+    let schema = resolve(definitions, schema);
+    match schema.get("oneOf").or_else(|| schema.get("anyOf")) {
+        Some(Value::Array(schemas)) => {
+            schemas.iter().for_each(|s| alternatives(definitions, s, out))
+        }
+        _ => out.push(schema),
+    }
+}
+
+fn required(schema: &Value) -> Vec<&str> {
+    let required = schema["required"].as_array().map(Vec::as_slice).unwrap_or_default();
+    let mut required: Vec<_> = required.iter().map(|f| f.as_str().unwrap()).collect();
+    required.sort_unstable();
+    required
+}
+
+/// The schema of the contents of the variant named `name`, which is `null` for unit variants.
+/// With `tag`, the enum uses `#[serde(tag = "...", content = "...")]`.
+fn variant_contents<'a>(
+    alts: &[&'a Value],
+    tag: Option<(&str, &str)>,
+    name: &str,
+) -> Option<&'a Value> {
+    let alt = alts.iter().copied().find(|alt| match tag {
+        Some((tag, _)) => alt["properties"][tag]["const"] == name,
+        None => {
+            let unit = alt["enum"].as_array().map_or(false, |names| names.contains(&json!(name)));
+            let properties = alt["properties"].as_object();
+            unit || properties.map_or(false, |p| p.len() == 1 && p.contains_key(name))
+        }
+    })?;
+    Some(&alt["properties"][tag.map_or(name, |(_, content)| content)])
+}
+
+/// Whether a variant of an untagged enum, which is written as its contents alone, is described.
+fn untagged_variant(schema: &Value, alts: &[&Value], shape: &Shape<'_>) -> bool {
+    let schemas = schema["anyOf"].as_array().unwrap();
+    match shape {
+        Shape::Unit => schemas.contains(&json!({ "type": "null" })),
+        Shape::Tuple("String") => schemas.contains(&string()),
+        Shape::Tuple(ty) => schemas.contains(&reference(ty)),
+        Shape::Struct(fields) => alts.iter().any(|alt| required(alt) == *fields),
+    }
+}
+
+fn check_enum(definitions: &Value, name: &str, attrs: &[&str], variants: Vec<(&str, Shape<'_>)>) {
+    let schema = &definitions[name];
+    assert!(!schema.is_null(), "`{}` has no schema", name);
+    let mut alts = Vec::new();
+    alternatives(definitions, schema, &mut alts);
+    let snake = attrs.contains(&"#[serde(rename_all = \"snake_case\")]");
+    let untagged = attrs.contains(&"#[serde(untagged)]");
+    let tag = attrs.iter().find_map(|attr| attr.strip_prefix("#[serde(tag = \"")).map(|attr| {
+        let mut parts = attr.split('"');
+        (parts.next().unwrap(), parts.nth(1).unwrap())
+    });
+    for (variant, shape) in variants {
+        if untagged {
+            let described = untagged_variant(schema, &alts, &shape);
+            assert!(described, "`{}::{}` isn't described", name, variant);
+            continue;
+        }
+        let json_name = if snake { snake_case(variant) } else { variant.to_owned() };
+        let contents = variant_contents(&alts, tag, &json_name);
+        assert!(contents.is_some(), "`{}::{}` isn't described", name, variant);
+        if let (Shape::Struct(fields), Some(contents)) = (shape, contents) {
+            let contents = resolve(definitions, contents);
+            assert_eq!(required(contents), fields, "the fields of `{}::{}` differ", name, variant);
+        }
+    }
+}
+
+#[test]
+fn every_type_is_described() {
+    let schema = schema();
+    let definitions = &schema["definitions"];
+    let mut lines = TYPES.lines();
+    let mut attrs = Vec::new();
+    while let Some(line) = lines.next() {
+        if line.starts_with("#[serde(") {
+            attrs.push(line);
+        } else if let Some(decl) = line.strip_prefix("pub enum ") {
+            check_enum(definitions, ident(decl), &attrs, variants(&mut lines));
+        } else if let Some(decl) = line.strip_prefix("pub struct ") {
+            // Tuple structs like `Id` are described by hand.
+            if line.ends_with('{') {
+                let name = ident(decl);
+                let fields = fields(&mut lines, "    ", "}");
+                assert_eq!(required(&definitions[name]), fields, "the fields of `{}` differ", name);
+            }
+        }
+        if line.starts_with("pub ") {
+            attrs.clear();
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! `--json-semver`: sorts the changes found by `--json-diff` by the version bump they call for
+//! under semantic versioning, following the rules of
+//! [RFC 1105](https://github.com/rust-lang/rfcs/blob/master/text/1105-api-evolution.md).
+//!
+//! Only the changes to the public API are reported. Everything else, like changes to docs or to
+//! private items, only calls for a patch release.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::json::types::{
+    Diff, EffectiveVisibility, Id, Item, ItemEnum, SemverBump, SemverChange, SemverReport,
+    SemverRule,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Classifies the changes of `diff`, which was made between the indexes `old` and `new`.
+crate fn classify(
+    old: &BTreeMap<Id, Item>,
+    new: &BTreeMap<Id, Item>,
+    diff: &Diff,
+) -> SemverReport {
+    let mut changes = Vec::new();
+    for change in &diff.removed {
+        if is_public(&old[&change.id]) {
+            changes.push(semver_change(&old[&change.id], SemverRule::Removed, SemverBump::Major));
+        }
+    }
+    for change in &diff.added {
+        let item = &new[&change.id];
+        if is_public(item) {
+            let (rule, bump) = addition(old, new, item);
+            changes.push(semver_change(item, rule, bump));
+        }
+    }
+    for change in &diff.changed {
+        let (old_item, new_item) = (&old[&change.id], &new[&change.id]);
+        let rules = match (is_public(old_item), is_public(new_item)) {
+            (true, true) => modifications(old_item, new_item),
+            (true, false) => vec![(SemverRule::Removed, SemverBump::Major)],
+            (false, true) => vec![addition(old, new, new_item)],
+            (false, false) => Vec::new(),
+        };
+        changes.extend(rules.into_iter().map(|(rule, bump)| semver_change(new_item, rule, bump)));
+    }
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    let verdict = changes.iter().map(|change| change.bump).max().unwrap_or(SemverBump::Patch);
+    SemverReport { verdict, changes }
+}
+
+/// Whether other crates can use the item. Hidden items aren't part of the API either.
+crate fn is_public(item: &Item) -> bool {
+    item.stripped.is_none()
+        && !item.is_doc_hidden
+        && item.effective_visibility != Some(EffectiveVisibility::Private)
+}
+
+fn semver_change(item: &Item, rule: SemverRule, bump: SemverBump) -> SemverChange {
+    SemverChange {
+        id: item.id.clone(),
+        path: item.path.clone(),
+        kind: item.kind.clone(),
+        rule,
+        bump,
+    }
+}
+
+/// Adding an item is only breaking when it's added to something that already existed and that
+/// other crates may have exhaustively matched, built or implemented.
+fn addition(
+    old: &BTreeMap<Id, Item>,
+    new: &BTreeMap<Id, Item>,
+    item: &Item,
+) -> (SemverRule, SemverBump) {
+    let parent = match &item.parent {
+        Some(parent) if old.contains_key(parent) => new.get(parent).map(|parent| &parent.inner),
+        _ => None,
+    };
+    match (&item.inner, parent) {
+        (ItemEnum::VariantItem(_), Some(ItemEnum::EnumItem(e))) if !e.is_non_exhaustive => {
+            (SemverRule::VariantAdded, SemverBump::Major)
+        }
+        (ItemEnum::StructFieldItem(_), Some(ItemEnum::StructItem(s)))
+            if !s.is_non_exhaustive && !s.fields_stripped =>
+        {
+            (SemverRule::FieldAdded, SemverBump::Major)
+        }
+        (_, Some(ItemEnum::TraitItem(t)))
+            if !t.is_sealed && t.required_items.contains(&item.id) =>
+        {
+            (SemverRule::RequiredItemAdded, SemverBump::Major)
+        }
+        (ItemEnum::VariantItem(_), _) => (SemverRule::VariantAdded, SemverBump::Minor),
+        (ItemEnum::StructFieldItem(_), _) => (SemverRule::FieldAdded, SemverBump::Minor),
+        _ => (SemverRule::Added, SemverBump::Minor),
+    }
+}
+
+/// The breaking changes to an item that stayed public, and its deprecation.
+fn modifications(old: &Item, new: &Item) -> Vec<(SemverRule, SemverBump)> {
+    let mut rules = Vec::new();
+    match (&old.inner, &new.inner) {
+        (ItemEnum::FunctionItem(old), ItemEnum::FunctionItem(new)) => {
+            let old = (&old.decl, &old.generics, &old.header);
+            if !same(&old, &(&new.decl, &new.generics, &new.header)) {
+                rules.push(SemverRule::SignatureChanged);
+            }
+        }
+        (ItemEnum::MethodItem(old), ItemEnum::MethodItem(new)) => {
+            let old = (&old.decl, &old.generics, &old.header);
+            if !same(&old, &(&new.decl, &new.generics, &new.header)) {
+                rules.push(SemverRule::SignatureChanged);
+            }
+        }
+        (ItemEnum::StructItem(old), ItemEnum::StructItem(new)) => {
+            if !old.is_non_exhaustive && new.is_non_exhaustive {
+                rules.push(SemverRule::NonExhaustiveAdded);
+            }
+            // The struct can't be built with a struct expression anymore.
+            if !old.is_non_exhaustive && !old.fields_stripped && new.fields_stripped {
+                rules.push(SemverRule::FieldsMadePrivate);
+            }
+            if !same(&old.generics, &new.generics) {
+                rules.push(SemverRule::GenericsChanged);
+            }
+        }
+        (ItemEnum::EnumItem(old), ItemEnum::EnumItem(new)) => {
+            if !old.is_non_exhaustive && new.is_non_exhaustive {
+                rules.push(SemverRule::NonExhaustiveAdded);
+            }
+            if !same(&old.generics, &new.generics) {
+                rules.push(SemverRule::GenericsChanged);
+            }
+        }
+        (ItemEnum::UnionItem(old), ItemEnum::UnionItem(new)) => {
+            if !same(&old.generics, &new.generics) {
+                rules.push(SemverRule::GenericsChanged);
+            }
+        }
+        (ItemEnum::TraitItem(old), ItemEnum::TraitItem(new)) => {
+            if !same(&old.generics, &new.generics) {
+                rules.push(SemverRule::GenericsChanged);
+            }
+            if (!old.is_unsafe && new.is_unsafe)
+                || (old.is_object_safe && !new.is_object_safe)
+                || !same(&old.bounds, &new.bounds)
+            {
+                rules.push(SemverRule::TraitChanged);
+            }
+        }
+        (ItemEnum::StructFieldItem(old), ItemEnum::StructFieldItem(new)) => {
+            if !same(old, new) {
+                rules.push(SemverRule::TypeChanged);
+            }
+        }
+        (ItemEnum::ConstantItem(old), ItemEnum::ConstantItem(new)) => {
+            if !same(&old.type_, &new.type_) {
+                rules.push(SemverRule::TypeChanged);
+            }
+        }
+        (ItemEnum::StaticItem(old), ItemEnum::StaticItem(new)) => {
+            if !same(&old.type_, &new.type_) || old.mutable != new.mutable {
+                rules.push(SemverRule::TypeChanged);
+            }
+        }
+        (ItemEnum::TypedefItem(old), ItemEnum::TypedefItem(new)) => {
+            if !same(old, new) {
+                rules.push(SemverRule::TypeChanged);
+            }
+        }
+        (
+            ItemEnum::AssocConstItem { type_: old, .. },
+            ItemEnum::AssocConstItem { type_: new, .. },
+        ) => {
+            if !same(old, new) {
+                rules.push(SemverRule::TypeChanged);
+            }
+        }
+        (
+            ItemEnum::AssocTypeItem { bounds: old, .. },
+            ItemEnum::AssocTypeItem { bounds: new, .. },
+        ) => {
+            if !same(old, new) {
+                rules.push(SemverRule::TypeChanged);
+            }
+        }
+        (old, new) if std::mem::discriminant(old) != std::mem::discriminant(new) => {
+            rules.push(SemverRule::KindChanged);
+        }
+        _ => {}
+    }
+    let mut rules: Vec<_> = rules.into_iter().map(|rule| (rule, SemverBump::Major)).collect();
+    if old.deprecation.is_none() && new.deprecation.is_some() {
+        rules.push((SemverRule::Deprecated, SemverBump::Minor));
+    }
+    rules
+}
+
+/// Compares parts of the JSON types, which don't implement `PartialEq`, by how they serialize.
+fn same<T: Serialize>(old: &T, new: &T) -> bool {
+    serde_json::to_value(old).ok() == serde_json::to_value(new).ok()
+}
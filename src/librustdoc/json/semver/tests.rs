@@ -0,0 +1,89 @@
+use crate::json::diff::diff;
+use crate::json::test_utils;
+use crate::json::types::{Enum, Generics, ItemKind, StripReason, Variant, VariantKind};
+
+use super::*;
+
+fn item(id: &str, parent: Option<&str>, kind: ItemKind, inner: ItemEnum) -> Item {
+    Item {
+        parent: parent.map(|parent| Id(parent.to_owned())),
+        effective_visibility: Some(EffectiveVisibility::Public),
+        ..test_utils::item(id, kind, inner)
+    }
+}
+
+fn enum_(variants: &[&str], is_non_exhaustive: bool) -> Item {
+    let inner = ItemEnum::EnumItem(Enum {
+        generics: Generics::default(),
+        variants_stripped: false,
+        variants: variants.iter().map(|&id| Id(id.to_owned())).collect(),
+        impls: Vec::new(),
+        is_non_exhaustive,
+        must_use: None,
+        repr: Vec::new(),
+        derived: Vec::new(),
+        layout: None,
+    });
+    item("E", None, ItemKind::Enum, inner)
+}
+
+fn variant(id: &str) -> Item {
+    let inner = ItemEnum::VariantItem(Variant {
+        kind: VariantKind::Plain,
+        is_non_exhaustive: false,
+        discriminant: None,
+    });
+    item(id, Some("E"), ItemKind::Variant, inner)
+}
+
+fn index(items: Vec<Item>) -> BTreeMap<Id, Item> {
+    items.into_iter().map(|item| (item.id.clone(), item)).collect()
+}
+
+fn compare(old: Vec<Item>, new: Vec<Item>) -> SemverReport {
+    let (old, new) = (index(old), index(new));
+    classify(&old, &new, &diff(&old, &new))
+}
+
+#[test]
+fn added_variants() {
+    let old = vec![enum_(&["A"], false), variant("A")];
+    let new = vec![enum_(&["A", "B"], false), variant("A"), variant("B")];
+    let report = compare(old, new);
+    assert_eq!(report.verdict, SemverBump::Major);
+    assert_eq!(report.changes.len(), 1);
+    assert_eq!(report.changes[0].rule, SemverRule::VariantAdded);
+
+    let old = vec![enum_(&["A"], true), variant("A")];
+    let new = vec![enum_(&["A", "B"], true), variant("A"), variant("B")];
+    assert_eq!(compare(old, new).verdict, SemverBump::Minor);
+}
+
+#[test]
+fn non_exhaustive_added() {
+    let report = compare(vec![enum_(&[], false)], vec![enum_(&[], true)]);
+    assert_eq!(report.verdict, SemverBump::Major);
+    assert_eq!(report.changes[0].rule, SemverRule::NonExhaustiveAdded);
+}
+
+#[test]
+fn removed_items() {
+    let report = compare(vec![enum_(&[], false)], Vec::new());
+    assert_eq!(report.verdict, SemverBump::Major);
+    assert_eq!(report.changes[0].rule, SemverRule::Removed);
+
+    let mut private = enum_(&[], false);
+    private.stripped = Some(StripReason::Private);
+    let report = compare(vec![private], Vec::new());
+    assert_eq!(report.verdict, SemverBump::Patch);
+    assert!(report.changes.is_empty());
+}
+
+#[test]
+fn docs_are_patches() {
+    let mut documented = enum_(&[], false);
+    documented.docs = "An enum.".to_owned();
+    let report = compare(vec![enum_(&[], false)], vec![documented]);
+    assert_eq!(report.verdict, SemverBump::Patch);
+    assert!(report.changes.is_empty());
+}
@@ -0,0 +1,75 @@
+//! The index is spooled to a temporary file as the items are converted, rather than kept in
+//! memory until the end. Once every item was converted, the index is read back from the file one
+//! item at a time as the output is written, so that only a single item is in memory at once. The
+//! items are copied into the output as the JSON they were spooled as, so this is only used when
+//! the output is compact JSON as well.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use serde::ser::{Error as _, SerializeMap};
+use serde::{Serialize, Serializer};
+use serde_json::value::RawValue;
+
+use crate::json::types::{Id, Item};
+
+#[cfg(test)]
+mod tests;
+
+crate struct Spool {
+    file: BufWriter<File>,
+    /// The offset and length of each item in `file`. An item written more than once replaces the
+    /// earlier copy, like it would in a map.
+    items: BTreeMap<Id, (u64, usize)>,
+    len: u64,
+}
+
+impl Spool {
+    /// Creates the spool in `dir`. The file is removed once the spool is dropped.
+    crate fn new(dir: &Path) -> io::Result<Self> {
+        let file = BufWriter::new(tempfile::tempfile_in(dir)?);
+        Ok(Spool { file, items: BTreeMap::new(), len: 0 })
+    }
+
+    crate fn write(&mut self, id: Id, item: &Item) -> io::Result<()> {
+        let bytes = serde_json::to_vec(item)?;
+        self.file.write_all(&bytes)?;
+        self.items.insert(id, (self.len, bytes.len()));
+        self.len += bytes.len() as u64;
+        Ok(())
+    }
+
+    crate fn contains(&self, id: &Id) -> bool {
+        self.items.contains_key(id)
+    }
+
+    /// The spooled items, which serialize as a map from their IDs sorted like the index of a
+    /// [`Crate`](crate::json::types::Crate).
+    crate fn index(&mut self) -> io::Result<SpooledIndex<'_>> {
+        self.file.flush()?;
+        Ok(SpooledIndex { file: self.file.get_ref(), items: &self.items })
+    }
+}
+
+crate struct SpooledIndex<'a> {
+    file: &'a File,
+    items: &'a BTreeMap<Id, (u64, usize)>,
+}
+
+impl Serialize for SpooledIndex<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.items.len()))?;
+        for (id, &(offset, len)) in self.items {
+            let mut json = String::with_capacity(len);
+            let mut file = self.file;
+            file.seek(SeekFrom::Start(offset))
+                .and_then(|_| file.take(len as u64).read_to_string(&mut json))
+                .map_err(S::Error::custom)?;
+            let item = RawValue::from_string(json).map_err(S::Error::custom)?;
+            map.serialize_entry(id, &item)?;
+        }
+        map.end()
+    }
+}
@@ -0,0 +1,33 @@
+use crate::json::test_utils;
+use crate::json::types::{Crate, ItemEnum, ItemKind};
+
+use super::*;
+
+fn item(id: &str) -> Item {
+    let item = test_utils::item(id, ItemKind::ForeignType, ItemEnum::ForeignTypeItem);
+    Item { docs: format!("The `{}` type.", id), ..item }
+}
+
+fn krate(index: BTreeMap<Id, Item>) -> Crate {
+    Crate {
+        name: "krate".to_owned(),
+        root: Id("0".to_owned()),
+        index,
+        format_version: 1,
+        ..Crate::default()
+    }
+}
+
+#[test]
+fn spooled_crates_serialize_like_crates() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut spool = Spool::new(dir.path()).unwrap();
+    let mut index = BTreeMap::new();
+    for id in &["b", "a", "b"] {
+        spool.write(Id(id.to_string()), &item(id)).unwrap();
+        index.insert(Id(id.to_string()), item(id));
+    }
+    let spooled = krate(BTreeMap::new()).map_index(|_| spool.index().unwrap());
+    let expected = serde_json::to_string(&krate(index)).unwrap();
+    assert_eq!(serde_json::to_string(&spooled).unwrap(), expected);
+}
@@ -0,0 +1,47 @@
+//! The counts of items written to `stats` in the output.
+
+use crate::json::types::{Item, ItemEnum, ItemKind, Stats};
+
+#[cfg(test)]
+mod tests;
+
+/// What's counted of an item. This is kept until every item was converted, since the items
+/// themselves are already written out by then with `--json-lines`.
+crate struct Counted {
+    crate kind: ItemKind,
+    is_trait_impl: bool,
+    is_stripped: bool,
+    has_docs: bool,
+}
+
+impl Counted {
+    crate fn new(item: &Item) -> Self {
+        Counted {
+            kind: item.kind.clone(),
+            is_trait_impl: matches!(&item.inner, ItemEnum::ImplItem(i) if i.trait_.is_some()),
+            is_stripped: item.stripped.is_some(),
+            has_docs: !item.docs.is_empty(),
+        }
+    }
+}
+
+crate fn stats<'a>(items: impl Iterator<Item = &'a Counted>) -> Stats {
+    let mut stats = Stats::default();
+    for item in items {
+        *stats.kinds.entry(item.kind.clone()).or_default() += 1;
+        if item.kind == ItemKind::Impl {
+            stats.impls += 1;
+            if item.is_trait_impl {
+                stats.trait_impls += 1;
+            }
+        }
+        if item.is_stripped {
+            stats.stripped += 1;
+        } else if item.has_docs {
+            stats.documented += 1;
+        } else {
+            stats.undocumented += 1;
+        }
+    }
+    stats
+}
@@ -0,0 +1,24 @@
+use super::*;
+
+fn counted(kind: ItemKind, is_stripped: bool, has_docs: bool) -> Counted {
+    Counted { kind, is_trait_impl: false, is_stripped, has_docs }
+}
+
+#[test]
+fn counts() {
+    let trait_impl = Counted { is_trait_impl: true, ..counted(ItemKind::Impl, false, false) };
+    let items = vec![
+        counted(ItemKind::Struct, false, true),
+        counted(ItemKind::Struct, true, true),
+        counted(ItemKind::Function, false, false),
+        counted(ItemKind::Impl, false, false),
+        trait_impl,
+    ];
+    let stats = stats(items.iter());
+    assert_eq!(stats.kinds[&ItemKind::Struct], 2);
+    assert_eq!(stats.kinds[&ItemKind::Function], 1);
+    assert!(!stats.kinds.contains_key(&ItemKind::Enum));
+    assert_eq!((stats.impls, stats.trait_impls), (2, 1));
+    assert_eq!(stats.stripped, 1);
+    assert_eq!((stats.documented, stats.undocumented), (1, 3));
+}
@@ -0,0 +1,40 @@
+//! Helpers shared by the tests of the modules that work on converted items.
+
+use std::collections::BTreeMap;
+
+use crate::json::types::{Id, Item, ItemEnum, ItemKind, Visibility};
+
+/// A public item `id` at the root of the crate `krate`, without docs or attributes.
+crate fn item(id: &str, kind: ItemKind, inner: ItemEnum) -> Item {
+    Item {
+        id: Id(id.to_owned()),
+        hash: String::new(),
+        crate_id: 0,
+        parent: None,
+        trait_item: None,
+        extern_block: None,
+        expansion: None,
+        name: Some(id.to_owned()),
+        path: vec!["krate".to_owned(), id.to_owned()],
+        html_url: None,
+        source: None,
+        visibility: Visibility::Public,
+        effective_visibility: None,
+        stripped: None,
+        is_doc_hidden: false,
+        docs: String::new(),
+        docs_summary: String::new(),
+        docs_html: None,
+        docs_ast: None,
+        doctests: None,
+        code_blocks: Vec::new(),
+        links: BTreeMap::new(),
+        attrs: Vec::new(),
+        aliases: Vec::new(),
+        deprecation: None,
+        cfg: None,
+        doc_cfg: None,
+        kind,
+        inner,
+    }
+}
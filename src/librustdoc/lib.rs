@@ -55,9 +55,11 @@ extern crate rustc_trait_selection;
 extern crate rustc_typeck;
 extern crate test as testing;
 
+use std::cell::RefCell;
 use std::default::Default;
 use std::env;
 use std::process;
+use std::rc::Rc;
 
 use rustc_errors::ErrorReported;
 use rustc_session::config::{make_crate_type_option, ErrorOutputType, RustcOptGroup};
@@ -75,12 +77,16 @@ mod doctree;
 #[macro_use]
 mod error;
 mod doctest;
+mod dot;
 mod fold;
 crate mod formats;
 pub mod html;
 mod json;
+mod man;
 mod markdown;
+mod markdown_output;
 mod passes;
+mod sqlite;
 mod theme;
 mod visit_ast;
 mod visit_lib;
@@ -140,7 +146,14 @@ fn opts() -> Vec<RustcOptGroup> {
         stable("r", |o| {
             o.optopt("r", "input-format", "the input type of the specified file", "[rust]")
         }),
-        stable("w", |o| o.optopt("w", "output-format", "the output type to write", "[html]")),
+        stable("w", |o| {
+            o.optopt(
+                "w",
+                "output-format",
+                "the output types to write, separated by commas",
+                "[html|json|msgpack|cbor|msgpack-indexed|markdown|man|lsif|dot|sqlite]",
+            )
+        }),
         stable("o", |o| o.optopt("o", "output", "where to place the output", "PATH")),
         stable("crate-name", |o| {
             o.optopt("", "crate-name", "specify the name of this crate", "NAME")
@@ -409,6 +422,170 @@ fn opts() -> Vec<RustcOptGroup> {
                 "specified the rustc-like binary to use as the test builder",
             )
         }),
+        unstable("json-pretty", |o| {
+            o.optflag("", "json-pretty", "indent the output of `--output-format json`")
+        }),
+        unstable("json-lines", |o| {
+            o.optflag(
+                "",
+                "json-lines",
+                "write the output of `--output-format json` as one item per line",
+            )
+        }),
+        unstable("json-compress", |o| {
+            o.optopt(
+                "",
+                "json-compress",
+                "compress the output of `--output-format json`",
+                "[gzip]",
+            )
+        }),
+        unstable("json-compact-ids", |o| {
+            o.optflag(
+                "",
+                "json-compact-ids",
+                "use sequential integers as the IDs of `--output-format json`, and write the IDs \
+                 they replace to `<crate>.ids.json`",
+            )
+        }),
+        unstable("json-doctests", |o| {
+            o.optflag(
+                "",
+                "json-doctests",
+                "include the doctests of each item in the output of `--output-format json`",
+            )
+        }),
+        unstable("json-render-docs", |o| {
+            o.optflag(
+                "",
+                "json-render-docs",
+                "include the docs of each item rendered to HTML in the output of \
+                 `--output-format json`",
+            )
+        }),
+        unstable("json-docs-ast", |o| {
+            o.optflag(
+                "",
+                "json-docs-ast",
+                "include the Markdown tree of the docs of each item in the output of \
+                 `--output-format json`",
+            )
+        }),
+        unstable("json-type-layout", |o| {
+            o.optflag(
+                "",
+                "json-type-layout",
+                "include the size, alignment and field offsets of types in the output of \
+                 `--output-format json`",
+            )
+        }),
+        unstable("json-inline-reexports", |o| {
+            o.optflag(
+                "",
+                "json-inline-reexports",
+                "include the items of other crates that are re-exported without being inlined in \
+                 the index of `--output-format json`",
+            )
+        }),
+        unstable("json-ffi-only", |o| {
+            o.optflag(
+                "",
+                "json-ffi-only",
+                "only include the C API of the crate in the index of `--output-format json`: \
+                 functions with a foreign ABI, types with a C-compatible `#[repr]`, and the \
+                 types they use",
+            )
+        }),
+        unstable("json-kinds", |o| {
+            o.optopt(
+                "",
+                "json-kinds",
+                "only include items of the given kinds in the index of `--output-format json`, \
+                 e.g. `struct,enum,trait,function`",
+                "KINDS",
+            )
+        }),
+        unstable("json-only-path", |o| {
+            o.optopt(
+                "",
+                "json-only-path",
+                "only include the items of the given module, e.g. `crate::api`, in the index of \
+                 `--output-format json`",
+                "PATH",
+            )
+        }),
+        unstable("json-no-docs", |o| {
+            o.optflag(
+                "",
+                "json-no-docs",
+                "leave the docs and intra-doc links of items out of the output of \
+                 `--output-format json`",
+            )
+        }),
+        unstable("json-api-surface", |o| {
+            o.optflag(
+                "",
+                "json-api-surface",
+                "only write the public API of the crate in the output of `--output-format json`: \
+                 items and their signatures, without docs, attributes or source locations",
+            )
+        }),
+        unstable("json-shard", |o| {
+            o.optflag(
+                "",
+                "json-shard",
+                "split the index of `--output-format json` into one file per module, listed in \
+                 the file of the crate",
+            )
+        }),
+        unstable("json-docs-file", |o| {
+            o.optflag(
+                "",
+                "json-docs-file",
+                "write the docs of the items of `--output-format json` to `<crate>.docs.json`, \
+                 keyed by ID, rather than in the index",
+            )
+        }),
+        unstable("json-externs-file", |o| {
+            o.optflag(
+                "",
+                "json-externs-file",
+                "write the external crates and items of `--output-format json` to `externs.json`, \
+                 shared by all the crates documented into the same directory",
+            )
+        }),
+        unstable("json-incremental", |o| {
+            o.optflag(
+                "",
+                "json-incremental",
+                "reuse the items of the last `--output-format json` run that didn't change since, \
+                 rather than converting them again",
+            )
+        }),
+        unstable("json-diff", |o| {
+            o.optopt(
+                "",
+                "json-diff",
+                "compare the items of `--output-format json` with the ones of an earlier output of \
+                 the crate, and write the differences to `<crate>.diff.json`",
+                "PATH",
+            )
+        }),
+        unstable("json-semver", |o| {
+            o.optflag(
+                "",
+                "json-semver",
+                "sort the changes found by `--json-diff` by the version bump they call for, and \
+                 write them to `<crate>.semver.json`",
+            )
+        }),
+        unstable("emit-schema", |o| {
+            o.optflag(
+                "",
+                "emit-schema",
+                "write a JSON Schema describing the output of `--output-format json`",
+            )
+        }),
     ]
 }
 
@@ -459,14 +636,15 @@ fn wrap_return(diag: &rustc_errors::Handler, res: Result<(), String>) -> MainRes
     }
 }
 
-fn run_renderer<T: formats::FormatRenderer>(
+fn run_renderer(
+    backend: &formats::registry::Backend,
     krate: clean::Crate,
     renderopts: config::RenderOptions,
     render_info: config::RenderInfo,
     diag: &rustc_errors::Handler,
     edition: rustc_span::edition::Edition,
 ) -> MainResult {
-    match formats::run_format::<T>(krate, renderopts, render_info, &diag, edition) {
+    match (backend.run)(krate, renderopts, render_info, &diag, edition) {
         Ok(_) => Ok(()),
         Err(e) => {
             let mut msg = diag.struct_err(&format!("couldn't generate documentation: {}", e.error));
@@ -510,7 +688,7 @@ fn main_options(options: config::Options) -> MainResult {
     // plug/cleaning passes.
     let crate_name = options.crate_name.clone();
     let crate_version = options.crate_version.clone();
-    let output_format = options.output_format;
+    let output_formats = options.output_formats.clone();
     let (mut krate, renderinfo, renderopts, sess) = core::run_core(options);
 
     info!("finished with rustc");
@@ -533,12 +711,34 @@ fn main_options(options: config::Options) -> MainResult {
     info!("going to format");
     let (error_format, edition, debugging_options) = diag_opts;
     let diag = core::new_handler(error_format, None, &debugging_options);
-    match output_format {
-        None | Some(config::OutputFormat::Html) => sess.time("render_html", || {
-            run_renderer::<html::render::Context>(krate, renderopts, renderinfo, &diag, edition)
-        }),
-        Some(config::OutputFormat::Json) => sess.time("render_json", || {
-            run_renderer::<json::JsonRenderer>(krate, renderopts, renderinfo, &diag, edition)
-        }),
+    let formats = if output_formats.is_empty() {
+        vec![config::OutputFormat::Html]
+    } else {
+        output_formats
+    };
+    // Every format is rendered from the same crate, so the compiler only runs once. Renderers take
+    // the crate apart, so all but the last one get a copy of it.
+    let mut krate = Some(krate);
+    let mut formats = formats.into_iter().peekable();
+    while let Some(format) = formats.next() {
+        let krate = if formats.peek().is_some() {
+            copy_crate(krate.as_ref().unwrap())
+        } else {
+            krate.take().unwrap()
+        };
+        let renderopts = renderopts.clone();
+        let renderinfo = config::RenderInfo { output_format: Some(format), ..renderinfo.clone() };
+        let backend = formats::registry::backend(format);
+        sess.time(backend.timer, || {
+            run_renderer(backend, krate, renderopts, renderinfo, &diag, edition)
+        })?;
     }
+    Ok(())
+}
+
+/// Clones `krate` for another renderer. The external traits are shared through an `Rc`, and the
+/// first renderer would take them out from under the others.
+fn copy_crate(krate: &clean::Crate) -> clean::Crate {
+    let external_traits = krate.external_traits.borrow().clone();
+    clean::Crate { external_traits: Rc::new(RefCell::new(external_traits)), ..krate.clone() }
 }
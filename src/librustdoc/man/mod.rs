@@ -0,0 +1,324 @@
+//! Rustdoc's man page backend
+//!
+//! This writes a roff page in section 3 for every documented module and item, named after its
+//! path (`<out>/man3/<crate>::<path>.3`), so crates can ship `man 3` documentation generated from
+//! their doc comments. Module pages list the items they contain, and the members of an item,
+//! such as its fields and methods, are described on the item's page.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs;
+use std::mem;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag};
+use rustc_span::edition::Edition;
+
+use crate::clean;
+use crate::config::{OutputFormat, RenderInfo, RenderOptions};
+use crate::docfs::PathError;
+use crate::error::Error;
+use crate::formats::cache::Cache;
+use crate::formats::item_type::ItemType;
+use crate::formats::registry::Backend;
+use crate::formats::signature::{members, signature};
+use crate::formats::{run_format, FormatRenderer};
+use crate::html::markdown::{map_line, opts, ErrorCodes, LangString};
+use crate::html::render::{item_ty_to_strs, plain_text_summary};
+
+#[cfg(test)]
+mod tests;
+
+crate static BACKEND: Backend = Backend {
+    name: "man",
+    format: OutputFormat::Man,
+    timer: "render_man",
+    run: run_format::<ManRenderer>,
+};
+
+/// The contents of a module's page, filled in as its items are rendered.
+struct Module {
+    docs: Option<String>,
+    /// The summary of each item, keyed by kind and name so that they're grouped and listed in a
+    /// stable order.
+    items: BTreeMap<(ItemType, String), String>,
+}
+
+#[derive(Clone)]
+crate struct ManRenderer {
+    /// The `man3` directory the pages are written to.
+    dst: PathBuf,
+    /// The path of the module being rendered, starting with the crate name.
+    current: Vec<String>,
+    /// The crate name and version, shown in the footer of every page.
+    source: String,
+    /// Every documented module. Stripped modules aren't in here, so their items are left out.
+    modules: Rc<RefCell<BTreeMap<Vec<String>, Module>>>,
+}
+
+impl ManRenderer {
+    /// Writes the page of the item at `path`, putting the header and `NAME` section in front of
+    /// its `sections`.
+    fn write_page(&self, path: &[String], summary: &str, sections: &str) -> Result<(), Error> {
+        let title = path.join("::");
+        let mut page = format!(
+            ".TH \"{}\" 3 \"\" \"{}\" \"Rust Library Documentation\"\n",
+            title, self.source
+        );
+        page.push_str(".SH NAME\n");
+        page.push_str(&title);
+        if !summary.is_empty() {
+            page.push_str(" \\- ");
+            push_text(&mut page, summary);
+        }
+        page.push('\n');
+        page.push_str(sections);
+        // `:` isn't allowed in file names on Windows.
+        let sep = if cfg!(windows) { "." } else { "::" };
+        let p = self.dst.join(format!("{}.3", path.join(sep)));
+        try_err!(fs::write(&p, page), &p);
+        Ok(())
+    }
+}
+
+impl FormatRenderer for ManRenderer {
+    fn init(
+        krate: clean::Crate,
+        options: RenderOptions,
+        _render_info: RenderInfo,
+        _edition: Edition,
+        _cache: &mut Cache,
+    ) -> Result<(Self, clean::Crate), Error> {
+        debug!("Initializing man renderer");
+        let dst = options.output.join("man3");
+        try_err!(fs::create_dir_all(&dst), &dst);
+        let source = match &krate.version {
+            Some(version) => format!("{} {}", krate.name, version),
+            None => krate.name.clone(),
+        };
+        Ok((
+            ManRenderer {
+                dst,
+                current: Vec::new(),
+                source,
+                modules: Rc::new(RefCell::new(BTreeMap::new())),
+            },
+            krate,
+        ))
+    }
+
+    fn item(&mut self, item: clean::Item, cache: &Cache) -> Result<(), Error> {
+        match item.inner {
+            clean::ImportItem(_) | clean::ExternCrateItem(..) => return Ok(()),
+            _ if item.is_stripped() => return Ok(()),
+            _ => {}
+        }
+        let name = item.name.clone().unwrap();
+        let summary = plain_text_summary(item.doc_value());
+        match self.modules.borrow_mut().get_mut(&self.current) {
+            Some(module) => module.items.insert((item.type_(), name.clone()), summary.clone()),
+            None => return Ok(()),
+        };
+        let mut path = self.current.clone();
+        path.push(name);
+        let mut sections = String::new();
+        if let Some(sig) = signature(&item) {
+            sections.push_str(".SH SYNOPSIS\n.nf\n");
+            push_text(&mut sections, &sig);
+            sections.push_str("\n.fi\n");
+        }
+        if let Some(docs) = item.doc_value() {
+            sections.push_str(".SH DESCRIPTION\n");
+            sections.push_str(&to_roff(docs));
+        }
+        for group in members(&item, cache) {
+            sections.push_str(&format!(".SH {}\n", group.title.to_uppercase()));
+            for (sig, member) in group.entries {
+                sections.push_str(".TP\n\\fB");
+                push_text(&mut sections, &sig);
+                sections.push_str("\\fR\n");
+                push_text(&mut sections, &plain_text_summary(member.doc_value()));
+                sections.push('\n');
+            }
+        }
+        sections.push_str(".SH SEE ALSO\n");
+        sections.push_str(&format!(".BR {} (3)\n", self.current.join("::")));
+        self.write_page(&path, &summary, &sections)
+    }
+
+    fn mod_item_in(
+        &mut self,
+        item: &clean::Item,
+        item_name: &str,
+        _cache: &Cache,
+    ) -> Result<(), Error> {
+        let parent = self.current.clone();
+        self.current.push(item_name.to_owned());
+        if item.is_stripped() {
+            return Ok(());
+        }
+        let mut modules = self.modules.borrow_mut();
+        if let Some(module) = modules.get_mut(&parent) {
+            let summary = plain_text_summary(item.doc_value());
+            module.items.insert((ItemType::Module, item_name.to_owned()), summary);
+        }
+        let module = Module { docs: item.doc_value().map(str::to_owned), items: BTreeMap::new() };
+        modules.insert(self.current.clone(), module);
+        Ok(())
+    }
+
+    fn mod_item_out(&mut self, _item_name: &str) -> Result<(), Error> {
+        self.current.pop();
+        Ok(())
+    }
+
+    fn after_krate(&mut self, _krate: &clean::Crate, _cache: &Cache) -> Result<(), Error> {
+        for (path, module) in self.modules.borrow().iter() {
+            let mut sections = String::new();
+            if let Some(docs) = &module.docs {
+                sections.push_str(".SH DESCRIPTION\n");
+                sections.push_str(&to_roff(docs));
+            }
+            let mut last = None;
+            for ((ty, name), summary) in &module.items {
+                if last != Some(*ty) {
+                    sections.push_str(&format!(".SH {}\n", item_ty_to_strs(ty).1.to_uppercase()));
+                    last = Some(*ty);
+                }
+                sections.push_str(&format!(".TP\n.BR {}::{} (3)\n", path.join("::"), name));
+                push_text(&mut sections, summary);
+                sections.push('\n');
+            }
+            self.write_page(path, &plain_text_summary(module.docs.as_deref()), &sections)?;
+        }
+        Ok(())
+    }
+
+    fn after_run(&mut self, _diag: &rustc_errors::Handler) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Appends `text` to `out`, escaping everything roff would otherwise interpret.
+fn push_text(out: &mut String, text: &str) {
+    for c in text.chars() {
+        // A line starting with one of these is a request.
+        if (c == '.' || c == '\'') && (out.is_empty() || out.ends_with('\n')) {
+            out.push_str("\\&");
+        }
+        match c {
+            '\\' => out.push_str("\\e"),
+            '-' => out.push_str("\\-"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// Starts a new line with a request.
+fn push_request(out: &mut String, request: &str) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out.push_str(request);
+    out.push('\n');
+}
+
+/// Converts a doc comment to roff. Rust code blocks lose their hidden lines, as they do in the
+/// HTML output, and links are reduced to their text.
+fn to_roff(docs: &str) -> String {
+    let mut out = String::new();
+    // Whether we're in a code block, and whether it's Rust code.
+    let mut code_block = None;
+    // The number of the next item of each list we're in, or `None` for bulleted lists.
+    let mut lists: Vec<Option<u64>> = Vec::new();
+    // Whether the paragraph would be the first one of a list item, which `.IP` already started.
+    let mut item_start = false;
+    for event in Parser::new_ext(docs, opts()) {
+        match event {
+            Event::Start(Tag::Paragraph) => {
+                if !mem::take(&mut item_start) {
+                    push_request(&mut out, if lists.is_empty() { ".PP" } else { ".IP \"\" 4" });
+                }
+            }
+            Event::Start(Tag::Heading(_)) => push_request(&mut out, ".SS"),
+            Event::Start(Tag::BlockQuote) => push_request(&mut out, ".RS 4"),
+            Event::End(Tag::BlockQuote) => push_request(&mut out, ".RE"),
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let rust = match kind {
+                    CodeBlockKind::Fenced(lang) => {
+                        LangString::parse_without_check(&lang, ErrorCodes::No, false).rust
+                    }
+                    CodeBlockKind::Indented => true,
+                };
+                code_block = Some(rust);
+                push_request(&mut out, ".PP");
+                push_request(&mut out, ".RS 4");
+                push_request(&mut out, ".nf");
+            }
+            Event::End(Tag::CodeBlock(_)) => {
+                code_block = None;
+                push_request(&mut out, ".fi");
+                push_request(&mut out, ".RE");
+            }
+            Event::Start(Tag::List(start)) => {
+                if !lists.is_empty() {
+                    push_request(&mut out, ".RS 4");
+                }
+                lists.push(start);
+            }
+            Event::End(Tag::List(_)) => {
+                lists.pop();
+                if !lists.is_empty() {
+                    push_request(&mut out, ".RE");
+                }
+            }
+            Event::Start(Tag::Item) => {
+                match lists.last_mut() {
+                    Some(Some(n)) => {
+                        push_request(&mut out, &format!(".IP {}. 4", n));
+                        *n += 1;
+                    }
+                    _ => push_request(&mut out, ".IP \\(bu 4"),
+                }
+                item_start = true;
+            }
+            Event::End(Tag::Item) => item_start = false,
+            Event::Start(Tag::Emphasis) => out.push_str("\\fI"),
+            Event::Start(Tag::Strong) => out.push_str("\\fB"),
+            Event::End(Tag::Emphasis) | Event::End(Tag::Strong) => out.push_str("\\fR"),
+            Event::End(Tag::TableCell) => out.push_str("  "),
+            Event::End(Tag::TableHead) | Event::End(Tag::TableRow) => push_request(&mut out, ".br"),
+            Event::Start(Tag::FootnoteDefinition(name)) => {
+                push_request(&mut out, ".PP");
+                push_text(&mut out, &format!("[{}] ", name));
+            }
+            Event::Text(text) => match code_block {
+                Some(rust) => {
+                    for line in text.lines() {
+                        let line = if rust { map_line(line).for_html() } else { Some(line.into()) };
+                        if let Some(line) = line {
+                            push_text(&mut out, &line);
+                            out.push('\n');
+                        }
+                    }
+                }
+                None => push_text(&mut out, &text),
+            },
+            Event::Code(code) => {
+                out.push_str("\\fB");
+                push_text(&mut out, &code);
+                out.push_str("\\fR");
+            }
+            Event::FootnoteReference(name) => push_text(&mut out, &format!("[{}]", name)),
+            Event::SoftBreak => out.push('\n'),
+            Event::HardBreak => push_request(&mut out, ".br"),
+            Event::Rule => push_request(&mut out, ".PP"),
+            _ => {}
+        }
+    }
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
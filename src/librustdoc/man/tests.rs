@@ -0,0 +1,33 @@
+use super::{push_text, to_roff};
+
+#[test]
+fn text_is_escaped() {
+    let mut out = String::new();
+    push_text(&mut out, ".hidden 'quote' a-b \\n");
+    assert_eq!(out, "\\&.hidden 'quote' a\\-b \\en");
+    push_text(&mut out, "\n'x");
+    assert_eq!(out, "\\&.hidden 'quote' a\\-b \\en\n\\&'x");
+}
+
+#[test]
+fn paragraphs_and_headings() {
+    assert_eq!(
+        to_roff("Some *emphasis* and `code`.\n\n# Examples\n\nMore."),
+        ".PP\nSome \\fIemphasis\\fR and \\fBcode\\fR.\n.SS\nExamples\n.PP\nMore.\n",
+    );
+}
+
+#[test]
+fn code_blocks() {
+    assert_eq!(
+        to_roff("```\n# use foo::Bar;\nlet x = Bar;\n```"),
+        ".PP\n.RS 4\n.nf\nlet x = Bar;\n.fi\n.RE\n",
+    );
+    assert_eq!(to_roff("```text\n# kept\n```"), ".PP\n.RS 4\n.nf\n# kept\n.fi\n.RE\n");
+}
+
+#[test]
+fn lists() {
+    assert_eq!(to_roff("- a\n- b"), ".IP \\(bu 4\na\n.IP \\(bu 4\nb\n");
+    assert_eq!(to_roff("3. a\n4. b"), ".IP 3. 4\na\n.IP 4. 4\nb\n");
+}
@@ -0,0 +1,226 @@
+//! Rustdoc's Markdown backend
+//!
+//! This writes one Markdown file per module, `<out>/<crate>/<module path>/index.md`, for
+//! publishing API documentation to wikis or [mdBook](https://rust-lang.github.io/mdBook/)
+//! without the HTML site. Items are printed with the plain text form of the HTML backend's
+//! signatures, and doc comments are copied over with their headings nested under the item.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use rustc_span::edition::Edition;
+
+use crate::clean;
+use crate::config::{OutputFormat, RenderInfo, RenderOptions};
+use crate::docfs::PathError;
+use crate::error::Error;
+use crate::formats::cache::Cache;
+use crate::formats::item_type::ItemType;
+use crate::formats::registry::Backend;
+use crate::formats::signature::{members, signature};
+use crate::formats::{run_format, FormatRenderer};
+use crate::html::markdown::{map_line, ErrorCodes, LangString};
+use crate::html::render::{item_ty_to_strs, plain_text_summary};
+
+#[cfg(test)]
+mod tests;
+
+crate static BACKEND: Backend = Backend {
+    name: "markdown",
+    format: OutputFormat::Markdown,
+    timer: "render_markdown",
+    run: run_format::<MarkdownRenderer>,
+};
+
+/// The contents of a module's file, filled in as its items are rendered.
+struct Page {
+    docs: Option<String>,
+    /// The rendered items, keyed by kind and name so that they're grouped and written in a
+    /// stable order.
+    items: BTreeMap<(ItemType, String), String>,
+}
+
+#[derive(Clone)]
+crate struct MarkdownRenderer {
+    out_path: PathBuf,
+    /// The path of the module being rendered, starting with the crate name.
+    current: Vec<String>,
+    /// The pages of every documented module. Stripped modules don't get one, so their items
+    /// are left out.
+    pages: Rc<RefCell<BTreeMap<Vec<String>, Page>>>,
+}
+
+impl FormatRenderer for MarkdownRenderer {
+    fn init(
+        krate: clean::Crate,
+        options: RenderOptions,
+        _render_info: RenderInfo,
+        _edition: Edition,
+        _cache: &mut Cache,
+    ) -> Result<(Self, clean::Crate), Error> {
+        debug!("Initializing markdown renderer");
+        let out_path = options.output;
+        try_err!(fs::create_dir_all(&out_path), &out_path);
+        Ok((
+            MarkdownRenderer {
+                out_path,
+                current: Vec::new(),
+                pages: Rc::new(RefCell::new(BTreeMap::new())),
+            },
+            krate,
+        ))
+    }
+
+    fn item(&mut self, item: clean::Item, cache: &Cache) -> Result<(), Error> {
+        if item.is_stripped() {
+            return Ok(());
+        }
+        if let Some(page) = self.pages.borrow_mut().get_mut(&self.current) {
+            let name = item.name.clone().unwrap();
+            page.items.insert((item.type_(), name), render_item(&item, cache));
+        }
+        Ok(())
+    }
+
+    fn mod_item_in(
+        &mut self,
+        item: &clean::Item,
+        item_name: &str,
+        _cache: &Cache,
+    ) -> Result<(), Error> {
+        let parent = self.current.clone();
+        self.current.push(item_name.to_owned());
+        if item.is_stripped() {
+            return Ok(());
+        }
+        let mut pages = self.pages.borrow_mut();
+        if let Some(page) = pages.get_mut(&parent) {
+            let link = format!("[`{0}`]({0}/index.md)", item_name);
+            page.items.insert((ItemType::Module, item_name.to_owned()), bullet(&link, item));
+        }
+        let page = Page { docs: item.doc_value().map(str::to_owned), items: BTreeMap::new() };
+        pages.insert(self.current.clone(), page);
+        Ok(())
+    }
+
+    fn mod_item_out(&mut self, _item_name: &str) -> Result<(), Error> {
+        self.current.pop();
+        Ok(())
+    }
+
+    fn after_krate(&mut self, _krate: &clean::Crate, _cache: &Cache) -> Result<(), Error> {
+        for (path, page) in self.pages.borrow().iter() {
+            let dir: PathBuf = self.out_path.join(path.iter().collect::<PathBuf>());
+            try_err!(fs::create_dir_all(&dir), &dir);
+            let p = dir.join("index.md");
+            try_err!(fs::write(&p, render_page(path, page)), &p);
+        }
+        Ok(())
+    }
+
+    fn after_run(&mut self, _diag: &rustc_errors::Handler) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+fn render_page(path: &[String], page: &Page) -> String {
+    let kind = if path.len() == 1 { "Crate" } else { "Module" };
+    let mut out = format!("# {} `{}`\n\n", kind, path.join("::"));
+    if let Some(docs) = &page.docs {
+        out.push_str(&nest_docs(docs, 1));
+        out.push_str("\n\n");
+    }
+    let mut last = None;
+    for ((ty, _), section) in &page.items {
+        if last != Some(*ty) {
+            // Modules are a list rather than a series of sections.
+            if last == Some(ItemType::Module) {
+                out.push('\n');
+            }
+            out.push_str(&format!("## {}\n\n", item_ty_to_strs(ty).1));
+            last = Some(*ty);
+        }
+        out.push_str(section);
+        out.push_str(if *ty == ItemType::Module { "\n" } else { "\n\n" });
+    }
+    let mut out = out.trim_end().to_owned();
+    out.push('\n');
+    out
+}
+
+/// Renders the section of a module's page describing `item`.
+fn render_item(item: &clean::Item, cache: &Cache) -> String {
+    let mut out = format!("### `{}`\n\n", item.name.as_ref().unwrap());
+    if let Some(sig) = signature(item) {
+        out.push_str(&format!("```rust\n{}\n```\n\n", sig));
+    }
+    if let Some(docs) = item.doc_value() {
+        out.push_str(&nest_docs(docs, 3));
+        out.push_str("\n\n");
+    }
+    for group in members(item, cache) {
+        let entries: Vec<_> =
+            group.entries.iter().map(|(sig, i)| bullet(&format!("`{}`", sig), i)).collect();
+        out.push_str(&format!("#### {}\n\n{}\n\n", group.title, entries.join("\n")));
+    }
+    out.trim_end().to_owned()
+}
+
+/// A list entry for `item`, followed by the first paragraph of its docs.
+fn bullet(text: &str, item: &clean::Item) -> String {
+    let summary = plain_text_summary(item.doc_value());
+    if summary.is_empty() { format!("- {}", text) } else { format!("- {}: {}", text, summary) }
+}
+
+/// The fence that opens or closes a code block, if `line` is one.
+fn fence_marker(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    let c = line.chars().next().filter(|&c| c == '`' || c == '~')?;
+    let len = line.chars().take_while(|&x| x == c).count();
+    if len >= 3 { Some(&line[..len]) } else { None }
+}
+
+/// Prepares the doc comment of an item for a page where the item's heading is at `depth`. The
+/// headings of the docs are nested under it, and Rust code blocks are tagged as such with their
+/// hidden lines removed, since Markdown renderers don't know rustdoc's defaults.
+crate fn nest_docs(docs: &str, depth: usize) -> String {
+    let mut out = Vec::new();
+    // The fence of the code block we're in, and whether it's Rust code.
+    let mut code_block: Option<(&str, bool)> = None;
+    for line in docs.lines() {
+        match code_block {
+            Some((fence, rust)) => {
+                if fence_marker(line).map_or(false, |m| m.starts_with(fence) && m == line.trim()) {
+                    code_block = None;
+                    out.push(line.trim().to_owned());
+                } else if !rust {
+                    out.push(line.to_owned());
+                } else if let Some(line) = map_line(line).for_html() {
+                    out.push(line.into_owned());
+                }
+            }
+            None => {
+                let trimmed = line.trim_start();
+                let hashes = trimmed.chars().take_while(|&c| c == '#').count();
+                if let Some(fence) = fence_marker(line) {
+                    let lang = trimmed[fence.len()..].trim();
+                    let rust = LangString::parse_without_check(lang, ErrorCodes::No, false).rust;
+                    code_block = Some((fence, rust));
+                    out.push(if rust { format!("{}rust", fence) } else { trimmed.to_owned() });
+                } else if line.len() - trimmed.len() < 4
+                    && (1..=6).contains(&hashes)
+                    && (trimmed.len() == hashes || trimmed[hashes..].starts_with(' '))
+                {
+                    let level = (hashes + depth).min(6);
+                    out.push(format!("{}{}", "#".repeat(level), &trimmed[hashes..]));
+                } else {
+                    out.push(line.to_owned());
+                }
+            }
+        }
+    }
+    out.join("\n")
+}
@@ -0,0 +1,24 @@
+use super::nest_docs;
+
+#[test]
+fn headings_are_nested() {
+    assert_eq!(nest_docs("# Examples\n\ntext", 3), "#### Examples\n\ntext");
+    assert_eq!(nest_docs("### Deep", 4), "###### Deep");
+    assert_eq!(nest_docs("#no-space\n    # indented", 1), "#no-space\n    # indented");
+}
+
+#[test]
+fn rust_code_blocks() {
+    let docs = "```\n# use foo::Bar;\nlet x = Bar;\n## not hidden\n```";
+    assert_eq!(nest_docs(docs, 1), "```rust\nlet x = Bar;\n# not hidden\n```");
+    let docs = "```should_panic\n# fn main() {}\n```";
+    assert_eq!(nest_docs(docs, 1), "```rust\n```");
+}
+
+#[test]
+fn other_code_blocks_are_kept() {
+    let docs = "~~~text\n# not a heading\n~~~\n# Heading";
+    assert_eq!(nest_docs(docs, 2), "~~~text\n# not a heading\n~~~\n### Heading");
+    let docs = "````\n```\n# hidden\n````";
+    assert_eq!(nest_docs(docs, 1), "````rust\n```\n````");
+}
@@ -4,6 +4,8 @@ use crate::fold::{self, DocFolder};
 use crate::html::markdown::{find_testable_code, ErrorCodes};
 use crate::passes::doc_test_lints::{should_have_doc_example, Tests};
 use crate::passes::Pass;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::DefId;
 use rustc_lint::builtin::MISSING_DOCS;
 use rustc_middle::lint::LintSource;
 use rustc_session::lint;
@@ -29,16 +31,36 @@ fn calculate_doc_coverage(krate: clean::Crate, ctx: &DocContext<'_>) -> clean::C
     krate
 }
 
+/// Counts the documentation coverage of every item without printing it, for the backends that
+/// include it in their output. The results end up in `RenderInfo::doc_coverage`.
+pub fn count_doc_coverage(krate: clean::Crate, ctx: &DocContext<'_>) -> clean::Crate {
+    let mut calc = CoverageCalculator::new(ctx);
+    let krate = calc.fold_crate(krate);
+    ctx.renderinfo.borrow_mut().doc_coverage = calc.per_item;
+    krate
+}
+
 #[derive(Default, Copy, Clone, Serialize, Debug)]
-struct ItemCount {
-    total: u64,
-    with_docs: u64,
-    total_examples: u64,
-    with_examples: u64,
+pub struct ItemCount {
+    pub total: u64,
+    pub with_docs: u64,
+    pub total_examples: u64,
+    pub with_examples: u64,
+}
+
+/// What was found out about a single item while counting.
+#[derive(Copy, Clone, Debug)]
+pub struct ItemCoverage {
+    /// The module the item is counted in: the closest one it's in, or itself for the crate root.
+    pub module: DefId,
+    pub has_docs: bool,
+    pub has_doc_example: bool,
+    pub should_have_doc_example: bool,
+    pub should_have_docs: bool,
 }
 
 impl ItemCount {
-    fn count_item(
+    pub fn count_item(
         &mut self,
         has_docs: bool,
         has_doc_example: bool,
@@ -101,6 +123,9 @@ impl ops::AddAssign for ItemCount {
 
 struct CoverageCalculator<'a, 'b> {
     items: BTreeMap<FileName, ItemCount>,
+    per_item: FxHashMap<DefId, ItemCoverage>,
+    /// The modules the current item is in, innermost last.
+    modules: Vec<DefId>,
     ctx: &'a DocContext<'b>,
 }
 
@@ -116,7 +141,12 @@ fn limit_filename_len(filename: String) -> String {
 
 impl<'a, 'b> CoverageCalculator<'a, 'b> {
     fn new(ctx: &'a DocContext<'b>) -> CoverageCalculator<'a, 'b> {
-        CoverageCalculator { items: Default::default(), ctx }
+        CoverageCalculator {
+            items: Default::default(),
+            per_item: Default::default(),
+            modules: Vec::new(),
+            ctx,
+        }
     }
 
     fn to_json(&self) -> String {
@@ -258,16 +288,43 @@ impl<'a, 'b> fold::DocFolder for CoverageCalculator<'a, 'b> {
                 // unless the user had an explicit `allow`
                 let should_have_docs =
                     level != lint::Level::Allow || matches!(source, LintSource::Default);
+                let should_have_doc_example = should_have_doc_example(self.ctx, &i);
                 debug!("counting {:?} {:?} in {}", i.type_(), i.name, i.source.filename);
                 self.items.entry(i.source.filename.clone()).or_default().count_item(
                     has_docs,
                     has_doc_example,
-                    should_have_doc_example(self.ctx, &i),
+                    should_have_doc_example,
                     should_have_docs,
                 );
+                self.per_item.insert(
+                    i.def_id,
+                    ItemCoverage {
+                        module: self.modules.last().copied().unwrap_or(i.def_id),
+                        has_docs,
+                        has_doc_example,
+                        should_have_doc_example,
+                        should_have_docs,
+                    },
+                );
             }
         }
 
-        self.fold_item_recur(i)
+        // The fields of tuple variants are documented along with their variant (the missing-docs
+        // lint doesn't ask for docs on them), so they aren't counted.
+        if let clean::VariantItem(clean::Variant { kind: clean::VariantKind::Tuple(..), .. }) =
+            i.inner
+        {
+            return Some(i);
+        }
+
+        let is_mod = i.is_mod();
+        if is_mod {
+            self.modules.push(i.def_id);
+        }
+        let i = self.fold_item_recur(i);
+        if is_mod {
+            self.modules.pop();
+        }
+        i
     }
 }
@@ -61,7 +61,7 @@ pub fn should_have_doc_example(cx: &DocContext<'_>, item: &clean::Item) -> bool
     if matches!(item.inner,
         clean::StructFieldItem(_)
         | clean::VariantItem(_)
-        | clean::AssocConstItem(_, _)
+        | clean::AssocConstItem(..)
         | clean::AssocTypeItem(_, _)
         | clean::TypedefItem(_, _)
         | clean::StaticItem(_)
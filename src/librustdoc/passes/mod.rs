@@ -42,7 +42,9 @@ mod check_code_block_syntax;
 pub use self::check_code_block_syntax::CHECK_CODE_BLOCK_SYNTAX;
 
 mod calculate_doc_coverage;
-pub use self::calculate_doc_coverage::CALCULATE_DOC_COVERAGE;
+pub use self::calculate_doc_coverage::{
+    count_doc_coverage, ItemCount, ItemCoverage, CALCULATE_DOC_COVERAGE,
+};
 
 mod html_tags;
 pub use self::html_tags::CHECK_INVALID_HTML_TAGS;
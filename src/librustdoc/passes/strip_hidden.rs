@@ -47,7 +47,8 @@ impl<'a> DocFolder for Stripper<'a> {
                     // strip things like impl methods but when doing so
                     // we must not add any items to the `retained` set.
                     let old = mem::replace(&mut self.update_retained, false);
-                    let ret = StripItem(self.fold_item_recur(i).unwrap()).strip();
+                    let ret = StripItem(self.fold_item_recur(i).unwrap())
+                        .strip(clean::StripReason::Hidden);
                     self.update_retained = old;
                     return ret;
                 }
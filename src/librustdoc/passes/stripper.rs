@@ -40,6 +40,7 @@ impl<'a> DocFolder for Stripper<'a> {
             | clean::UnionItem(..)
             | clean::AssocConstItem(..)
             | clean::TraitAliasItem(..)
+            | clean::MacroItem(clean::Macro { macro_rules: false, .. })
             | clean::ForeignTypeItem => {
                 if i.def_id.is_local() {
                     if !self.access_levels.is_exported(i.def_id) {
@@ -51,7 +52,7 @@ impl<'a> DocFolder for Stripper<'a> {
 
             clean::StructFieldItem(..) => {
                 if i.visibility != clean::Public {
-                    return StripItem(i).strip();
+                    return StripItem(i).strip(clean::StripReason::Private);
                 }
             }
 
@@ -59,7 +60,8 @@ impl<'a> DocFolder for Stripper<'a> {
                 if i.def_id.is_local() && i.visibility != clean::Public {
                     debug!("Stripper: stripping module {:?}", i.name);
                     let old = mem::replace(&mut self.update_retained, false);
-                    let ret = StripItem(self.fold_item_recur(i).unwrap()).strip();
+                    let ret = StripItem(self.fold_item_recur(i).unwrap())
+                        .strip(clean::StripReason::Private);
                     self.update_retained = old;
                     return ret;
                 }
@@ -70,7 +72,7 @@ impl<'a> DocFolder for Stripper<'a> {
 
             clean::ImplItem(..) => {}
 
-            // tymethods/macros have no control over privacy
+            // tymethods/`macro_rules!` macros have no control over privacy
             clean::MacroItem(..) | clean::TyMethodItem(..) => {}
 
             // Proc-macros are always public
@@ -93,8 +95,11 @@ impl<'a> DocFolder for Stripper<'a> {
 
             // implementations of traits are always public.
             clean::ImplItem(ref imp) if imp.trait_.is_some() => true,
-            // Struct variant fields have inherited visibility
-            clean::VariantItem(clean::Variant { kind: clean::VariantKind::Struct(..) }) => true,
+            // Variant fields have inherited visibility
+            clean::VariantItem(clean::Variant {
+                kind: clean::VariantKind::Struct(..) | clean::VariantKind::Tuple(..),
+                ..
+            }) => true,
             _ => false,
         };
 
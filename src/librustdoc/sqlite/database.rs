@@ -0,0 +1,417 @@
+//! Writes SQLite database files, as described in <https://www.sqlite.org/fileformat2.html>.
+//!
+//! The database is written once, from rows that are all known up front, so rather than inserting
+//! them one at a time, the b-tree of each table and index is built bottom-up: full leaf pages
+//! first, then the interior pages pointing to them, until a level fits on a single page. Nothing
+//! is ever deleted, so the file has no free pages.
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+use std::iter;
+
+#[cfg(test)]
+mod tests;
+
+/// SQLite's default page size.
+const PAGE_SIZE: usize = 4096;
+/// The size of the database header at the start of the first page.
+const HEADER_SIZE: usize = 100;
+
+/// The flags starting the header of each kind of b-tree page.
+const INTERIOR_INDEX: u8 = 0x02;
+const INTERIOR_TABLE: u8 = 0x05;
+const LEAF_INDEX: u8 = 0x0a;
+const LEAF_TABLE: u8 = 0x0d;
+
+/// A value of a column. These are ordered like SQLite orders them in an index, with text compared
+/// byte by byte. Integers are stored as signed ones, so they have to be below 2^63.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+crate enum Value<'a> {
+    Null,
+    Integer(u64),
+    Text(Cow<'a, str>),
+}
+
+/// A row of a table: its rowid and the values of its columns.
+crate type Row<'a> = (u64, Vec<Value<'a>>);
+
+crate struct Database {
+    /// The pages written so far. The first one is filled in last, once the schema is known.
+    pages: Vec<Vec<u8>>,
+    /// The rows of the `sqlite_master` table: the type, name, table, root page and SQL of each
+    /// table and index.
+    schema: Vec<(&'static str, String, String, u32, Option<String>)>,
+}
+
+impl Database {
+    crate fn new() -> Self {
+        Database { pages: vec![vec![0; PAGE_SIZE]], schema: Vec::new() }
+    }
+
+    /// Adds the table `name`, created by `sql`, with `rows` sorted by rowid.
+    crate fn add_table(&mut self, name: &str, sql: &str, rows: &[Row<'_>]) {
+        let root = self.table(rows, false);
+        self.schema.push(("table", name.to_owned(), name.to_owned(), root, Some(sql.to_owned())));
+    }
+
+    /// Adds the index `name` of the `columns` of `table`, whose rows are `rows`. The index of a
+    /// `PRIMARY KEY` or `UNIQUE` column is named `sqlite_autoindex_<table>_<n>` and has no SQL.
+    crate fn add_index(
+        &mut self,
+        name: &str,
+        table: &str,
+        sql: Option<&str>,
+        rows: &[Row<'_>],
+        columns: &[usize],
+    ) {
+        let entries = rows
+            .iter()
+            .map(|(rowid, values)| {
+                let key = columns.iter().map(|&c| values[c].clone());
+                key.chain(iter::once(Value::Integer(*rowid))).collect()
+            })
+            .collect();
+        let root = self.index(entries);
+        let sql = sql.map(str::to_owned);
+        self.schema.push(("index", name.to_owned(), table.to_owned(), root, sql));
+    }
+
+    /// Writes out the database, with the schema on its first page.
+    crate fn write(mut self, out: &mut impl Write) -> io::Result<()> {
+        let schema = std::mem::take(&mut self.schema);
+        let rows: Vec<_> = schema
+            .iter()
+            .enumerate()
+            .map(|(i, (kind, name, table, root, sql))| {
+                let sql = sql.as_deref().map_or(Value::Null, |sql| Value::Text(sql.into()));
+                let values = vec![
+                    Value::Text((*kind).into()),
+                    Value::Text(name.into()),
+                    Value::Text(table.into()),
+                    Value::Integer(u64::from(*root)),
+                    sql,
+                ];
+                (i as u64 + 1, values)
+            })
+            .collect();
+        self.table(&rows, true);
+
+        let len = self.pages.len() as u32;
+        let header = &mut self.pages[0][..HEADER_SIZE];
+        header[..16].copy_from_slice(b"SQLite format 3\0");
+        header[16..18].copy_from_slice(&(PAGE_SIZE as u16).to_be_bytes());
+        // The file format versions of rollback journals, no space reserved at the end of pages, and
+        // the payload fractions every database uses.
+        header[18..24].copy_from_slice(&[1, 1, 0, 64, 32, 32]);
+        let fields = [
+            // The file change counter, and the size of the database in pages.
+            (24, 1),
+            (28, len),
+            // The schema cookie and schema format number.
+            (40, 1),
+            (44, 4),
+            // The text encoding, UTF-8.
+            (56, 1),
+            // The change counter the version below is valid for, and the version of SQLite the
+            // file is compatible with.
+            (92, 1),
+            (96, 3_031_001),
+        ];
+        for &(offset, value) in &fields {
+            header[offset..offset + 4].copy_from_slice(&value.to_be_bytes());
+        }
+        for page in &self.pages {
+            out.write_all(page)?;
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, page: Vec<u8>) -> u32 {
+        self.pages.push(page);
+        self.pages.len() as u32
+    }
+
+    /// Adds `page` as the root of a b-tree, which is the first page for the `sqlite_master`
+    /// table.
+    fn root(&mut self, page: Vec<u8>, first: bool) -> u32 {
+        if first {
+            self.pages[0] = page;
+            1
+        } else {
+            self.push(page)
+        }
+    }
+
+    /// Writes the b-tree of a table with `rows` and returns its root page.
+    fn table(&mut self, rows: &[Row<'_>], first: bool) -> u32 {
+        // The pages of the `sqlite_master` table all leave room for the database header, since
+        // any of them could end up as the root.
+        let budget = PAGE_SIZE - if first { HEADER_SIZE } else { 0 };
+        // Leaf pages hold every row, so they're simply filled one after the other.
+        let mut leaves = vec![Vec::new()];
+        let mut used = 0;
+        for (rowid, values) in rows {
+            let payload = record(values);
+            let mut prefix = Vec::new();
+            varint(&mut prefix, payload.len() as u64);
+            varint(&mut prefix, *rowid);
+            let cell = Cell { prefix, payload, index: false };
+            let size = cell.size() + 2;
+            if used + size > budget - 8 && !leaves.last().unwrap().is_empty() {
+                leaves.push(Vec::new());
+                used = 0;
+            }
+            leaves.last_mut().unwrap().push((cell, *rowid));
+            used += size;
+        }
+        if leaves.len() == 1 {
+            let page = self.page(LEAF_TABLE, cells(leaves.pop().unwrap()), None, first);
+            return self.root(page, first);
+        }
+        // Each child of an interior page is keyed by the largest rowid in it.
+        let mut level = Vec::new();
+        for leaf in leaves {
+            let key = leaf.last().unwrap().1;
+            let page = self.page(LEAF_TABLE, cells(leaf), None, false);
+            level.push((self.push(page), key));
+        }
+        loop {
+            let last = level.pop().unwrap();
+            let children = level.into_iter().map(|(child, key)| {
+                let mut prefix = child.to_be_bytes().to_vec();
+                varint(&mut prefix, key);
+                (Cell { prefix, payload: Vec::new(), index: false }, (child, key))
+            });
+            let (groups, separators) = split(children.collect(), budget - 12);
+            let rights = separators.into_iter().map(|(_, right)| right).chain(iter::once(last));
+            let root = groups.len() == 1;
+            let mut pages = Vec::new();
+            for (group, (right, key)) in groups.into_iter().zip(rights) {
+                let page = self.page(INTERIOR_TABLE, cells(group), Some(right), first && root);
+                pages.push((page, key));
+            }
+            if root {
+                return self.root(pages.pop().unwrap().0, first);
+            }
+            level = pages.into_iter().map(|(page, key)| (self.push(page), key)).collect();
+        }
+    }
+
+    /// Writes the b-tree of an index with `entries`, the indexed values of each row followed by
+    /// its rowid, and returns its root page.
+    fn index(&mut self, mut entries: Vec<Vec<Value<'_>>>) -> u32 {
+        entries.sort();
+        let entries = entries.iter().map(|entry| {
+            let payload = record(entry);
+            let mut prefix = Vec::new();
+            varint(&mut prefix, payload.len() as u64);
+            (Cell { prefix, payload, index: true }, ())
+        });
+        // Unlike in tables, each entry is only stored once: the entries between two pages go in
+        // their parent.
+        let (mut leaves, separators) = split(entries.collect(), PAGE_SIZE - 8);
+        if leaves.len() == 1 {
+            let page = self.page(LEAF_INDEX, cells(leaves.pop().unwrap()), None, false);
+            return self.push(page);
+        }
+        let mut children = Vec::new();
+        for leaf in leaves {
+            let page = self.page(LEAF_INDEX, cells(leaf), None, false);
+            children.push(self.push(page));
+        }
+        let mut keys: Vec<_> = separators.into_iter().map(|(key, ())| key).collect();
+        loop {
+            let last = children.pop().unwrap();
+            let items = children.into_iter().zip(keys).map(|(child, mut key)| {
+                key.prefix = [&child.to_be_bytes()[..], &key.prefix].concat();
+                (key, child)
+            });
+            let (groups, separators) = split(items.collect(), PAGE_SIZE - 12);
+            let rights: Vec<_> =
+                separators.iter().map(|&(_, right)| right).chain(iter::once(last)).collect();
+            keys = separators
+                .into_iter()
+                .map(|(mut key, _)| {
+                    key.prefix.drain(..4);
+                    key
+                })
+                .collect();
+            let mut pages = Vec::new();
+            for (group, right) in groups.into_iter().zip(rights) {
+                pages.push(self.page(INTERIOR_INDEX, cells(group), Some(right), false));
+            }
+            if pages.len() == 1 {
+                return self.push(pages.pop().unwrap());
+            }
+            children = pages.into_iter().map(|page| self.push(page)).collect();
+        }
+    }
+
+    /// Lays out a b-tree page of `kind` holding `cells`, with its `right` child if it's an
+    /// interior page. The header of the first page comes before that of the b-tree.
+    fn page(&mut self, kind: u8, cells: Vec<Cell>, right: Option<u32>, first: bool) -> Vec<u8> {
+        let mut page = vec![0; PAGE_SIZE];
+        let start = if first { HEADER_SIZE } else { 0 };
+        let mut pointer = start + if right.is_some() { 12 } else { 8 };
+        // The cells are stored from the end of the page, and pointed to from after the header.
+        let mut content = PAGE_SIZE;
+        for cell in &cells {
+            let cell = cell.write(self);
+            content -= cell.len();
+            page[content..content + cell.len()].copy_from_slice(&cell);
+            page[pointer..pointer + 2].copy_from_slice(&(content as u16).to_be_bytes());
+            pointer += 2;
+        }
+        debug_assert!(pointer <= content);
+        page[start] = kind;
+        page[start + 3..start + 5].copy_from_slice(&(cells.len() as u16).to_be_bytes());
+        page[start + 5..start + 7].copy_from_slice(&(content as u16).to_be_bytes());
+        if let Some(right) = right {
+            page[start + 8..start + 12].copy_from_slice(&right.to_be_bytes());
+        }
+        page
+    }
+}
+
+/// A cell of a b-tree page: what comes before the payload (like the child page it points to and
+/// the size of the payload), and the payload itself. If the payload is too large, only its start
+/// is kept in the cell, and the rest is stored in a list of overflow pages.
+#[derive(Debug)]
+struct Cell {
+    prefix: Vec<u8>,
+    payload: Vec<u8>,
+    /// Whether this is a cell of an index, which keep less of their payload in the page.
+    index: bool,
+}
+
+impl Cell {
+    /// How much of the payload is stored in the page.
+    fn local_len(&self) -> usize {
+        let len = self.payload.len();
+        let max = if self.index { (PAGE_SIZE - 12) * 64 / 255 - 23 } else { PAGE_SIZE - 35 };
+        if len <= max {
+            return len;
+        }
+        let min = (PAGE_SIZE - 12) * 32 / 255 - 23;
+        let len = min + (len - min) % (PAGE_SIZE - 4);
+        if len <= max { len } else { min }
+    }
+
+    /// The size of the cell in its page.
+    fn size(&self) -> usize {
+        let local = self.local_len();
+        self.prefix.len() + local + if local < self.payload.len() { 4 } else { 0 }
+    }
+
+    /// The bytes of the cell, writing the overflow pages it points to into `db`.
+    fn write(&self, db: &mut Database) -> Vec<u8> {
+        let (local, rest) = self.payload.split_at(self.local_len());
+        let mut cell = [&self.prefix[..], local].concat();
+        if !rest.is_empty() {
+            // Each overflow page starts with the number of the next one, so they're written last
+            // to first.
+            let mut next = 0u32;
+            for chunk in rest.chunks(PAGE_SIZE - 4).rev() {
+                let mut page = next.to_be_bytes().to_vec();
+                page.extend_from_slice(chunk);
+                page.resize(PAGE_SIZE, 0);
+                next = db.push(page);
+            }
+            cell.extend_from_slice(&next.to_be_bytes());
+        }
+        cell
+    }
+}
+
+fn cells<T>(group: Vec<(Cell, T)>) -> Vec<Cell> {
+    group.into_iter().map(|(cell, _)| cell).collect()
+}
+
+/// Splits `items` into groups whose cells fit in `budget` bytes, along with their pointers. The
+/// item following each group but the last one is returned as a separator, which the level of the
+/// b-tree above points between the pages with. Every group has at least one item.
+fn split<T>(items: Vec<(Cell, T)>, budget: usize) -> (Vec<Vec<(Cell, T)>>, Vec<(Cell, T)>) {
+    let mut groups = vec![Vec::new()];
+    let mut separators = Vec::new();
+    let mut used = 0;
+    for item in items {
+        let size = item.0.size() + 2;
+        let group = groups.last_mut().unwrap();
+        if used + size > budget && !group.is_empty() {
+            separators.push(item);
+            groups.push(Vec::new());
+            used = 0;
+        } else {
+            group.push(item);
+            used += size;
+        }
+    }
+    // If the last item became a separator, it goes in the last group instead, and the item
+    // before it is the separator.
+    if groups.last().unwrap().is_empty() && groups.len() > 1 {
+        let n = groups.len();
+        let item = separators.pop().unwrap();
+        separators.push(groups[n - 2].pop().unwrap());
+        groups[n - 1].push(item);
+    }
+    (groups, separators)
+}
+
+/// Writes `v` as a variable-length integer: big-endian groups of 7 bits, with the high bit of
+/// each byte but the last set, and up to 9 bytes of which the last holds 8 bits.
+fn varint(out: &mut Vec<u8>, mut v: u64) {
+    let mut bytes = [0; 9];
+    if v > 0x00ff_ffff_ffff_ffff {
+        bytes[8] = v as u8;
+        v >>= 8;
+        for byte in bytes[..8].iter_mut().rev() {
+            *byte = (v & 0x7f) as u8 | 0x80;
+            v >>= 7;
+        }
+        out.extend_from_slice(&bytes);
+        return;
+    }
+    let mut start = 8;
+    bytes[8] = (v & 0x7f) as u8;
+    v >>= 7;
+    while v != 0 {
+        start -= 1;
+        bytes[start] = (v & 0x7f) as u8 | 0x80;
+        v >>= 7;
+    }
+    out.extend_from_slice(&bytes[start..]);
+}
+
+/// The payload of a row or index entry: a header with the type of each value, then the values.
+fn record(values: &[Value<'_>]) -> Vec<u8> {
+    let mut types = Vec::new();
+    let mut body = Vec::new();
+    for value in values {
+        match value {
+            Value::Null => varint(&mut types, 0),
+            Value::Integer(0) => varint(&mut types, 8),
+            Value::Integer(1) => varint(&mut types, 9),
+            &Value::Integer(n) => {
+                // The types of integers stored in 1, 2, 3, 4, 6 and 8 bytes.
+                let sizes = [(1, 1), (2, 2), (3, 3), (4, 4), (6, 5), (8, 6)];
+                let (len, ty) = *sizes.iter().find(|&&(len, _)| n < 1 << (8 * len - 1)).unwrap();
+                varint(&mut types, ty);
+                body.extend_from_slice(&n.to_be_bytes()[8 - len..]);
+            }
+            Value::Text(s) => {
+                varint(&mut types, s.len() as u64 * 2 + 13);
+                body.extend_from_slice(s.as_bytes());
+            }
+        }
+    }
+    // The size of the header counts the size of the varint it's stored in.
+    let mut len = types.len() as u64 + 1;
+    if len >= 0x80 {
+        len += 1;
+    }
+    let mut record = Vec::with_capacity(types.len() + body.len() + 2);
+    varint(&mut record, len);
+    record.extend_from_slice(&types);
+    record.extend_from_slice(&body);
+    record
+}
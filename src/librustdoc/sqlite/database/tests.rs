@@ -0,0 +1,51 @@
+use std::convert::TryInto;
+
+use super::*;
+
+fn encode(v: u64) -> Vec<u8> {
+    let mut out = Vec::new();
+    varint(&mut out, v);
+    out
+}
+
+#[test]
+fn varints() {
+    assert_eq!(encode(0), [0]);
+    assert_eq!(encode(0x7f), [0x7f]);
+    assert_eq!(encode(0x80), [0x81, 0]);
+    assert_eq!(encode(0x00ff_ffff_ffff_ffff), [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]);
+    assert_eq!(encode(u64::MAX), [0xff; 9]);
+}
+
+#[test]
+fn records() {
+    let values = [Value::Null, Value::Integer(1), Value::Integer(300), Value::Text("ab".into())];
+    assert_eq!(record(&values), [5, 0, 9, 2, 17, 0x01, 0x2c, b'a', b'b']);
+}
+
+#[test]
+fn splits_leave_no_group_empty() {
+    let cell = |len| (Cell { prefix: Vec::new(), payload: vec![0; len], index: false }, len);
+    let (groups, separators) = split(vec![cell(40), cell(40), cell(40)], 90);
+    let lens: Vec<Vec<_>> =
+        groups.iter().map(|g| g.iter().map(|&(_, len)| len).collect()).collect();
+    assert_eq!(lens, [vec![40], vec![40]]);
+    assert_eq!(separators.len(), 1);
+}
+
+#[test]
+fn header_and_schema() {
+    let mut db = Database::new();
+    let rows: Vec<Row<'_>> =
+        (1..=1000).map(|i| (i, vec![Value::Text("x".repeat(100).into())])).collect();
+    db.add_table("t", "CREATE TABLE t (a TEXT)", &rows);
+    let mut out = Vec::new();
+    db.write(&mut out).unwrap();
+    assert!(out.starts_with(b"SQLite format 3\0"));
+    assert_eq!(out.len() % PAGE_SIZE, 0);
+    let pages = u32::from_be_bytes(out[28..32].try_into().unwrap());
+    assert_eq!(pages as usize, out.len() / PAGE_SIZE);
+    // The schema is a single leaf page after the header, and the table has an interior root.
+    assert_eq!(out[HEADER_SIZE], LEAF_TABLE);
+    assert_eq!(out[(pages as usize - 1) * PAGE_SIZE], INTERIOR_TABLE);
+}
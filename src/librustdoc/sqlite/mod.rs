@@ -0,0 +1,186 @@
+//! Rustdoc's SQLite backend
+//!
+//! This writes the items of the crate to an SQLite database, `<out>/<crate>.db`, so that tools
+//! can look them up with `SELECT` rather than reading the whole output of the JSON backend.
+//! Rustdoc doesn't link against SQLite, so the file is written by the [`database`] module.
+//!
+//! The items are those of the JSON backend, which this format is rendered by, and the tables use
+//! its IDs. The `inner` column of `items` holds the JSON of the item's kind-specific data, which
+//! can be queried with SQLite's JSON functions.
+
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::config::OutputFormat;
+use crate::formats::registry::Backend;
+use crate::formats::run_format;
+use crate::json::types;
+use crate::json::JsonRenderer;
+
+use self::database::{Database, Row, Value};
+
+mod database;
+
+#[cfg(test)]
+mod tests;
+
+crate static BACKEND: Backend = Backend {
+    name: "sqlite",
+    format: OutputFormat::Sqlite,
+    timer: "render_sqlite",
+    run: run_format::<JsonRenderer>,
+};
+
+const CRATES: &str = "\
+CREATE TABLE crates (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    version TEXT,
+    html_root_url TEXT
+)";
+
+const ITEMS: &str = "\
+CREATE TABLE items (
+    id TEXT PRIMARY KEY,
+    crate_id INTEGER NOT NULL,
+    parent TEXT,
+    name TEXT,
+    path TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    visibility TEXT NOT NULL,
+    docs TEXT NOT NULL,
+    file TEXT,
+    line INTEGER,
+    html_url TEXT,
+    deprecated INTEGER NOT NULL,
+    inner TEXT NOT NULL
+)";
+
+const PATHS: &str = "\
+CREATE TABLE paths (
+    id TEXT PRIMARY KEY,
+    crate_id INTEGER NOT NULL,
+    path TEXT NOT NULL,
+    kind TEXT NOT NULL
+)";
+
+const IMPLS: &str = "\
+CREATE TABLE impls (
+    id TEXT PRIMARY KEY,
+    for_id TEXT,
+    trait_id TEXT,
+    synthetic INTEGER NOT NULL,
+    blanket INTEGER NOT NULL
+)";
+
+/// The indices of the tables besides their primary keys: the name, table, SQL and the column each
+/// one is on.
+const INDICES: &[(&str, &str, &str, usize)] = &[
+    ("items_parent", "items", "CREATE INDEX items_parent ON items (parent)", 2),
+    ("items_path", "items", "CREATE INDEX items_path ON items (path)", 4),
+    ("paths_path", "paths", "CREATE INDEX paths_path ON paths (path)", 2),
+    ("impls_for_id", "impls", "CREATE INDEX impls_for_id ON impls (for_id)", 1),
+    ("impls_trait_id", "impls", "CREATE INDEX impls_trait_id ON impls (trait_id)", 2),
+];
+
+/// Writes the database of `krate` to `out`.
+crate fn write(out: &mut impl Write, krate: &types::Crate) -> io::Result<()> {
+    // `id` is an alias of the rowid, so it's left out of the rows themselves.
+    let mut crates: Vec<Row<'_>> = vec![(
+        u64::from(krate.crate_num),
+        vec![Value::Null, text(&krate.name), opt_text(krate.crate_version.as_deref()), Value::Null],
+    )];
+    for (&id, c) in &krate.external_crates {
+        let values = vec![
+            Value::Null,
+            text(&c.name),
+            opt_text(c.version.as_deref()),
+            opt_text(c.html_root_url.as_deref()),
+        ];
+        crates.push((u64::from(id), values));
+    }
+    crates.sort_by_key(|&(id, _)| id);
+
+    let mut paths = Vec::new();
+    for (id, summary) in &krate.paths {
+        let values = vec![
+            text(&id.0),
+            Value::Integer(u64::from(summary.crate_id)),
+            text(summary.path.join("::")),
+            text(variant_name(&summary.kind)),
+        ];
+        paths.push((paths.len() as u64 + 1, values));
+    }
+
+    let mut items = Vec::new();
+    let mut impls = Vec::new();
+    for (id, item) in &krate.index {
+        let values = vec![
+            text(&id.0),
+            Value::Integer(u64::from(item.crate_id)),
+            opt_text(item.parent.as_ref().map(|p| p.0.as_str())),
+            opt_text(item.name.as_deref()),
+            text(item.path.join("::")),
+            text(variant_name(&item.kind)),
+            text(variant_name(&item.visibility)),
+            text(&item.docs),
+            item.source.as_ref().map_or(Value::Null, |s| text(s.filename.to_string_lossy())),
+            item.source.as_ref().map_or(Value::Null, |s| Value::Integer(s.begin.0 as u64)),
+            opt_text(item.html_url.as_deref()),
+            Value::Integer(item.deprecation.is_some() as u64),
+            text(serde_json::to_string(&item.inner)?),
+        ];
+        items.push((items.len() as u64 + 1, values));
+        if let types::ItemEnum::ImplItem(impl_) = &item.inner {
+            let values = vec![
+                text(&id.0),
+                opt_text(resolved_id(&impl_.for_)),
+                opt_text(impl_.trait_.as_ref().and_then(resolved_id)),
+                Value::Integer(impl_.synthetic as u64),
+                Value::Integer(impl_.blanket_impl.is_some() as u64),
+            ];
+            impls.push((impls.len() as u64 + 1, values));
+        }
+    }
+
+    let mut db = Database::new();
+    db.add_table("crates", CRATES, &crates);
+    let tables = [("items", ITEMS, items), ("paths", PATHS, paths), ("impls", IMPLS, impls)];
+    for (table, sql, rows) in &tables {
+        db.add_table(table, sql, rows);
+        // The index SQLite keeps for the `PRIMARY KEY` of the table.
+        db.add_index(&format!("sqlite_autoindex_{}_1", table), table, None, rows, &[0]);
+        for &(name, _, sql, column) in INDICES.iter().filter(|index| index.1 == *table) {
+            db.add_index(name, table, Some(sql), rows, &[column]);
+        }
+    }
+    db.write(out)
+}
+
+fn text<'a>(s: impl Into<Cow<'a, str>>) -> Value<'a> {
+    Value::Text(s.into())
+}
+
+fn opt_text(s: Option<&str>) -> Value<'_> {
+    s.map_or(Value::Null, text)
+}
+
+/// The ID of the item `ty` names, if it's a path.
+fn resolved_id(ty: &types::Type) -> Option<&str> {
+    match ty {
+        types::Type::ResolvedPath { id, .. } => Some(&id.0),
+        _ => None,
+    }
+}
+
+/// The serialized name of the variant `value` is, e.g. `"restricted"` for
+/// `Visibility::Restricted { .. }`.
+fn variant_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        Ok(serde_json::Value::Object(map)) => map.keys().next().cloned().unwrap_or_default(),
+        _ => String::new(),
+    }
+}
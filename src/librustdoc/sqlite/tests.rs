@@ -0,0 +1,12 @@
+use super::*;
+
+#[test]
+fn variant_names() {
+    assert_eq!(variant_name(&types::Visibility::Public), "public");
+    assert_eq!(variant_name(&types::ItemKind::Struct), "struct");
+    let restricted = types::Visibility::Restricted {
+        parent: types::Id("0:1".to_owned()),
+        path: "super".to_owned(),
+    };
+    assert_eq!(variant_name(&restricted), "restricted");
+}
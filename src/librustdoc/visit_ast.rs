@@ -8,7 +8,7 @@ use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::{DefId, LOCAL_CRATE};
 use rustc_hir::Node;
 use rustc_middle::middle::privacy::AccessLevel;
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::{DefIdTree, TyCtxt};
 use rustc_span::hygiene::MacroKind;
 use rustc_span::source_map::Spanned;
 use rustc_span::symbol::{kw, sym, Ident, Symbol};
@@ -72,9 +72,13 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
             None,
         );
         // Attach the crate's exported macros to the top-level module:
-        module
-            .macros
-            .extend(krate.exported_macros.iter().map(|def| self.visit_local_macro(def, None)));
+        module.macros.extend(
+            krate
+                .exported_macros
+                .iter()
+                .filter(|def| def.ast.macro_rules)
+                .map(|def| self.visit_local_macro(def, None)),
+        );
         module.is_crate = true;
 
         self.cx.renderinfo.get_mut().exact_paths = self.exact_paths;
@@ -247,6 +251,15 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
             let item = self.cx.tcx.hir().expect_item(i.id);
             self.visit_item(item, None, &mut om);
         }
+        // Unlike `macro_rules!` macros, `macro` items belong to the module they're defined in.
+        let tcx = self.cx.tcx;
+        let module_did = tcx.hir().local_def_id(id).to_def_id();
+        for def in tcx.hir().krate().exported_macros {
+            let did = tcx.hir().local_def_id(def.hir_id).to_def_id();
+            if !def.ast.macro_rules && tcx.parent(did) == Some(module_did) {
+                om.macros.push(self.visit_local_macro(def, None));
+            }
+        }
         self.inside_public_path = orig_inside_public_path;
         om
     }
@@ -618,14 +631,18 @@ impl<'a, 'tcx> RustdocVisitor<'a, 'tcx> {
         let tts = def.ast.body.inner_tokens().trees().collect::<Vec<_>>();
         // Extract the spans of all matchers. They represent the "interface" of the macro.
         let matchers = tts.chunks(4).map(|arm| arm[0].span()).collect();
+        let rules = clean::utils::parse_macro_rules(&tts);
 
         Macro {
             hid: def.hir_id,
             def_id: self.cx.tcx.hir().local_def_id(def.hir_id).to_def_id(),
             attrs: &def.attrs,
+            vis: &def.vis,
             name: renamed.unwrap_or(def.ident.name),
             span: def.span,
             matchers,
+            rules,
+            macro_rules: def.ast.macro_rules,
             imported_from: None,
         }
     }
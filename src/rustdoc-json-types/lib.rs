@@ -0,0 +1,1333 @@
+//! Rustdoc's JSON output interface
+//!
+//! These types are the public API exposed through the `--output-format json` flag. The [`Crate`]
+//! struct is the root of the JSON blob and all other items are contained within.
+//!
+//! This crate doesn't depend on the compiler, so tools consuming the output can depend on it
+//! directly to deserialize it.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// A `Crate` is the root of the emitted JSON blob. It contains all type/documentation information
+/// about the language items in the local crate, as well as info about external items to allow
+/// tools to find or link to them.
+///
+/// The output is deterministic: maps are sorted by key, and lists of items are in declaration
+/// order or, for impls, sorted by def path.
+///
+/// The index is generic so that it can be written from something other than a map held in memory,
+/// see [`Crate::map_index`].
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Crate<I = BTreeMap<Id, Item>> {
+    /// The name of the local crate.
+    pub name: String,
+    /// The `crate_id` of the items of the local crate.
+    pub crate_num: u32,
+    /// The disambiguator of the local crate, see [`ExternalCrate::disambiguator`].
+    pub disambiguator: String,
+    /// The id of the root [`Module`] item of the local crate.
+    pub root: Id,
+    /// The version string given to `--crate-version`, if any.
+    pub crate_version: Option<String>,
+    /// Whether or not the output includes private items, which is the case with
+    /// `--document-private-items` (or `#![doc(document_private_items)]`). This says nothing about
+    /// `#[doc(hidden)]` items, which are stripped separately.
+    pub includes_private: bool,
+    /// Whether or not the output includes `#[doc(hidden)]` items, which is the case with
+    /// `--document-hidden-items`.
+    pub includes_hidden: bool,
+    /// A collection of all items in the local crate as well as some external traits and their
+    /// items that are referenced locally.
+    ///
+    /// When the output is written as newline-delimited JSON (`--json-lines`), this is empty: each
+    /// [`Item`] is written on its own line instead, and this struct is the last line of the file.
+    /// It's also empty when the output is split with `--json-shard`, see `shards`.
+    pub index: I,
+    /// With `--json-shard`, maps the path of each module (like `"std::collections"`) to the file
+    /// its part of the index is written to, relative to the output directory. Each of these files
+    /// has a map from IDs to items, like `index`. The items of impls, traits and types are in the
+    /// shard of the module they're listed in, and items listed in no module in that of the crate
+    /// root.
+    pub shards: BTreeMap<String, String>,
+    /// Full definitions of every trait known to rustdoc, including external traits that are
+    /// implemented or referenced by local items. The items of these traits can be found in
+    /// `index`.
+    pub traits: BTreeMap<Id, Trait>,
+    /// Maps IDs to fully qualified paths and other info helpful for generating links.
+    ///
+    /// With `--json-externs-file`, this only has the local items: the external ones are in
+    /// [`Externs::paths`].
+    pub paths: BTreeMap<Id, ItemSummary>,
+    /// Maps `crate_id` of items to a crate name and html_root_url if it exists.
+    ///
+    /// With `--json-externs-file`, only the `name` and `disambiguator` of each crate are set here:
+    /// the rest is in [`Externs::crates`].
+    pub external_crates: BTreeMap<u32, ExternalCrate>,
+    /// How much of the local crate is documented, as counted by `--show-coverage`.
+    pub coverage: Coverage,
+    /// A hash of the signatures of the public items in the output, which only changes when the
+    /// public API does. Docs and source locations aren't part of it, nor are the items left out
+    /// of the output, for instance with `--json-kinds`.
+    pub api_hash: String,
+    /// Counts of the items in the output, for tools that don't need the items themselves.
+    pub stats: Stats,
+    /// The version of the compiler used, as printed by `rustc --version`.
+    pub rustc_version: String,
+    /// The version of rustdoc used, as printed by `rustdoc --version`.
+    pub rustdoc_version: String,
+    /// The target triple the crate was documented for, e.g. `x86_64-unknown-linux-gnu`.
+    pub target: String,
+    /// Whether the crate is a proc-macro crate, whose only public items are its procedural
+    /// macros.
+    pub is_proc_macro: bool,
+    /// A single version number to be used in the future when making backwards incompatible changes
+    /// to the JSON output.
+    pub format_version: u32,
+}
+
+impl<I> Crate<I> {
+    /// The same crate with its index replaced by `f(index)`.
+    pub fn map_index<J>(self, f: impl FnOnce(I) -> J) -> Crate<J> {
+        let Crate {
+            name,
+            crate_num,
+            disambiguator,
+            root,
+            crate_version,
+            includes_private,
+            includes_hidden,
+            index,
+            shards,
+            traits,
+            paths,
+            external_crates,
+            coverage,
+            api_hash,
+            stats,
+            rustc_version,
+            rustdoc_version,
+            target,
+            is_proc_macro,
+            format_version,
+        } = self;
+        Crate {
+            name,
+            crate_num,
+            disambiguator,
+            root,
+            crate_version,
+            includes_private,
+            includes_hidden,
+            index: f(index),
+            shards,
+            traits,
+            paths,
+            external_crates,
+            coverage,
+            api_hash,
+            stats,
+            rustc_version,
+            rustdoc_version,
+            target,
+            is_proc_macro,
+            format_version,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExternalCrate {
+    pub name: String,
+    pub html_root_url: Option<String>,
+    /// The directory of the crate's HTML documentation relative to the output directory, e.g.
+    /// `"serde/"`, if it was documented into the same directory. Items of the crate can then be
+    /// linked to without an `html_root_url`.
+    pub html_local_path: Option<String>,
+    /// The version of the crate. This is only known for crates from a registry.
+    pub version: Option<String>,
+    /// The stable hash of the crate's metadata, which identifies the exact build of the crate
+    /// that was linked to.
+    pub hash: Option<String>,
+    /// Tells the crate apart from other crates of the same name, like two versions of it. Unlike
+    /// `crate_id`s, which are only meaningful within one output, this is the same in the output of
+    /// every crate linking to it, so it can be used to match up crates across outputs.
+    pub disambiguator: String,
+    /// Where the sources of the crate came from, if it could be told from their location.
+    pub source: Option<CrateSource>,
+}
+
+/// The external data of the crates documented into the same directory with `--json-externs-file`,
+/// written to `externs.json` there. Each crate adds the crates and items it knows about to the ones
+/// already in the file, so that they're only written once for a whole workspace.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Externs {
+    /// The external crates, by [`ExternalCrate::disambiguator`].
+    pub crates: BTreeMap<String, ExternalCrate>,
+    /// The summaries of external items, like [`Crate::paths`]. The `crate_id` of each is the one
+    /// of its crate in the output of the first crate that added it, so the crate is better told
+    /// from the first segment of `path`.
+    pub paths: BTreeMap<Id, ItemSummary>,
+}
+
+/// The items added, removed and changed since an earlier output of the crate, written to
+/// `<crate>.diff.json` with `--json-diff`. Items are matched up by [`Id`], so an item that was
+/// moved or renamed is both removed and added. Each list is sorted by path.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Diff {
+    pub added: Vec<ItemChange>,
+    pub removed: Vec<ItemChange>,
+    pub changed: Vec<ItemChange>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ItemChange {
+    pub id: Id,
+    /// The path of the item in the output it's in, the new one for changed items.
+    pub path: Vec<String>,
+    pub kind: ItemKind,
+    /// For changed items, the names of the fields of [`Item`] that differ, like `"docs"` or
+    /// `"inner"`. Fields locating the item in the source aren't compared. This is empty for added
+    /// and removed items.
+    pub fields: Vec<String>,
+}
+
+/// The version bump the changes of a [`Diff`] call for under semantic versioning, written to
+/// `<crate>.semver.json` with `--json-semver`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SemverReport {
+    /// The largest bump any of the changes calls for, or `patch` if none of them is to the public
+    /// API.
+    pub verdict: SemverBump,
+    /// The changes to the public API, sorted by path. An item can have several.
+    pub changes: Vec<SemverChange>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SemverBump {
+    Patch,
+    Minor,
+    Major,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SemverChange {
+    pub id: Id,
+    pub path: Vec<String>,
+    pub kind: ItemKind,
+    pub rule: SemverRule,
+    pub bump: SemverBump,
+}
+
+/// What changed about an item, as named in the rules of RFC 1105.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SemverRule {
+    /// A public item was removed, or made private or hidden.
+    Removed,
+    /// A public item was added, or an existing one made public.
+    Added,
+    /// A variant was added to an enum, which is only a minor change if it's `#[non_exhaustive]`.
+    VariantAdded,
+    /// A public field was added to a struct, which is only a minor change if the struct couldn't
+    /// be built outside of its crate already.
+    FieldAdded,
+    /// A struct that could be built with a struct expression got a private field.
+    FieldsMadePrivate,
+    /// An item without a default was added to a trait that isn't sealed.
+    RequiredItemAdded,
+    /// A struct or enum became `#[non_exhaustive]`.
+    NonExhaustiveAdded,
+    /// The parameters, return type, generics or qualifiers of a function or method changed.
+    SignatureChanged,
+    /// The type of a field, constant, static or type alias changed, or the bounds of an
+    /// associated type.
+    TypeChanged,
+    /// The generic parameters or where clauses of a type or trait changed.
+    GenericsChanged,
+    /// A trait became unsafe or stopped being object safe, or its supertraits changed.
+    TraitChanged,
+    /// An item is now of another kind, like a struct that became an enum.
+    KindChanged,
+    /// An item was deprecated.
+    Deprecated,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrateSource {
+    /// A crate downloaded from crates.io or another Cargo registry.
+    Registry,
+    /// A crate checked out from a git repository by Cargo.
+    Git,
+    /// Any other crate, with the path of its root source file.
+    Path(String),
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Coverage {
+    /// The counts for the whole crate.
+    pub total: CoverageCount,
+    /// The counts for the items directly in each module (including the fields, variants and
+    /// associated items of those), by the ID of the module. A module is counted in its parent.
+    pub modules: BTreeMap<Id, CoverageCount>,
+    /// Every counted item. Imports, trait impls and the items made up by rustdoc aren't counted.
+    pub items: BTreeMap<Id, ItemCoverage>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Stats {
+    /// The number of items of each kind. Kinds without any items are left out.
+    pub kinds: BTreeMap<ItemKind, u64>,
+    /// The number of impls, including the ones rustdoc makes up for auto traits and blanket
+    /// impls.
+    pub impls: u64,
+    /// How many of `impls` are trait impls.
+    pub trait_impls: u64,
+    /// The number of [`Item::stripped`] items.
+    pub stripped: u64,
+    /// The number of items that aren't stripped, with docs and without them. Every item is
+    /// undocumented with `--json-no-docs`.
+    pub documented: u64,
+    pub undocumented: u64,
+}
+
+/// The same counts as the ones printed by `--show-coverage --output-format json`.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct CoverageCount {
+    /// The number of items that have docs or should have them. An item only shouldn't when the
+    /// `missing_docs` lint is explicitly allowed for it.
+    pub total: u64,
+    pub with_docs: u64,
+    /// The number of items that have an example in their docs or should have one, according to
+    /// the `missing_doc_code_examples` lint.
+    pub total_examples: u64,
+    pub with_examples: u64,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct ItemCoverage {
+    pub has_docs: bool,
+    pub should_have_docs: bool,
+    pub has_example: bool,
+    pub should_have_example: bool,
+}
+
+/// For external (not defined in the local crate) items, you don't get the same level of
+/// information. This struct should contain enough to generate a link/reference to the item in
+/// question, or can be used by a tool that takes the json output of multiple crates to find
+/// the actual item definition with all the relevant info.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ItemSummary {
+    /// Can be used to look up the name and html_root_url of the crate this item came from in the
+    /// `external_crates` map.
+    pub crate_id: u32,
+    /// The list of path components for the fully qualified path of this item (e.g.
+    /// `["std", "io", "lazy", "Lazy"]` for `std::io::lazy::Lazy`).
+    pub path: Vec<String>,
+    /// Whether this item is a struct, trait, macro, etc.
+    pub kind: ItemKind,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Item {
+    /// The unique identifier of this item. Can be used to find this item in various mappings.
+    pub id: Id,
+    /// A hash of all the other fields of the item, which changes whenever any of them does. This
+    /// includes the docs, even with `--json-docs-file`.
+    pub hash: String,
+    /// This can be used as a key to the `external_crates` map of [`Crate`] to see which crate
+    /// this item came from.
+    pub crate_id: u32,
+    /// The item this one is listed in: the module, impl or trait containing it, or the struct,
+    /// union, enum or variant for fields and variants. Only the crate root has no parent.
+    pub parent: Option<Id>,
+    /// For the items of trait impls, the item of the trait they implement, whose docs apply if
+    /// this one has none.
+    pub trait_item: Option<Id>,
+    /// For foreign functions, statics and types, the `extern` block they're declared in.
+    pub extern_block: Option<ExternBlock>,
+    /// For items generated by a macro, the invocation of the macro. Their `source` points into the
+    /// definition of the macro.
+    pub expansion: Option<Expansion>,
+    /// Some items such as impls don't have names.
+    pub name: Option<String>,
+    /// The fully qualified path of this item, starting with the crate name, e.g.
+    /// `["std", "vec", "Vec", "push"]`. Impls have the path of the type they're implemented for.
+    pub path: Vec<String>,
+    /// The URL of the item in the documentation generated by the HTML backend, including the
+    /// anchor for items documented on the page of another one (like methods and fields). This is
+    /// relative to the output directory, unless the item's crate is documented elsewhere.
+    pub html_url: Option<String>,
+    /// The source location of this item (absent if it came from a macro expansion or inline
+    /// assembly).
+    pub source: Option<Span>,
+    /// By default all documented items are public, but you can tell rustdoc to output private items
+    /// so this field is needed to differentiate.
+    pub visibility: Visibility,
+    /// How far the item can be used from outside its crate, which `visibility` doesn't tell for
+    /// items re-exported from private modules or reachable through the public API. Only set for
+    /// items of the crate being documented.
+    pub effective_visibility: Option<EffectiveVisibility>,
+    /// Set for items that were stripped by rustdoc but are still listed, like the private fields
+    /// of structs or private modules with `--document-private-items` unset.
+    pub stripped: Option<StripReason>,
+    /// Whether the item itself has `#[doc(hidden)]`, as opposed to being in a hidden module.
+    /// Unless [`Crate::includes_hidden`] is set, the only hidden items in the output are those
+    /// `stripped` for it.
+    pub is_doc_hidden: bool,
+    /// The full markdown docstring of this item. With `--json-docs-file`, this is empty and the
+    /// docs are in `<crate>.docs.json` instead, a map from IDs to docs which leaves out the items
+    /// without any.
+    pub docs: String,
+    /// The first paragraph of `docs` on a single line, as shown in the tables of items of the
+    /// HTML documentation. Empty when the docs start with a code block.
+    pub docs_summary: String,
+    /// `docs` rendered to HTML the way the HTML backend does, if `--json-render-docs` was passed.
+    /// Intra-doc links point to the HTML documentation, relative to the output directory like
+    /// `html_url`.
+    pub docs_html: Option<String>,
+    /// The Markdown tree of `docs`, if `--json-docs-ast` was passed.
+    pub docs_ast: Option<Vec<DocNode>>,
+    /// The code blocks of `docs` that are run as doctests, if `--json-doctests` was passed.
+    pub doctests: Option<Vec<Doctest>>,
+    /// Every code block of `docs`, whether it's Rust code or not.
+    pub code_blocks: Vec<CodeBlock>,
+    /// This mapping resolves [intra-doc links](https://github.com/rust-lang/rfcs/blob/master/text/1946-intra-rustdoc-links.md) from the docstring to their IDs
+    pub links: BTreeMap<String, Id>,
+    /// Stringified versions of the attributes on this item (e.g. `"#[inline]"`)
+    pub attrs: Vec<String>,
+    /// The other names this item can be searched for with, given with `#[doc(alias = "...")]`,
+    /// in sorted order.
+    pub aliases: Vec<String>,
+    pub deprecation: Option<Deprecation>,
+    /// The configuration this item is available under, combined from its `#[cfg(...)]` and
+    /// `#[doc(cfg(...))]` attributes. These attributes are not repeated in `attrs`.
+    pub cfg: Option<Cfg>,
+    /// The configuration given with `#[doc(cfg(...))]` on this item and the items it's in. This is
+    /// what the HTML documentation shows as "Available on ... only".
+    pub doc_cfg: Option<Cfg>,
+    pub kind: ItemKind,
+    pub inner: ItemEnum,
+    // TODO: should we have a "stability" field if it's only used by the standard library?
+}
+
+/// An `extern` block, e.g. `#[link(name = "c")] extern "C" { ... }`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExternBlock {
+    /// The ID of the block, the same for every item declared in it. Blocks aren't in the index.
+    pub id: Id,
+    pub abi: Abi,
+    /// Stringified versions of the attributes on the block (e.g. `"#[link(name = \"c\")]"`)
+    pub attrs: Vec<String>,
+}
+
+/// A macro invocation, e.g. `foo!(...)` or `#[derive(Foo)]`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Expansion {
+    /// The ID of the macro. It's only in the index if the macro is documented.
+    pub macro_id: Option<Id>,
+    /// The name of the macro, or the path it was invoked with for attributes and derives.
+    pub name: String,
+    pub kind: MacroKind,
+    /// Where the macro was invoked.
+    pub call_site: Option<Span>,
+    /// Where the macro was defined.
+    pub def_site: Option<Span>,
+}
+
+/// A node of the Markdown tree of an item's docs, as parsed by rustdoc: with tables, footnotes
+/// and strikethrough, but without smart punctuation or task lists.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocNode {
+    Paragraph(Vec<DocNode>),
+    /// `level` goes from 1 for `#` to 6.
+    Heading {
+        level: u32,
+        children: Vec<DocNode>,
+    },
+    BlockQuote(Vec<DocNode>),
+    /// The code is as written, including the lines hidden from the documentation. `info` is the
+    /// info string after the opening fence, and is absent for indented blocks.
+    CodeBlock {
+        info: Option<String>,
+        code: String,
+    },
+    /// The children are `list_item`s. `start` is the number of the first item of ordered lists.
+    List {
+        start: Option<u64>,
+        children: Vec<DocNode>,
+    },
+    ListItem(Vec<DocNode>),
+    FootnoteDefinition {
+        label: String,
+        children: Vec<DocNode>,
+    },
+    /// The children are a `table_head` followed by `table_row`s, all made of `table_cell`s.
+    Table {
+        alignments: Vec<Alignment>,
+        children: Vec<DocNode>,
+    },
+    TableHead(Vec<DocNode>),
+    TableRow(Vec<DocNode>),
+    TableCell(Vec<DocNode>),
+    Emphasis(Vec<DocNode>),
+    Strong(Vec<DocNode>),
+    Strikethrough(Vec<DocNode>),
+    /// The URL of intra-doc links is the link as written, which is a key of [`Item::links`].
+    Link {
+        url: String,
+        title: String,
+        children: Vec<DocNode>,
+    },
+    Image {
+        url: String,
+        title: String,
+        children: Vec<DocNode>,
+    },
+    Text(String),
+    Code(String),
+    Html(String),
+    FootnoteReference(String),
+    SoftBreak,
+    HardBreak,
+    Rule,
+}
+
+/// The alignment of a table column.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Alignment {
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+/// A code block of an item's docs that `rustdoc --test` runs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Doctest {
+    /// The code of the test as written, including the lines hidden from the documentation (with
+    /// their leading `# ` removed). Like rustdoc, consumers need to wrap it in a `fn main` if it
+    /// doesn't have one.
+    pub code: String,
+    /// The line of [`Item::docs`] the code block starts on, counting from 1.
+    pub line: usize,
+    pub attrs: CodeBlockAttrs,
+}
+
+/// The attributes rustdoc reads from the info string of a Rust code block, which say how it's
+/// tested.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CodeBlockAttrs {
+    pub should_panic: bool,
+    pub no_run: bool,
+    /// Whether the test is ignored, on every target or only on some of them.
+    pub ignore: bool,
+    /// Whether the test is expected to fail to compile. These tests are never run.
+    pub compile_fail: bool,
+    /// The error codes a `compile_fail` test is expected to fail with, e.g. `["E0308"]`.
+    pub error_codes: Vec<String>,
+    /// Whether the code is compiled as a test harness (with `--test`) instead of being wrapped in
+    /// a `fn main`.
+    pub test_harness: bool,
+    pub allow_fail: bool,
+    /// The edition the test is compiled with, e.g. `"2018"`, if it's not the crate's.
+    pub edition: Option<String>,
+}
+
+/// A code block of an item's docs, as shown in the documentation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CodeBlock {
+    /// The language of the code: `"rust"` for the blocks rustdoc treats as Rust code (including
+    /// untagged ones), otherwise the first word of `info`, if any.
+    pub lang: Option<String>,
+    /// The info string after the opening fence, e.g. `"rust,no_run"`. Empty for indented blocks.
+    pub info: String,
+    /// How the block is tested, if it's Rust code.
+    pub attrs: Option<CodeBlockAttrs>,
+    /// The code, without the lines hidden from the documentation in Rust code.
+    pub code: String,
+    /// The lines of [`Item::docs`] the block starts and ends on, including its fences, counting
+    /// from 1.
+    pub first_line: usize,
+    pub last_line: usize,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Span {
+    /// The path to the source file for this span relative to the path `rustdoc` was invoked with.
+    pub filename: PathBuf,
+    /// Zero indexed Line and Column of the first character of the `Span`
+    pub begin: (usize, usize),
+    /// Zero indexed Line and Column of the last character of the `Span`
+    pub end: (usize, usize),
+}
+
+/// A structured `#[cfg(...)]` predicate.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Cfg {
+    /// Accepts all configurations.
+    True,
+    /// Denies all configurations.
+    False,
+    /// A single configuration option, e.g. `unix` (which has no `value`) or
+    /// `target_os = "linux"`.
+    KeyValue { key: String, value: Option<String> },
+    /// `not(...)`
+    Not(Box<Cfg>),
+    /// `any(...)`
+    Any(Vec<Cfg>),
+    /// `all(...)`
+    All(Vec<Cfg>),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Deprecation {
+    pub since: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EffectiveVisibility {
+    /// Not usable from other crates.
+    Private,
+    /// Not nameable from other crates, but part of the public API, like a type in a private
+    /// module that a public function returns.
+    Reachable,
+    /// Nameable from other crates, but only through a `pub use` re-export.
+    Exported,
+    /// Nameable from other crates through the path it's defined at.
+    Public,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StripReason {
+    /// The item isn't public.
+    Private,
+    /// The item is `#[doc(hidden)]`.
+    Hidden,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    Public,
+    /// For the most part items are private by default. The exceptions are associated items of
+    /// public traits and variants of public enums.
+    Default,
+    Crate,
+    /// For `pub(in path)` visibility. `parent` is the module it's restricted to and `path` is how
+    /// that module was referenced (like `"super::super"` or `"crate::foo::bar"`).
+    Restricted {
+        parent: Id,
+        path: String,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GenericArgs {
+    /// <'a, 32, B: Copy, C = u32>
+    AngleBracketed { args: Vec<GenericArg>, bindings: Vec<TypeBinding> },
+    /// Fn(A, B) -> C
+    Parenthesized { inputs: Vec<Type>, output: Option<Type> },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GenericArg {
+    Lifetime(String),
+    Type(Type),
+    Const(Constant),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Constant {
+    #[serde(rename = "type")]
+    pub type_: Type,
+    pub expr: String,
+    pub value: Option<String>,
+    pub is_literal: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TypeBinding {
+    pub name: String,
+    pub binding: TypeBindingKind,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeBindingKind {
+    Equality(Type),
+    Constraint(Vec<GenericBound>),
+}
+
+/// An opaque identifier for an item. IDs are derived from the item's def path rather than the
+/// order the compiler happened to see it in, so the same item has the same ID in two dumps of the
+/// crate as long as it wasn't moved or renamed. The def path includes the name and
+/// [disambiguator](ExternalCrate::disambiguator) of the item's crate, so an external item also has
+/// the same ID in the outputs of all the crates referring to it.
+///
+/// With `--json-compact-ids`, IDs are sequential integers instead. The IDs they replace are then
+/// written to `<crate>.ids.json` as an array indexed by the compact ID.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Id(pub String);
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemKind {
+    Module,
+    ExternCrate,
+    Import,
+    Struct,
+    StructField,
+    Union,
+    Enum,
+    Variant,
+    Function,
+    Typedef,
+    OpaqueTy,
+    Constant,
+    Trait,
+    TraitAlias,
+    Method,
+    Impl,
+    Static,
+    ForeignType,
+    Macro,
+    DeclMacro,
+    ProcAttribute,
+    ProcDerive,
+    AssocConst,
+    AssocType,
+    Primitive,
+    Keyword,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ItemEnum {
+    ModuleItem(Module),
+    ExternCrateItem {
+        name: String,
+        rename: Option<String>,
+        /// The number of the crate, as in [`Crate::external_crates`].
+        crate_id: u32,
+        /// Whether the crate is re-exported with `pub extern crate`.
+        is_pub: bool,
+        /// `Some` if the crate has `#[macro_use]`, with the names of the macros the attribute is
+        /// restricted to, if any.
+        macro_use: Option<Vec<String>>,
+    },
+    ImportItem(Import),
+
+    StructItem(Struct),
+    UnionItem(Union),
+    StructFieldItem(Type),
+    EnumItem(Enum),
+    VariantItem(Variant),
+
+    FunctionItem(Function),
+
+    TraitItem(Trait),
+    TraitAliasItem(TraitAlias),
+    MethodItem(Method),
+    ImplItem(Impl),
+
+    TypedefItem(Typedef),
+    OpaqueTyItem(OpaqueTy),
+    ConstantItem(Constant),
+
+    StaticItem(Static),
+
+    /// `type`s from an extern block
+    ForeignTypeItem,
+
+    /// Declarative macro_rules! macro
+    MacroItem(Macro),
+    /// Declarative macro defined with the `macro` keyword. Unlike `macro_rules!` macros, these
+    /// have a visibility and are found at the path of the module they're defined in.
+    DeclMacroItem(Macro),
+    ProcMacroItem(ProcMacro),
+
+    /// A primitive type documented with `#[doc(primitive)]`
+    PrimitiveItem(Primitive),
+
+    /// A keyword documented with `#[doc(keyword)]`
+    KeywordItem(String),
+
+    AssocConstItem {
+        #[serde(rename = "type")]
+        type_: Type,
+        /// e.g. `const X: usize = 5;`
+        default: Option<String>,
+        /// The value of `default`, if it could be evaluated. This is only done when it doesn't
+        /// depend on generic parameters, so never for the constants of traits.
+        value: Option<String>,
+    },
+    AssocTypeItem {
+        bounds: Vec<GenericBound>,
+        /// e.g. `type X = usize;`
+        default: Option<Type>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Module {
+    pub is_crate: bool,
+    pub items: Vec<Id>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Struct {
+    pub struct_type: StructType,
+    pub generics: Generics,
+    pub fields_stripped: bool,
+    pub fields: Vec<Id>,
+    pub impls: Vec<Id>,
+    /// Whether the struct is `#[non_exhaustive]`, so that it can't be built outside of its crate.
+    pub is_non_exhaustive: bool,
+    /// Set if the struct is `#[must_use]`.
+    pub must_use: Option<MustUse>,
+    /// The hints of its `#[repr]` attributes, empty for the default representation.
+    pub repr: Vec<Repr>,
+    /// The names of the traits implemented with `#[derive]`, such as `Clone`. Their impls are
+    /// also in `impls`, marked with [`Impl::is_derived`].
+    pub derived: Vec<String>,
+    /// Only set with `--json-type-layout`, for types without type or const parameters.
+    pub layout: Option<TypeLayout>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Union {
+    pub generics: Generics,
+    pub fields_stripped: bool,
+    pub fields: Vec<Id>,
+    pub impls: Vec<Id>,
+    /// Set if the union is `#[must_use]`.
+    pub must_use: Option<MustUse>,
+    /// The hints of its `#[repr]` attributes, empty for the default representation.
+    pub repr: Vec<Repr>,
+    /// The names of the traits implemented with `#[derive]`, such as `Clone`. Their impls are
+    /// also in `impls`, marked with [`Impl::is_derived`].
+    pub derived: Vec<String>,
+    /// Only set with `--json-type-layout`, for types without type or const parameters.
+    pub layout: Option<TypeLayout>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Enum {
+    pub generics: Generics,
+    pub variants_stripped: bool,
+    pub variants: Vec<Id>,
+    pub impls: Vec<Id>,
+    /// Whether the enum is `#[non_exhaustive]`, so that matching on it outside of its crate
+    /// needs a wildcard arm.
+    pub is_non_exhaustive: bool,
+    /// Set if the enum is `#[must_use]`.
+    pub must_use: Option<MustUse>,
+    /// The hints of its `#[repr]` attributes, empty for the default representation.
+    pub repr: Vec<Repr>,
+    /// The names of the traits implemented with `#[derive]`, such as `Clone`. Their impls are
+    /// also in `impls`, marked with [`Impl::is_derived`].
+    pub derived: Vec<String>,
+    /// Only set with `--json-type-layout`, for types without type or const parameters.
+    pub layout: Option<TypeLayout>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Variant {
+    pub kind: VariantKind,
+    /// Whether the variant is `#[non_exhaustive]`, so that it can't be built outside of its
+    /// crate.
+    pub is_non_exhaustive: bool,
+    /// Only set for the variants of enums without fields in any variant, such as C-like enums.
+    pub discriminant: Option<Discriminant>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Discriminant {
+    /// The expression given for the discriminant, if it's explicit (as in `A = 1 << 2`).
+    pub expr: Option<String>,
+    /// The value of the discriminant in decimal, if it could be evaluated. This is a string
+    /// because it may not fit in the integers of JSON parsers.
+    pub value: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "variant_kind", content = "variant_inner")]
+pub enum VariantKind {
+    Plain,
+    /// The IDs of the fields, which are `struct_field` items named by their index.
+    Tuple(Vec<Id>),
+    Struct(Vec<Id>),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StructType {
+    Plain,
+    Tuple,
+    Unit,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TypeLayout {
+    /// The size of the type in bytes.
+    pub size: u64,
+    /// The alignment of the type in bytes.
+    pub align: u64,
+    /// The offset of each field of a struct or union in bytes. Empty for enums.
+    pub field_offsets: BTreeMap<Id, u64>,
+    /// The number of invalid values of the largest niche of the type, which enums containing it
+    /// can use to store their discriminant without taking more space. This is a string because
+    /// it may not fit in the integers of JSON parsers.
+    pub niche_values: Option<String>,
+}
+
+/// A hint of a `#[repr]` attribute, which changes the layout of a type.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Repr {
+    C,
+    Transparent,
+    Simd,
+    /// `packed(n)`, where `packed` alone is `packed(1)`.
+    Packed(u64),
+    Align(u64),
+    /// An integer type, such as `u8`, used for the discriminant of an enum.
+    Int(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Function {
+    pub decl: FnDecl,
+    pub generics: Generics,
+    pub header: FnHeader,
+    /// Set if the function is `#[must_use]`.
+    pub must_use: Option<MustUse>,
+    pub linkage: Linkage,
+}
+
+/// The attributes of a function or static that decide the symbol it's linked under.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Linkage {
+    /// Whether the item is `#[no_mangle]`, in which case its name is the symbol.
+    pub no_mangle: bool,
+    /// The symbol given with `#[export_name = "..."]`.
+    pub export_name: Option<String>,
+    /// The symbol of a foreign item given with `#[link_name = "..."]`.
+    pub link_name: Option<String>,
+    /// The section given with `#[link_section = "..."]`.
+    pub link_section: Option<String>,
+}
+
+/// The qualifiers of a function, written before `fn`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FnHeader {
+    pub is_unsafe: bool,
+    pub is_const: bool,
+    /// Whether the function is `async`, in which case the `output` of its [`FnDecl`] is the
+    /// return type as written rather than the `impl Future` the function actually returns.
+    pub is_async: bool,
+    pub abi: Abi,
+}
+
+/// The ABI of a function, from its `extern "abi"`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Abi {
+    /// The ABI of functions without an `extern`.
+    Rust,
+    /// `extern "C"`, also written `extern`. `unwind` is set for `"C-unwind"`, which this compiler
+    /// doesn't support yet.
+    C {
+        unwind: bool,
+    },
+    /// `extern "system"`. `unwind` is set for `"system-unwind"`, which this compiler doesn't
+    /// support yet.
+    System {
+        unwind: bool,
+    },
+    Cdecl,
+    /// Any other ABI, by the name it's written with.
+    Other(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Method {
+    pub decl: FnDecl,
+    pub generics: Generics,
+    pub header: FnHeader,
+    pub has_body: bool,
+    /// Whether the method is `default` in an impl, so that more specialized impls can override
+    /// it.
+    pub is_default: bool,
+    /// Set if the method is `#[must_use]`.
+    pub must_use: Option<MustUse>,
+    /// How the method takes `self`, which is also the first of its `decl.inputs`. `None` for
+    /// associated functions without a `self` parameter.
+    pub receiver: Option<Receiver>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Receiver {
+    /// `self` or `mut self`.
+    Value,
+    /// `&self`, `&'a mut self`, etc.
+    Borrowed { lifetime: Option<String>, mutable: bool },
+    /// `self: Type`, as in `self: Box<Self>`.
+    Explicit(Type),
+}
+
+/// A `#[must_use]` attribute. This is an object rather than an optional string so that an
+/// attribute without a message can be told apart from no attribute at all.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MustUse {
+    /// The message of `#[must_use = "..."]`.
+    pub message: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Generics {
+    pub params: Vec<GenericParamDef>,
+    pub where_predicates: Vec<WherePredicate>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GenericParamDef {
+    /// The ID of type and const parameters, which can be used to tell apart parameters of the
+    /// same name, like those of an impl and one of its methods. Lifetimes don't have one.
+    pub id: Option<Id>,
+    pub name: String,
+    pub kind: GenericParamDefKind,
+    /// The variance inferred by the compiler. Only set for the parameters of structs, enums and
+    /// unions.
+    pub variance: Option<Variance>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Variance {
+    Covariant,
+    Invariant,
+    Contravariant,
+    /// The parameter isn't used, which is only allowed for lifetimes.
+    Bivariant,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GenericParamDefKind {
+    Lifetime,
+    Type { bounds: Vec<GenericBound>, default: Option<Type> },
+    // TODO: const parameters can't have defaults in this compiler yet. Once they can, this should
+    // become `Const { ty, default }` with the default as written and its value when it can be
+    // evaluated, like `Constant`.
+    Const(Type),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WherePredicate {
+    BoundPredicate { ty: Type, bounds: Vec<GenericBound> },
+    RegionPredicate { lifetime: String, bounds: Vec<GenericBound> },
+    EqPredicate { lhs: Type, rhs: Type },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GenericBound {
+    TraitBound {
+        #[serde(rename = "trait")]
+        trait_: Type,
+        /// Used for HRTBs
+        generic_params: Vec<GenericParamDef>,
+        modifier: TraitBoundModifier,
+    },
+    Outlives(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TraitBoundModifier {
+    None,
+    Maybe,
+    MaybeConst,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "kind", content = "inner")]
+pub enum Type {
+    /// Structs, enums, and traits
+    ResolvedPath { name: String, id: Id, args: Option<Box<GenericArgs>> },
+    /// `dyn Trait + Send + 'a`
+    DynTrait(DynTrait),
+    /// Parameterized types
+    Generic(String),
+    /// Fixed-size numeric types (plus int/usize/float), char, arrays, slices, and tuples
+    Primitive(String),
+    /// `extern "ABI" fn`
+    FunctionPointer(Box<FunctionPointer>),
+    /// `(String, u32, Box<usize>)`
+    Tuple(Vec<Type>),
+    /// `[u32]`
+    Slice(Box<Type>),
+    /// [u32; 15]
+    Array {
+        #[serde(rename = "type")]
+        type_: Box<Type>,
+        /// The length as written, or its value if it could be evaluated.
+        len: String,
+        /// The value of the length, unless it depends on generic parameters. Array lengths are
+        /// `usize`s of the target, and no target has a `usize` wider than 64 bits, so this always
+        /// fits.
+        value: Option<u64>,
+    },
+    /// `impl TraitA + TraitB + ...`
+    ImplTrait { bounds: Vec<GenericBound>, position: ImplTraitPosition },
+    /// `!`
+    Never,
+    /// `_`
+    Infer,
+    /// `*mut u32`, `*u8`, etc.
+    RawPointer {
+        mutable: bool,
+        #[serde(rename = "type")]
+        type_: Box<Type>,
+    },
+    /// `&'a mut String`, `&str`, etc.
+    BorrowedRef {
+        lifetime: Option<String>,
+        mutable: bool,
+        #[serde(rename = "type")]
+        type_: Box<Type>,
+    },
+    /// `<Type as Trait>::Name` or associated types like `T::Item` where `T: Iterator`
+    QualifiedPath {
+        name: String,
+        /// The generic arguments of the associated type, for generic associated types.
+        args: Option<Box<GenericArgs>>,
+        self_type: Box<Type>,
+        #[serde(rename = "trait")]
+        trait_: Box<Type>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DynTrait {
+    /// The traits of the trait object. The first one is the principal trait, and the others are
+    /// auto traits like `Send`.
+    pub traits: Vec<PolyTrait>,
+    /// The lifetime bound of the trait object, like `'a` in `dyn Trait + 'a`, if it's written.
+    pub lifetime: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PolyTrait {
+    #[serde(rename = "trait")]
+    pub trait_: Type,
+    /// Used for HRTBs
+    pub generic_params: Vec<GenericParamDef>,
+}
+
+/// Where an `impl Trait` type was written, which decides what it stands for.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImplTraitPosition {
+    /// The type of a function argument, which makes the function generic over it like a type
+    /// parameter that can't be named with a turbofish.
+    Argument,
+    /// The return type of a function, which is a type picked by the function.
+    Return,
+    /// Anywhere else, like type aliases.
+    Other,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FunctionPointer {
+    pub is_unsafe: bool,
+    pub generic_params: Vec<GenericParamDef>,
+    pub decl: FnDecl,
+    pub abi: Abi,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FnDecl {
+    pub inputs: Vec<(String, Type)>,
+    pub output: Option<Type>,
+    pub c_variadic: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Trait {
+    pub is_auto: bool,
+    /// Whether the trait can be used as a trait object (`dyn Trait`).
+    pub is_object_safe: bool,
+    /// Whether one of the supertraits of the trait isn't public, which is commonly done to keep
+    /// other crates from implementing it (the "sealed trait" pattern).
+    pub is_sealed: bool,
+    pub is_unsafe: bool,
+    pub items: Vec<Id>,
+    /// The items of `items` that implementors have to define: methods without a body, and
+    /// associated types and constants without a default.
+    pub required_items: Vec<Id>,
+    /// The items of `items` that have a default, which implementors may override.
+    pub provided_items: Vec<Id>,
+    pub generics: Generics,
+    pub bounds: Vec<GenericBound>,
+    /// Every trait this one inherits from, directly or through other supertraits, each once.
+    /// Unlike `bounds`, these are resolved: traits of other crates can be found in
+    /// [`Crate::paths`].
+    pub supertraits: Vec<Id>,
+    pub implementors: Vec<Id>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TraitAlias {
+    pub generics: Generics,
+    pub params: Vec<GenericBound>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Impl {
+    pub is_unsafe: bool,
+    /// Whether this is an `impl const Trait`. This is only known for the impls of the local
+    /// crate.
+    pub is_const: bool,
+    pub generics: Generics,
+    pub provided_trait_methods: Vec<String>,
+    #[serde(rename = "trait")]
+    pub trait_: Option<Type>,
+    #[serde(rename = "for")]
+    pub for_: Type,
+    pub items: Vec<Id>,
+    pub negative: bool,
+    pub synthetic: bool,
+    pub blanket_impl: Option<Type>,
+    /// Whether the impl was generated by one of the built-in derives, which mark their impls
+    /// `#[automatically_derived]`.
+    pub is_derived: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct Import {
+    /// The full path being imported.
+    pub span: String,
+    /// May be different from the last segment of `source` when renaming imports:
+    /// `use source as name;`
+    pub name: String,
+    /// The ID of the item being imported. This is where the item is defined, even if `span` names
+    /// a re-export of it.
+    pub id: Option<Id>, // FIXME is this actually ever None?
+    /// Whether this import uses a glob: `use source::*;`
+    pub glob: bool,
+    /// Whether rustdoc inlined the imported items (following `#[doc(inline)]`,
+    /// `#[doc(no_inline)]`, and whether they're from another crate), in which case the importing
+    /// module lists them in its `items` instead of this import.
+    pub inlined: bool,
+    /// The modules re-exporting the imported item that `span` goes through to reach `id`, in
+    /// order. This stops at the first module of another crate, as where their re-exports come
+    /// from isn't known, and is empty for globs.
+    pub reexports: Vec<Id>,
+    /// For globs, the public items of the module (or the variants of the enum) being imported.
+    /// Those whose names the importing module defines itself aren't brought into scope.
+    pub items: Option<Vec<Id>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Macro {
+    /// The signature of the macro, with the transcribers of its rules elided.
+    pub source: String,
+    pub rules: Vec<MacroRule>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MacroRule {
+    pub matcher: Vec<MacroMatcher>,
+    /// Whether the rule expands to anything, `false` for rules like `() => {}`.
+    pub has_transcriber: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MacroMatcher {
+    /// A token matched literally, e.g. `,` or `=>`.
+    Token(String),
+    /// A metavariable, `$name:kind`.
+    Fragment { name: String, kind: String },
+    /// `$(...) separator op`, where `op` is one of `*`, `+` or `?`.
+    Repetition { matchers: Vec<MacroMatcher>, separator: Option<String>, op: String },
+    /// Matchers between a pair of delimiters.
+    Delimited { delimiter: MacroDelimiter, matchers: Vec<MacroMatcher> },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MacroDelimiter {
+    Parenthesis,
+    Bracket,
+    Brace,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProcMacro {
+    pub kind: MacroKind,
+    /// For derive macros, the name of the trait in `#[derive(...)]`, which is also the name of
+    /// the item.
+    pub derived_trait: Option<String>,
+    pub helpers: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MacroKind {
+    /// A bang macro `foo!()`.
+    Bang,
+    /// An attribute macro `#[foo]`.
+    Attr,
+    /// A derive macro `#[derive(Foo)]`
+    Derive,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Primitive {
+    /// The name of the primitive type, e.g. `"u8"` or `"slice"`.
+    pub name: String,
+    pub impls: Vec<Id>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Typedef {
+    #[serde(rename = "type")]
+    pub type_: Type,
+    pub generics: Generics,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OpaqueTy {
+    pub bounds: Vec<GenericBound>,
+    pub generics: Generics,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Static {
+    #[serde(rename = "type")]
+    pub type_: Type,
+    pub mutable: bool,
+    pub expr: String,
+    pub linkage: Linkage,
+}
@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --output-format json --document-private-items --json-api-surface
+
+pub struct Foo;
@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --emit-schema
+
+pub struct Foo;
@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --json-api-surface
+
+pub struct Foo;
@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --json-compact-ids
+
+pub struct Foo;
@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --output-format json --json-compact-ids --json-externs-file
+
+pub struct Foo;
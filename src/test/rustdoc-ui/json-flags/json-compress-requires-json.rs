@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --json-compress gzip
+
+pub struct Foo;
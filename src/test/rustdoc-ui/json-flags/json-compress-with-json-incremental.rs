@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --output-format json --json-compress gzip --json-incremental
+
+pub struct Foo;
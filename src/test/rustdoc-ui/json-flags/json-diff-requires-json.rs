@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --json-diff old.json
+
+pub struct Foo;
@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --json-docs-ast
+
+pub struct Foo;
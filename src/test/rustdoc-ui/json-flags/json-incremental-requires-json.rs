@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --json-incremental
+
+pub struct Foo;
@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --output-format lsif --json-incremental
+
+pub struct Foo;
@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --output-format msgpack --json-incremental
+
+pub struct Foo;
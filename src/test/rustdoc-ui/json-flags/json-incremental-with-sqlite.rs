@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --output-format sqlite --json-incremental
+
+pub struct Foo;
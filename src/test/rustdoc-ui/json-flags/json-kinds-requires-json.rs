@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --json-kinds struct
+
+pub struct Foo;
@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --output-format json --json-lines --json-diff old.json
+
+pub struct Foo;
@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --output-format json --json-lines --json-ffi-only
+
+pub struct Foo;
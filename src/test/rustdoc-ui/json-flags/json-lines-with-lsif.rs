@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --output-format lsif --json-lines
+
+pub struct Foo;
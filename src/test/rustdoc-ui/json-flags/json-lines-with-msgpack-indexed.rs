@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --output-format msgpack-indexed --json-lines
+
+pub struct Foo;
@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --json-no-docs
+
+pub struct Foo;
@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --json-only-path foo
+
+pub struct Foo;
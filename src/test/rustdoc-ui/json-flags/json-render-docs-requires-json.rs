@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --json-render-docs
+
+pub struct Foo;
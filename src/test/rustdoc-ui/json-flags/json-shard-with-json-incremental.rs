@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --output-format json --json-shard --json-incremental
+
+pub struct Foo;
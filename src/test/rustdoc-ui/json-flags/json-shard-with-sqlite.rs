@@ -0,0 +1,3 @@
+// compile-flags:-Z unstable-options --output-format sqlite --json-shard
+
+pub struct Foo;